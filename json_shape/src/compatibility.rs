@@ -0,0 +1,330 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    IsSubset,
+    diff::{ChangeKind, ShapeChange},
+    value::Value,
+};
+
+/// Compatibility mode required between versions of a [`JsonShape`](crate::JsonShape), like the modes of a schema registry.
+///
+/// - `Backward`: readers of a version can read payloads of the previous version, `old` is a subset of `new`
+/// - `Forward`: readers of the previous version can read payloads of a version, `new` is a subset of `old`
+/// - `Full`: both `Backward` and `Forward`
+///
+/// Transitive modes check each version against every earlier version, instead of only its predecessor.
+///
+/// ```rust
+/// use std::str::FromStr;
+///
+/// use json_shape::{Compatibility, JsonShape};
+///
+/// let versions = [
+///     JsonShape::from_str(r#"{"id": 1}"#).unwrap(),
+///     JsonShape::from_sources(&[r#"{"id": 1, "name": "a"}"#.to_string(), r#"{"id": 2}"#.to_string()]).unwrap(),
+///     JsonShape::from_str(r#"{"id": 1, "name": "a"}"#).unwrap(),
+/// ];
+///
+/// assert!(Compatibility::Backward.check(&versions[..2]).is_ok());
+/// // `name` became required, version 2 can't read payloads of version 1 without it
+/// let incompatibilities = Compatibility::Backward.check(&versions).unwrap_err();
+/// assert_eq!(
+///     incompatibilities[0].to_string(),
+///     "version 2 breaks backward compatibility with version 1 at $.name: became required"
+/// );
+/// // Readers of version 1 know `name`, readers of version 0 don't
+/// assert!(Compatibility::Forward.check(&versions[1..]).is_ok());
+/// assert_eq!(Compatibility::ForwardTransitive.check(&versions).unwrap_err().len(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Compatibility {
+    /// Readers of a version can read payloads of its predecessor
+    Backward,
+    /// Readers of a version can read payloads of every earlier version
+    BackwardTransitive,
+    /// Readers of the predecessor of a version can read its payloads
+    Forward,
+    /// Readers of every earlier version can read the payloads of a version
+    ForwardTransitive,
+    /// Both [`Compatibility::Backward`] and [`Compatibility::Forward`]
+    Full,
+    /// Both [`Compatibility::BackwardTransitive`] and [`Compatibility::ForwardTransitive`]
+    FullTransitive,
+}
+
+/// A change breaking the [`Compatibility`] between two versions of a [`JsonShape`](crate::JsonShape).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Incompatibility {
+    /// Index of the earlier version
+    pub old: usize,
+    /// Index of the later version
+    pub new: usize,
+    /// Broken direction, either [`Compatibility::Backward`] or [`Compatibility::Forward`]
+    pub direction: Compatibility,
+    /// Change from the earlier version to the later version breaking compatibility, with its path
+    pub change: ShapeChange,
+}
+
+impl Compatibility {
+    /// Checks the compatibility of `versions`, ordered from the oldest to the newest, returning every [`Incompatibility`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with every [`Incompatibility`] found if any pair of versions is incompatible.
+    pub fn check(self, versions: &[Value]) -> Result<(), Vec<Incompatibility>> {
+        let mut incompatibilities = Vec::new();
+        for (new, new_shape) in versions.iter().enumerate() {
+            let earlier = if self.is_transitive() {
+                0..new
+            } else {
+                new.saturating_sub(1)..new
+            };
+            for old in earlier {
+                let old_shape = &versions[old];
+                if self.is_backward() {
+                    incompatibilities.extend(
+                        breaking_changes(old_shape, new_shape, Self::Backward).map(|change| {
+                            Incompatibility {
+                                old,
+                                new,
+                                direction: Self::Backward,
+                                change,
+                            }
+                        }),
+                    );
+                }
+                if self.is_forward() {
+                    incompatibilities.extend(
+                        breaking_changes(old_shape, new_shape, Self::Forward).map(|change| {
+                            Incompatibility {
+                                old,
+                                new,
+                                direction: Self::Forward,
+                                change,
+                            }
+                        }),
+                    );
+                }
+            }
+        }
+
+        if incompatibilities.is_empty() {
+            Ok(())
+        } else {
+            Err(incompatibilities)
+        }
+    }
+
+    /// Is each version checked against every earlier version?
+    #[must_use]
+    pub const fn is_transitive(self) -> bool {
+        matches!(
+            self,
+            Self::BackwardTransitive | Self::ForwardTransitive | Self::FullTransitive
+        )
+    }
+
+    /// Are payloads of earlier versions required to be readable by later versions?
+    #[must_use]
+    pub const fn is_backward(self) -> bool {
+        !matches!(self, Self::Forward | Self::ForwardTransitive)
+    }
+
+    /// Are payloads of later versions required to be readable by earlier versions?
+    #[must_use]
+    pub const fn is_forward(self) -> bool {
+        !matches!(self, Self::Backward | Self::BackwardTransitive)
+    }
+}
+
+/// Changes from `old` to `new` breaking `direction`, decided by [`IsSubset`] and explained by [`Value::diff`].
+/// When no single change explains it, the whole shape is reported as changed.
+fn breaking_changes(
+    old: &Value,
+    new: &Value,
+    direction: Compatibility,
+) -> impl Iterator<Item = ShapeChange> {
+    let is_compatible = match direction {
+        Compatibility::Forward | Compatibility::ForwardTransitive => new.is_subset(old),
+        _ => old.is_subset(new),
+    };
+    let mut changes = if is_compatible {
+        Vec::new()
+    } else {
+        old.diff(new)
+            .into_iter()
+            .filter(|change| breaks(&change.kind, direction))
+            .collect::<Vec<_>>()
+    };
+    if !is_compatible && changes.is_empty() {
+        changes.push(ShapeChange {
+            path: "$".to_string(),
+            kind: ChangeKind::TypeChanged {
+                old: old.clone(),
+                new: new.clone(),
+            },
+        });
+    }
+    changes.into_iter()
+}
+
+/// Backward compatibility breaks when new readers reject old payloads, forward compatibility when old readers reject new payloads.
+const fn breaks(kind: &ChangeKind, direction: Compatibility) -> bool {
+    let backward = matches!(
+        direction,
+        Compatibility::Backward | Compatibility::BackwardTransitive
+    );
+    match kind {
+        ChangeKind::TypeChanged { .. }
+        | ChangeKind::TupleLengthChanged { .. }
+        | ChangeKind::FieldAdded {
+            optional: false, ..
+        }
+        | ChangeKind::FieldRemoved {
+            optional: false, ..
+        } => true,
+        ChangeKind::FieldRemoved { .. }
+        | ChangeKind::BecameRequired
+        | ChangeKind::BecameNonNullable
+        | ChangeKind::TypeNarrowed { .. }
+        | ChangeKind::VariantRemoved(_) => backward,
+        ChangeKind::FieldAdded { .. }
+        | ChangeKind::BecameOptional
+        | ChangeKind::BecameNullable
+        | ChangeKind::TypeWidened { .. }
+        | ChangeKind::VariantAdded(_) => !backward,
+    }
+}
+
+impl Display for Compatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Backward => write!(f, "backward"),
+            Self::BackwardTransitive => write!(f, "transitive backward"),
+            Self::Forward => write!(f, "forward"),
+            Self::ForwardTransitive => write!(f, "transitive forward"),
+            Self::Full => write!(f, "full"),
+            Self::FullTransitive => write!(f, "transitive full"),
+        }
+    }
+}
+
+impl Display for Incompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "version {} breaks {} compatibility with version {} at {}",
+            self.new, self.direction, self.old, self.change
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{InferenceOptions, test_utils::shape};
+
+    fn versions(sources: &[&[&str]]) -> Vec<Value> {
+        sources
+            .iter()
+            .map(|sources| shape(sources, &InferenceOptions::default()))
+            .collect()
+    }
+
+    fn incompatibilities(compatibility: Compatibility, versions: &[Value]) -> Vec<String> {
+        compatibility
+            .check(versions)
+            .err()
+            .unwrap_or_default()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn adding_an_optional_key_is_backward_but_not_forward_compatible() {
+        let versions = versions(&[
+            &[r#"{"id": 1}"#],
+            &[r#"{"id": 1, "email": "a"}"#, r#"{"id": 2}"#],
+        ]);
+
+        assert_eq!(Compatibility::Backward.check(&versions), Ok(()));
+        assert_eq!(
+            incompatibilities(Compatibility::Forward, &versions),
+            [
+                "version 1 breaks forward compatibility with version 0 at $.email: optional field added `String`"
+            ]
+        );
+        assert_eq!(
+            incompatibilities(Compatibility::Full, &versions),
+            incompatibilities(Compatibility::Forward, &versions)
+        );
+    }
+
+    #[test]
+    fn narrowing_a_type_is_forward_but_not_backward_compatible() {
+        let versions = versions(&[&[r#"{"id": 1.5}"#, r#"{"id": null}"#], &[r#"{"id": 1}"#]]);
+
+        assert_eq!(Compatibility::Forward.check(&versions), Ok(()));
+        assert_eq!(
+            incompatibilities(Compatibility::Backward, &versions),
+            [
+                "version 1 breaks backward compatibility with version 0 at $.id: became non nullable",
+                "version 1 breaks backward compatibility with version 0 at $.id: type narrowed `Number` => `Integer<unsigned>`"
+            ]
+        );
+    }
+
+    #[test]
+    fn changing_a_type_breaks_full_compatibility_both_ways() {
+        let versions = versions(&[&[r#"[1, "a"]"#], &[r#"[1, "a", true]"#]]);
+
+        let incompatibilities = Compatibility::Full.check(&versions).unwrap_err();
+        assert_eq!(incompatibilities.len(), 2);
+        assert_eq!(incompatibilities[0].direction, Compatibility::Backward);
+        assert_eq!(incompatibilities[1].direction, Compatibility::Forward);
+        assert_eq!(
+            incompatibilities[0].change.kind,
+            ChangeKind::TupleLengthChanged { old: 2, new: 3 }
+        );
+    }
+
+    #[test]
+    fn transitive_modes_check_every_earlier_version() {
+        let versions = versions(&[
+            &[r#"{"a": 1}"#],
+            &[r#"{"a": 1}"#, r#"{"a": 1, "b": 2}"#],
+            &[r#"{"a": 1, "b": 2}"#],
+        ]);
+
+        assert_eq!(
+            incompatibilities(Compatibility::Backward, &versions),
+            ["version 2 breaks backward compatibility with version 1 at $.b: became required"]
+        );
+        assert_eq!(
+            incompatibilities(Compatibility::BackwardTransitive, &versions),
+            [
+                "version 2 breaks backward compatibility with version 0 at $.b: field added `Integer<unsigned>`",
+                "version 2 breaks backward compatibility with version 1 at $.b: became required"
+            ]
+        );
+        assert_eq!(Compatibility::FullTransitive.check(&versions[..1]), Ok(()));
+    }
+
+    #[test]
+    fn changing_the_root_type_is_reported_at_the_root() {
+        let old = Value::from_str(r#"{"a": 1}"#).unwrap();
+        let new = Value::from_str("[1]").unwrap();
+
+        assert_eq!(
+            incompatibilities(Compatibility::Forward, &[old, new]),
+            [
+                "version 1 breaks forward compatibility with version 0 at $: type changed `Object{a: Integer<unsigned>}` => `Array<Integer<unsigned>>`"
+            ]
+        );
+    }
+}
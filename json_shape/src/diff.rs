@@ -0,0 +1,554 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    IsSubset,
+    shape::{merger::normalize, recursion::rename},
+    statistics::key_path,
+    value::{LiteralValue, Value, absent_keys},
+};
+
+/// A single change between an old and a new [`JsonShape`](crate::JsonShape), located by its path.
+///
+/// Paths are written as `JSONPath` queries, like the paths of [`ShapeStatistics`](crate::ShapeStatistics),
+/// with `[*]` for array items, `[0]` for tuple elements and `*` for the keys of a [`JsonShape::Map`](crate::JsonShape::Map).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShapeChange {
+    /// Path of the changed value, eg `$.users[*].age`
+    pub path: String,
+    /// What changed at `path`
+    pub kind: ChangeKind,
+}
+
+/// Kind of a [`ShapeChange`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    /// Key added to an object
+    FieldAdded {
+        /// Shape of the new key
+        shape: Value,
+        /// The new key may be absent
+        optional: bool,
+    },
+    /// Key removed from an object
+    FieldRemoved {
+        /// Shape of the old key
+        shape: Value,
+        /// The old key could be absent
+        optional: bool,
+    },
+    /// Object key that may now be absent, eg `name: T` => `name?: T`
+    BecameOptional,
+    /// Object key that must now be present, eg `name?: T` => `name: T`
+    BecameRequired,
+    /// Value that may now be null, eg `T` => `Option<T>`
+    BecameNullable,
+    /// Value that can no longer be null, eg `Option<T>` => `T`
+    BecameNonNullable,
+    /// Type accepting more values than before, eg `Integer<unsigned>` => `Number`
+    TypeWidened {
+        /// Shape before the change
+        old: Value,
+        /// Shape after the change
+        new: Value,
+    },
+    /// Type accepting fewer values than before, eg `String` => `String<uuid>`
+    TypeNarrowed {
+        /// Shape before the change
+        old: Value,
+        /// Shape after the change
+        new: Value,
+    },
+    /// Type replaced by an unrelated one, eg `Number` => `String`
+    TypeChanged {
+        /// Shape before the change
+        old: Value,
+        /// Shape after the change
+        new: Value,
+    },
+    /// Variant added to a [`JsonShape::OneOf`](crate::JsonShape::OneOf) or [`JsonShape::Tagged`](crate::JsonShape::Tagged)
+    VariantAdded(Value),
+    /// Variant removed from a [`JsonShape::OneOf`](crate::JsonShape::OneOf) or [`JsonShape::Tagged`](crate::JsonShape::Tagged)
+    VariantRemoved(Value),
+    /// Number of elements of a [`JsonShape::Tuple`](crate::JsonShape::Tuple) changed
+    TupleLengthChanged {
+        /// Length before the change
+        old: usize,
+        /// Length after the change
+        new: usize,
+    },
+}
+
+impl Display for ShapeChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FieldAdded { shape, optional } => {
+                write!(f, "{}field added `{shape}`", optional_prefix(*optional))
+            }
+            Self::FieldRemoved { shape, optional } => {
+                write!(f, "{}field removed `{shape}`", optional_prefix(*optional))
+            }
+            Self::BecameOptional => write!(f, "became optional"),
+            Self::BecameRequired => write!(f, "became required"),
+            Self::BecameNullable => write!(f, "became nullable"),
+            Self::BecameNonNullable => write!(f, "became non nullable"),
+            Self::TypeWidened { old, new } => write!(f, "type widened `{old}` => `{new}`"),
+            Self::TypeNarrowed { old, new } => write!(f, "type narrowed `{old}` => `{new}`"),
+            Self::TypeChanged { old, new } => write!(f, "type changed `{old}` => `{new}`"),
+            Self::VariantAdded(shape) => write!(f, "variant added `{shape}`"),
+            Self::VariantRemoved(shape) => write!(f, "variant removed `{shape}`"),
+            Self::TupleLengthChanged { old, new } => {
+                write!(f, "tuple length changed {old} => {new}")
+            }
+        }
+    }
+}
+
+const fn optional_prefix(optional: bool) -> &'static str {
+    if optional { "optional " } else { "" }
+}
+
+impl Value {
+    /// Lists the structural changes from this `old` [`JsonShape`](crate::JsonShape) to a `new` one, in path order.
+    /// Equivalent shapes, such as shapes only differing in [`Examples`](crate::Examples), have no changes.
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use json_shape::JsonShape;
+    ///
+    /// let old = JsonShape::from_str(r#"{"id": 1, "name": "a", "tags": ["a"]}"#).unwrap();
+    /// let new = JsonShape::from_sources(&[
+    ///     r#"{"id": 1.5, "tags": ["a", 1], "email": null}"#.to_string(),
+    ///     r#"{"id": 2, "tags": []}"#.to_string(),
+    /// ])
+    /// .unwrap();
+    ///
+    /// let changes = old.diff(&new).iter().map(ToString::to_string).collect::<Vec<_>>();
+    /// assert_eq!(changes, [
+    ///     "$.email: optional field added `Null`",
+    ///     "$.id: type widened `Integer<unsigned>` => `Number`",
+    ///     "$.name: field removed `String`",
+    ///     "$.tags[*]: variant added `Integer<unsigned>`",
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn diff(&self, new: &Self) -> Vec<ShapeChange> {
+        let mut changes = Vec::new();
+        diff(self, new, "$", &mut changes);
+        changes
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn diff(old: &Value, new: &Value, path: &str, changes: &mut Vec<ShapeChange>) {
+    if old == new {
+        return;
+    }
+    let (non_null_old, non_null_new) = (non_null(old), non_null(new));
+    // `String => Null` is not only nullable, the string is gone
+    if non_null_old.is_null() != non_null_new.is_null() {
+        push(changes, path, compare(old, new));
+        return;
+    }
+    match (is_nullable(old), is_nullable(new)) {
+        (false, true) => push(changes, path, ChangeKind::BecameNullable),
+        (true, false) => push(changes, path, ChangeKind::BecameNonNullable),
+        _ => (),
+    }
+    let (old, new) = (non_null_old, non_null_new);
+    match (&old, &new) {
+        (Value::Null, Value::Null) => (),
+        (Value::OneOf { .. }, _) | (_, Value::OneOf { .. }) => {
+            diff_variants(&variants(&old), &variants(&new), path, changes);
+        }
+        (
+            Value::Object {
+                content, absent, ..
+            },
+            Value::Object {
+                content: new_content,
+                absent: new_absent,
+                ..
+            },
+        ) => diff_objects(
+            content,
+            &absent_keys(content, absent.as_ref()),
+            new_content,
+            &absent_keys(new_content, new_absent.as_ref()),
+            path,
+            changes,
+        ),
+        (
+            Value::Array { r#type, length, .. },
+            Value::Array {
+                r#type: new_type,
+                length: new_length,
+                ..
+            },
+        ) => {
+            if length != new_length {
+                push(changes, path, compare(&old, &new));
+            }
+            diff(r#type, new_type, &format!("{path}[*]"), changes);
+        }
+        (Value::Tuple { elements, .. }, Value::Tuple { elements: new, .. }) => {
+            if elements.len() != new.len() {
+                push(
+                    changes,
+                    path,
+                    ChangeKind::TupleLengthChanged {
+                        old: elements.len(),
+                        new: new.len(),
+                    },
+                );
+            }
+            for (index, (old, new)) in elements.iter().zip(new).enumerate() {
+                diff(old, new, &format!("{path}[{index}]"), changes);
+            }
+        }
+        (
+            Value::Map { r#type, key, .. },
+            Value::Map {
+                r#type: new_type,
+                key: new_key,
+                ..
+            },
+        ) => {
+            if key != new_key {
+                push(changes, path, compare(&old, &new));
+            }
+            diff(r#type, new_type, &format!("{path}.*"), changes);
+        }
+        (
+            Value::Tagged { tag, variants, .. },
+            Value::Tagged {
+                tag: new_tag,
+                variants: new_variants,
+                ..
+            },
+        ) if tag == new_tag => {
+            for (value, variant) in variants {
+                match new_variants.get(value) {
+                    Some(new) => diff(variant, new, path, changes),
+                    None => push(
+                        changes,
+                        path,
+                        ChangeKind::VariantRemoved(tagged_variant(tag, value, variant)),
+                    ),
+                }
+            }
+            for (value, variant) in new_variants {
+                if !variants.contains_key(value) {
+                    push(
+                        changes,
+                        path,
+                        ChangeKind::VariantAdded(tagged_variant(tag, value, variant)),
+                    );
+                }
+            }
+        }
+        (
+            Value::Recursive { name, shape },
+            Value::Recursive {
+                name: new_name,
+                shape: new_shape,
+            },
+        ) => diff(
+            shape,
+            &rename((**new_shape).clone(), new_name, name),
+            path,
+            changes,
+        ),
+        (old, new) => {
+            if !(old.is_subset(new) && new.is_subset(old)) {
+                push(changes, path, compare(old, new));
+            }
+        }
+    }
+}
+
+/// Keys are compared in order, removed and added keys being reported with their shapes.
+fn diff_objects(
+    content: &BTreeMap<String, Value>,
+    absent: &BTreeSet<String>,
+    new_content: &BTreeMap<String, Value>,
+    new_absent: &BTreeSet<String>,
+    path: &str,
+    changes: &mut Vec<ShapeChange>,
+) {
+    let keys = content
+        .keys()
+        .chain(new_content.keys())
+        .collect::<BTreeSet<_>>();
+    for key in keys {
+        let path = key_path(path, key);
+        match (content.get(key), new_content.get(key)) {
+            (Some(old), None) => push(
+                changes,
+                &path,
+                ChangeKind::FieldRemoved {
+                    shape: old.clone(),
+                    optional: absent.contains(key),
+                },
+            ),
+            (None, Some(new)) => push(
+                changes,
+                &path,
+                ChangeKind::FieldAdded {
+                    shape: new.clone(),
+                    optional: new_absent.contains(key),
+                },
+            ),
+            (Some(old), Some(new)) => {
+                match (absent.contains(key), new_absent.contains(key)) {
+                    (false, true) => push(changes, &path, ChangeKind::BecameOptional),
+                    (true, false) => push(changes, &path, ChangeKind::BecameRequired),
+                    _ => (),
+                }
+                diff(old, new, &path, changes);
+            }
+            (None, None) => (),
+        }
+    }
+}
+
+/// Pairs the variants of both unions by Json type, so `OneOf[Integer | String] => OneOf[Number | String]`
+/// widens the number variant instead of replacing it.
+fn diff_variants(old: &[Value], new: &[Value], path: &str, changes: &mut Vec<ShapeChange>) {
+    let mut added = new.iter().collect::<Vec<_>>();
+    for variant in old {
+        let paired = added
+            .iter()
+            .position(|new| json_type(new) == json_type(variant));
+        match paired {
+            Some(index) => diff(variant, added.remove(index), path, changes),
+            None => push(changes, path, ChangeKind::VariantRemoved(variant.clone())),
+        }
+    }
+    for variant in added {
+        push(changes, path, ChangeKind::VariantAdded(variant.clone()));
+    }
+}
+
+fn push(changes: &mut Vec<ShapeChange>, path: &str, kind: ChangeKind) {
+    changes.push(ShapeChange {
+        path: path.to_string(),
+        kind,
+    });
+}
+
+fn compare(old: &Value, new: &Value) -> ChangeKind {
+    let (old, new) = (old.clone(), new.clone());
+    if old.is_subset(&new) {
+        ChangeKind::TypeWidened { old, new }
+    } else if new.is_subset(&old) {
+        ChangeKind::TypeNarrowed { old, new }
+    } else {
+        ChangeKind::TypeChanged { old, new }
+    }
+}
+
+/// Variant of a [`Value::Tagged`] as the object it describes, eg `Object{type: Literal["a"], x: T}`.
+fn tagged_variant(tag: &str, value: &str, variant: &Value) -> Value {
+    match variant.clone() {
+        Value::Object {
+            mut content,
+            optional,
+            absent,
+        } => {
+            content.insert(
+                tag.to_string(),
+                Value::Literal {
+                    values: [LiteralValue::String(value.to_string())].into(),
+                    optional: false,
+                },
+            );
+            Value::Object {
+                content,
+                optional,
+                absent,
+            }
+        }
+        variant => variant,
+    }
+}
+
+fn is_nullable(value: &Value) -> bool {
+    value.is_optional()
+        || matches!(value, Value::OneOf { variants, .. } if variants.contains(&Value::Null))
+}
+
+/// The shape without its null values, `Null` itself is kept.
+fn non_null(value: &Value) -> Value {
+    match value {
+        Value::OneOf { variants, .. } => normalize(Value::OneOf {
+            variants: variants
+                .iter()
+                .filter(|variant| !variant.is_null())
+                .cloned()
+                .collect(),
+            optional: false,
+        }),
+        value => value.clone().as_non_optional(),
+    }
+}
+
+fn variants(value: &Value) -> Vec<Value> {
+    match value {
+        Value::OneOf { variants, .. } => variants.iter().cloned().collect(),
+        value => vec![value.clone()],
+    }
+}
+
+/// Json type of the values of a shape, matching the types of [`ShapeStatistics`](crate::ShapeStatistics).
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "Null",
+        Value::Unknown => "Unknown",
+        Value::Bool { .. } => "Boolean",
+        Value::Number { .. } => "Number",
+        Value::Literal { values, .. } if values.iter().all(LiteralValue::is_integer) => "Number",
+        Value::String { .. } | Value::Literal { .. } => "String",
+        Value::Array { .. } | Value::Tuple { .. } => "Array",
+        Value::Object { .. }
+        | Value::Map { .. }
+        | Value::Recursive { .. }
+        | Value::Ref { .. }
+        | Value::Tagged { .. } => "Object",
+        Value::OneOf { .. } => "OneOf",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{InferenceOptions, test_utils::shape};
+
+    fn changes(old: &[&str], new: &[&str], options: &InferenceOptions) -> Vec<String> {
+        shape(old, options)
+            .diff(&shape(new, options))
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn equal_shapes_have_no_changes() {
+        let options = InferenceOptions::default().with_examples(2);
+
+        assert!(changes(&[r#"{"a": [1, "b"]}"#], &[r#"{"a": [2, "c"]}"#], &options).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_optional_and_nullable_keys() {
+        assert_eq!(
+            changes(
+                &[r#"{"a": 1, "b": 2}"#, r#"{"a": 1}"#],
+                &[r#"{"a": null, "b": 2}"#, r#"{"a": 1, "b": 3}"#],
+                &InferenceOptions::default()
+            ),
+            ["$.a: became nullable", "$.b: became required"]
+        );
+    }
+
+    #[test]
+    fn diff_reports_values_becoming_null() {
+        assert_eq!(
+            changes(
+                &[r#"{"a": 1, "b": null}"#],
+                &[r#"{"a": null, "b": 1}"#, r#"{"a": null, "b": null}"#],
+                &InferenceOptions::default()
+            ),
+            [
+                "$.a: type changed `Integer<unsigned>` => `Null`",
+                "$.b: type widened `Null` => `Option<Integer<unsigned>>`",
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_reports_narrowed_and_changed_types() {
+        let options = InferenceOptions::default().with_length_bounds();
+
+        assert_eq!(
+            changes(
+                &[r#"{"id": "a", "n": 1, "v": [1, 2]}"#],
+                &[r#"{"id": "67e55044-10b1-426f-9247-bb680e5fe0c8", "n": "1", "v": [1]}"#],
+                &options
+            ),
+            [
+                "$.id: type changed `String[1..=1]` => `String<uuid>[36..=36]`",
+                "$.n: type changed `Integer<unsigned>` => `String[1..=1]`",
+                "$.v: type changed `Array<Integer<unsigned>>[2..=2]` => `Array<Integer<unsigned>>[1..=1]`",
+            ]
+        );
+        assert_eq!(
+            changes(
+                &[r#"{"v": [1, 2]}"#, r#"{"v": []}"#],
+                &[r#"{"v": [1]}"#],
+                &options
+            ),
+            [
+                "$.v: type narrowed `Array<Integer<unsigned>>[0..=2]` => `Array<Integer<unsigned>>[1..=1]`"
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_pairs_one_of_variants_by_type() {
+        assert_eq!(
+            changes(
+                &[r#"{"a": 1}"#, r#"{"a": "b"}"#, r#"{"a": true}"#],
+                &[r#"{"a": -1}"#, r#"{"a": "b"}"#, r#"{"a": [1]}"#],
+                &InferenceOptions::default()
+            ),
+            [
+                "$.a: variant removed `Boolean`",
+                "$.a: type widened `Integer<unsigned>` => `Integer<signed>`",
+                "$.a: variant added `Array<Integer<unsigned>>`",
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_reports_tuple_lengths_and_elements() {
+        assert_eq!(
+            changes(
+                &[r#"[1, "a"]"#],
+                &[r#"[-1, "a", true]"#],
+                &InferenceOptions::default()
+            ),
+            [
+                "$: tuple length changed 2 => 3",
+                "$[0]: type widened `Integer<unsigned>` => `Integer<signed>`",
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_reports_tagged_variants() {
+        let options = InferenceOptions::default().with_discriminators();
+
+        assert_eq!(
+            changes(
+                &[r#"[{"type": "a", "x": 1}, {"type": "b"}]"#],
+                &[r#"[{"type": "a", "x": 1, "y": 2}, {"type": "c"}]"#],
+                &options
+            ),
+            [
+                "$[*].y: field added `Integer<unsigned>`",
+                r#"$[*]: variant removed `Object{type: Literal["b"]}`"#,
+                r#"$[*]: variant added `Object{type: Literal["c"]}`"#,
+            ]
+        );
+    }
+}
@@ -2,6 +2,12 @@
 
 use std::ops::Range;
 
+use codespan_reporting::{
+    diagnostic::{Diagnostic, Label},
+    files::SimpleFile,
+    term::{self, Config},
+};
+
 use crate::value::Value;
 
 #[derive(Debug, thiserror::Error, PartialEq, Eq)]
@@ -37,8 +43,88 @@ pub enum Error {
     InvalidObjectValue,
     /// Object expected a type but found something unexpected.
     #[error("invalid type `{0}`. Expected `{1}`.")]
-    InvalidObjectValueType(Value, Value),
+    InvalidObjectValueType(Box<Value>, Box<Value>),
+    /// Invalid RFC 9535 `JSONPath` query, returns the reason with the range where it is located.
+    #[error("invalid JSONPath `{path}`: {reason} at {}..{}", span.start, span.end)]
+    InvalidJsonPath {
+        /// Invalid `JSONPath` query
+        path: String,
+        /// Why the query is invalid
+        reason: String,
+        /// Invalid query range
+        span: Range<usize>,
+    },
+    /// Invalid shape notation, returns the reason with the range where it is located.
+    #[error("invalid shape `{shape}`: {reason} at {}..{}", span.start, span.end)]
+    InvalidShape {
+        /// Invalid shape notation
+        shape: String,
+        /// Why the notation is invalid
+        reason: String,
+        /// Invalid notation range
+        span: Range<usize>,
+    },
+    /// Invalid shape interchange document, returns the reason.
+    #[error("invalid shape document: {0}")]
+    InvalidInterchange(String),
+    /// Shape interchange document of a later version than [`INTERCHANGE_VERSION`](crate::INTERCHANGE_VERSION),
+    /// returns its shape read best-effort, which may describe fewer refinements than the document.
+    #[error("shape document of later version {version}, read as `{shape}`")]
+    LaterInterchangeVersion {
+        /// Version of the document
+        version: u64,
+        /// Shape read from the document
+        shape: Box<Value>,
+    },
     /// Not able to merge the two [`JsonShapes`]
     #[error("not able to merge `{0}` with `{1}`.")]
-    CannotMerge(Value, Value),
+    CannotMerge(Box<Value>, Box<Value>),
+}
+
+impl Error {
+    /// Range of the source where the error is located, for invalid JSON, `JSONPath` queries and shape notations.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Self::InvalidJson { span, .. }
+            | Self::InvalidJsonPath { span, .. }
+            | Self::InvalidShape { span, .. } => Some(span.clone()),
+            _ => None,
+        }
+    }
+
+    /// [`Diagnostic`] of this error, labelling its span when known, to be emitted with [`codespan_reporting::term`].
+    #[must_use]
+    pub fn to_diagnostic(&self) -> Diagnostic<()> {
+        let message = match self {
+            Self::InvalidJson { .. } => "invalid JSON".to_string(),
+            Self::InvalidJsonPath { reason, .. } => format!("invalid JSONPath: {reason}"),
+            Self::InvalidShape { reason, .. } => format!("invalid shape: {reason}"),
+            error => error.to_string(),
+        };
+        let labels = self
+            .span()
+            .map(|span| Label::primary((), span))
+            .into_iter()
+            .collect();
+        Diagnostic::error()
+            .with_message(message)
+            .with_labels(labels)
+    }
+
+    /// Renders this error against the `source` it was raised for, named `name`, eg
+    /// ```text
+    /// error: invalid shape: expected `>`
+    ///   ┌─ shape:1:13
+    ///   │
+    /// 1 │ Array<Number
+    ///   │             ^
+    /// ```
+    /// Falls back to the error message when the span is outside of `source`.
+    #[must_use]
+    pub fn render(&self, name: &str, source: &str) -> String {
+        let file = SimpleFile::new(name, source);
+        term::emit_into_string(&Config::default(), &file, &self.to_diagnostic())
+            .unwrap_or_else(|_| self.to_string())
+    }
 }
@@ -0,0 +1,667 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Error,
+    value::{
+        Examples, KeyPattern, LengthRange, LiteralValue, NumberKind, NumberRange, StringFormat,
+        Value, absent_keys,
+    },
+};
+
+/// Version of the interchange format written by [`JsonShape::to_interchange`](crate::JsonShape::to_interchange).
+pub const INTERCHANGE_VERSION: u32 = 1;
+
+const FORMATS: [StringFormat; 7] = [
+    StringFormat::DateTime,
+    StringFormat::Date,
+    StringFormat::Uuid,
+    StringFormat::Email,
+    StringFormat::Uri,
+    StringFormat::Ipv4,
+    StringFormat::Ipv6,
+];
+
+/// Encoding of the shape of an interchange document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShapeEncoding {
+    /// Tree of nodes, describing every refinement, eg `{"type": "array", "items": {"type": "string"}}`.
+    #[default]
+    Tree,
+    /// Compact `Display` notation read by [`JsonShape::parse_shape`](crate::JsonShape::parse_shape), eg `"Array<String>"`,
+    /// without [`Examples`] and with [`NumberRange`]s only `zero` when one of their bounds is zero.
+    Notation,
+}
+
+impl Value {
+    /// Serializes this [`JsonShape`](crate::JsonShape) as a versioned interchange document, stable across releases,
+    /// unlike the `Serialize` implementation that follows the layout of the enum.
+    ///
+    /// Documents are JSON objects `{"version": 1, "shape": ...}` whose shape is either a [`ShapeEncoding::Notation`] string or a [`ShapeEncoding::Tree`] node.
+    /// Nodes are objects with a `type` and, when `true`, `optional`:
+    /// - `null`, `unknown` and `boolean`.
+    /// - `number`, with a `kind` among `unsigned`, `signed`, `u64` and `float`, and optional `range` `{"min", "max", "zero"}` and `examples`.
+    /// - `string`, with optional `format`, eg `date-time`, `length` `{"min", "max"}` and `examples`.
+    /// - `array`, with `items` node and optional `length`.
+    /// - `object`, with `fields` nodes by key and optional `absent` keys.
+    /// - `one_of` with `variants` nodes, `tuple` with `elements` nodes and `literal` with `values` strings and integers.
+    /// - `map`, with `values` node and optional `keys`, `integer` or a string format.
+    /// - `recursive`, with `name` and `shape` node, and `ref` with `name`.
+    /// - `tagged`, with `tag` key and `variants` object nodes by tag value.
+    /// ```
+    /// use json_shape::{JsonShape, ShapeEncoding};
+    /// use serde_json::json;
+    ///
+    /// let shape = JsonShape::parse_shape("Object{id: Integer<unsigned>, tags?: Array<String>}").unwrap();
+    ///
+    /// assert_eq!(
+    ///     shape.to_interchange(ShapeEncoding::Tree),
+    ///     json!({"version": 1, "shape": {
+    ///         "type": "object",
+    ///         "fields": {
+    ///             "id": {"type": "number", "kind": "unsigned"},
+    ///             "tags": {"type": "array", "items": {"type": "string"}}
+    ///         },
+    ///         "absent": ["tags"]
+    ///     }})
+    /// );
+    /// assert_eq!(
+    ///     shape.to_interchange(ShapeEncoding::Notation),
+    ///     json!({"version": 1, "shape": "Object{id: Integer<unsigned>, tags?: Array<String>}"})
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_interchange(&self, encoding: ShapeEncoding) -> serde_json::Value {
+        let shape = match encoding {
+            ShapeEncoding::Tree => {
+                serde_json::to_value(Node::from(self)).unwrap_or(serde_json::Value::Null)
+            }
+            ShapeEncoding::Notation => serde_json::Value::String(self.to_string()),
+        };
+        serde_json::json!({ "version": INTERCHANGE_VERSION, "shape": shape })
+    }
+
+    /// Reads a [`JsonShape`](crate::JsonShape) from an interchange document written by [`JsonShape::to_interchange`](crate::JsonShape::to_interchange),
+    /// by this release or any other.
+    ///
+    /// Unknown fields are ignored, and unknown number kinds, string formats and map keys are read as their widest known refinement.
+    /// Documents of later versions are read best-effort, with nodes of unknown types read as `Unknown`,
+    /// and reported as [`Error::LaterInterchangeVersion`] holding the shape read.
+    /// ```
+    /// use json_shape::JsonShape;
+    /// use serde_json::json;
+    ///
+    /// let document = json!({"version": 1, "shape": {
+    ///     "type": "array",
+    ///     "items": {"type": "string", "format": "hostname", "pattern": "^[a-z.]+$"},
+    ///     "unique": true
+    /// }});
+    ///
+    /// assert_eq!(JsonShape::from_interchange(&document).unwrap().to_string(), "Array<String>");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LaterInterchangeVersion`] when `document` is of a later version than [`INTERCHANGE_VERSION`],
+    /// [`Error::InvalidInterchange`] when it isn't an interchange document or has nodes of unknown types for its version,
+    /// or [`Error::InvalidShape`] when its notation is invalid.
+    pub fn from_interchange(document: &serde_json::Value) -> Result<Self, Error> {
+        let invalid = |reason: &str| Error::InvalidInterchange(reason.to_string());
+        let version = match document.get("version").and_then(serde_json::Value::as_u64) {
+            Some(0) | None => return Err(invalid("expected a `version` of at least 1")),
+            Some(version) => version,
+        };
+        let later = version > u64::from(INTERCHANGE_VERSION);
+        let shape = match document.get("shape") {
+            Some(serde_json::Value::String(notation)) => Self::parse_shape(notation)?,
+            Some(node) => {
+                let node = Node::deserialize(node).map_err(|error| invalid(&error.to_string()))?;
+                if !later && !node.is_known() {
+                    return Err(invalid(&format!(
+                        "unknown node `type` for version {version}"
+                    )));
+                }
+                Self::from(node)
+            }
+            None => return Err(invalid("expected a `shape`")),
+        };
+        if later {
+            Err(Error::LaterInterchangeVersion {
+                version,
+                shape: Box::new(shape),
+            })
+        } else {
+            Ok(shape)
+        }
+    }
+}
+
+/// Node of a [`ShapeEncoding::Tree`], decoupled from the layout of [`Value`]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Node {
+    Null,
+    Unknown,
+    Boolean {
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    Number {
+        #[serde(default)]
+        kind: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        range: Option<Bounds<serde_json::Number>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        examples: Option<Vec<serde_json::Value>>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    String {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        format: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        length: Option<Bounds<usize>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        examples: Option<Vec<serde_json::Value>>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    Array {
+        items: Box<Self>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        length: Option<Bounds<usize>>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    Object {
+        #[serde(default)]
+        fields: BTreeMap<String, Self>,
+        #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+        absent: BTreeSet<String>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    OneOf {
+        variants: Vec<Self>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    Tuple {
+        elements: Vec<Self>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    Literal {
+        values: BTreeSet<LiteralValue>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    Map {
+        values: Box<Self>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        keys: Option<String>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    Recursive {
+        name: String,
+        shape: Box<Self>,
+    },
+    Ref {
+        name: String,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    Tagged {
+        tag: String,
+        variants: BTreeMap<String, Self>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        optional: bool,
+    },
+    /// Node types of later versions
+    #[serde(other)]
+    Other,
+}
+
+impl Node {
+    /// Checks that this node and its nested nodes are of the types of [`INTERCHANGE_VERSION`].
+    fn is_known(&self) -> bool {
+        match self {
+            Self::Other => false,
+            Self::Null
+            | Self::Unknown
+            | Self::Boolean { .. }
+            | Self::Number { .. }
+            | Self::String { .. }
+            | Self::Literal { .. }
+            | Self::Ref { .. } => true,
+            Self::Array { items: node, .. }
+            | Self::Map { values: node, .. }
+            | Self::Recursive { shape: node, .. } => node.is_known(),
+            Self::Object { fields: nodes, .. }
+            | Self::Tagged {
+                variants: nodes, ..
+            } => nodes.values().all(Self::is_known),
+            Self::OneOf {
+                variants: nodes, ..
+            }
+            | Self::Tuple {
+                elements: nodes, ..
+            } => nodes.iter().all(Self::is_known),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Bounds<T> {
+    min: T,
+    max: T,
+    #[serde(default, skip_serializing_if = "is_false")]
+    zero: bool,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+const fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn examples_to_nodes(examples: &Examples) -> Vec<serde_json::Value> {
+    examples
+        .iter()
+        .filter_map(|example| serde_json::from_str(example).ok())
+        .collect()
+}
+
+fn examples_from_nodes(examples: &[serde_json::Value]) -> Examples {
+    examples.iter().fold(Examples::default(), |all, example| {
+        all.union(&Examples::new(example))
+    })
+}
+
+impl From<&LengthRange> for Bounds<usize> {
+    fn from(length: &LengthRange) -> Self {
+        Self {
+            min: length.min,
+            max: length.max,
+            zero: false,
+        }
+    }
+}
+
+impl From<Bounds<usize>> for LengthRange {
+    fn from(length: Bounds<usize>) -> Self {
+        Self {
+            min: length.min,
+            max: length.max,
+        }
+    }
+}
+
+fn format_from_name(name: Option<&str>) -> Option<StringFormat> {
+    name.and_then(|name| {
+        FORMATS
+            .into_iter()
+            .find(|format| format.to_string() == name)
+    })
+}
+
+impl From<&Value> for Node {
+    #[allow(clippy::too_many_lines)]
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Unknown => Self::Unknown,
+            Value::Bool { optional } => Self::Boolean {
+                optional: *optional,
+            },
+            Value::Number {
+                optional,
+                kind,
+                range,
+                examples,
+            } => Self::Number {
+                kind: match kind {
+                    NumberKind::Unsigned => "unsigned",
+                    NumberKind::Signed => "signed",
+                    NumberKind::LargeUnsigned => "u64",
+                    NumberKind::Float => "float",
+                }
+                .to_string(),
+                range: range.as_ref().map(|range| Bounds {
+                    min: range.min.clone(),
+                    max: range.max.clone(),
+                    zero: range.zero,
+                }),
+                examples: examples.as_deref().map(examples_to_nodes),
+                optional: *optional,
+            },
+            Value::String {
+                optional,
+                format,
+                length,
+                examples,
+            } => Self::String {
+                format: format.map(|format| format.to_string()),
+                length: length.as_ref().map(Bounds::from),
+                examples: examples.as_deref().map(examples_to_nodes),
+                optional: *optional,
+            },
+            Value::Array {
+                r#type,
+                optional,
+                length,
+            } => Self::Array {
+                items: Box::new(Self::from(&**r#type)),
+                length: length.as_ref().map(Bounds::from),
+                optional: *optional,
+            },
+            Value::Object {
+                content,
+                optional,
+                absent,
+            } => Self::Object {
+                fields: content
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Self::from(value)))
+                    .collect(),
+                absent: absent_keys(content, absent.as_ref()).into_owned(),
+                optional: *optional,
+            },
+            Value::OneOf { variants, optional } => Self::OneOf {
+                variants: variants.iter().map(Self::from).collect(),
+                optional: *optional,
+            },
+            Value::Tuple { elements, optional } => Self::Tuple {
+                elements: elements.iter().map(Self::from).collect(),
+                optional: *optional,
+            },
+            Value::Literal { values, optional } => Self::Literal {
+                values: values.clone(),
+                optional: *optional,
+            },
+            Value::Map {
+                r#type,
+                key,
+                optional,
+            } => Self::Map {
+                values: Box::new(Self::from(&**r#type)),
+                keys: key.map(|key| match key {
+                    KeyPattern::Integer => "integer".to_string(),
+                    KeyPattern::Format(format) => format.to_string(),
+                }),
+                optional: *optional,
+            },
+            Value::Recursive { name, shape } => Self::Recursive {
+                name: name.clone(),
+                shape: Box::new(Self::from(&**shape)),
+            },
+            Value::Ref { name, optional } => Self::Ref {
+                name: name.clone(),
+                optional: *optional,
+            },
+            Value::Tagged {
+                tag,
+                variants,
+                optional,
+            } => Self::Tagged {
+                tag: tag.clone(),
+                variants: variants
+                    .iter()
+                    .map(|(value, variant)| (value.clone(), Self::from(variant)))
+                    .collect(),
+                optional: *optional,
+            },
+        }
+    }
+}
+
+impl From<Node> for Value {
+    #[allow(clippy::too_many_lines)]
+    fn from(node: Node) -> Self {
+        match node {
+            Node::Null => Self::Null,
+            Node::Unknown | Node::Other => Self::Unknown,
+            Node::Boolean { optional } => Self::Bool { optional },
+            Node::Number {
+                kind,
+                range,
+                examples,
+                optional,
+            } => Self::Number {
+                optional,
+                kind: match kind.as_str() {
+                    "unsigned" => NumberKind::Unsigned,
+                    "signed" => NumberKind::Signed,
+                    "u64" => NumberKind::LargeUnsigned,
+                    _ => NumberKind::Float,
+                },
+                range: range.map(|range| NumberRange {
+                    min: range.min,
+                    max: range.max,
+                    zero: range.zero,
+                }),
+                examples: examples.as_deref().map(examples_from_nodes).map(Box::new),
+            },
+            Node::String {
+                format,
+                length,
+                examples,
+                optional,
+            } => Self::String {
+                optional,
+                format: format_from_name(format.as_deref()),
+                length: length.map(LengthRange::from),
+                examples: examples.as_deref().map(examples_from_nodes).map(Box::new),
+            },
+            Node::Array {
+                items,
+                length,
+                optional,
+            } => Self::Array {
+                r#type: Box::new(Self::from(*items)),
+                optional,
+                length: length.map(LengthRange::from),
+            },
+            Node::Object {
+                fields,
+                absent,
+                optional,
+            } => {
+                let absent = absent
+                    .into_iter()
+                    .filter(|key| fields.contains_key(key))
+                    .collect();
+                let content = fields
+                    .into_iter()
+                    .map(|(key, node)| (key, Self::from(node)))
+                    .collect();
+                Self::Object {
+                    absent: Some(absent),
+                    content,
+                    optional,
+                }
+            }
+            Node::OneOf { variants, optional } => Self::OneOf {
+                variants: variants.into_iter().map(Self::from).collect(),
+                optional,
+            },
+            Node::Tuple { elements, optional } => Self::Tuple {
+                elements: elements.into_iter().map(Self::from).collect(),
+                optional,
+            },
+            Node::Literal { values, optional } => Self::Literal { values, optional },
+            Node::Map {
+                values,
+                keys,
+                optional,
+            } => Self::Map {
+                r#type: Box::new(Self::from(*values)),
+                key: match keys.as_deref() {
+                    Some("integer") => Some(KeyPattern::Integer),
+                    keys => format_from_name(keys).map(KeyPattern::Format),
+                },
+                optional,
+            },
+            Node::Recursive { name, shape } => Self::Recursive {
+                name,
+                shape: Box::new(Self::from(*shape)),
+            },
+            Node::Ref { name, optional } => Self::Ref { name, optional },
+            Node::Tagged {
+                tag,
+                variants,
+                optional,
+            } => Self::Tagged {
+                tag,
+                variants: variants
+                    .into_iter()
+                    .map(|(value, node)| (value, Self::from(node)))
+                    .collect(),
+                optional,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::InferenceOptions;
+
+    use super::*;
+
+    fn refined_shape() -> Value {
+        let options = InferenceOptions::default()
+            .with_number_ranges()
+            .with_length_bounds()
+            .with_literal_limit(2)
+            .with_map_threshold(2)
+            .with_recursion()
+            .with_discriminators()
+            .with_examples(2);
+        Value::from_sources_with_options(
+            &[
+                r#"{"n": -1.5, "s": "2024-01-31", "ids": {"1": true, "2": false}, "tree": {"v": 1, "next": {"v": 2, "next": null}}, "e": [{"type": "a", "x": 1}, {"type": "b"}], "t": [1, "a"]}"#.to_string(),
+                r#"{"n": 2.5, "s": "2024-02-01", "t": [2, "b"], "l": 200}"#.to_string(),
+            ],
+            &options,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn tree_round_trips_every_refinement() {
+        let shape = refined_shape();
+        let document = shape.to_interchange(ShapeEncoding::Tree);
+
+        assert_eq!(Value::from_interchange(&document).unwrap(), shape);
+        assert_eq!(
+            document["shape"]["fields"]["n"],
+            json!({
+                "type": "number",
+                "kind": "float",
+                "range": {"min": -1.5, "max": 2.5},
+                "examples": [-1.5, 2.5]
+            })
+        );
+    }
+
+    #[test]
+    fn notation_round_trips_without_examples() {
+        let shape =
+            Value::parse_shape("Map<Integer, Tagged<kind>[\"a\": Object{x?: Option<Number>}]>")
+                .unwrap();
+        let document = shape.to_interchange(ShapeEncoding::Notation);
+
+        assert_eq!(document["shape"], json!(shape.to_string()));
+        assert_eq!(Value::from_interchange(&document).unwrap(), shape);
+    }
+
+    #[test]
+    fn reads_unknown_fields_and_refinements_as_widest_refinement() {
+        let document = json!({
+            "version": 1,
+            "checksum": "abc",
+            "shape": {
+                "type": "object",
+                "fields": {
+                    "a": {"type": "unknown", "scale": 2},
+                    "b": {"type": "number", "kind": "i128", "optional": true},
+                    "c": {"type": "map", "keys": "ulid", "values": {"type": "null"}},
+                },
+                "absent": ["b", "z"],
+                "closed": true
+            }
+        });
+
+        assert_eq!(
+            Value::from_interchange(&document).unwrap().to_string(),
+            "Object{a: Unknown, b?: Option<Number>, c: Map<String, Null>}"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_documents() {
+        assert_eq!(
+            Value::from_interchange(&json!({"shape": {"type": "null"}})),
+            Err(Error::InvalidInterchange(
+                "expected a `version` of at least 1".to_string()
+            ))
+        );
+        assert_eq!(
+            Value::from_interchange(&json!({"version": 1})),
+            Err(Error::InvalidInterchange("expected a `shape`".to_string()))
+        );
+        assert!(matches!(
+            Value::from_interchange(&json!({"version": 1, "shape": {"type": "array"}})),
+            Err(Error::InvalidInterchange(reason)) if reason.contains("items")
+        ));
+        assert!(matches!(
+            Value::from_interchange(&json!({"version": 1, "shape": "Array<"})),
+            Err(Error::InvalidShape { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_nodes() {
+        assert_eq!(
+            Value::from_interchange(&json!({"version": 1, "shape": {
+                "type": "array",
+                "items": {"type": "decimal"}
+            }})),
+            Err(Error::InvalidInterchange(
+                "unknown node `type` for version 1".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn reads_later_versions_best_effort() {
+        let document = json!({
+            "version": 2,
+            "checksum": "abc",
+            "shape": {
+                "type": "object",
+                "fields": {
+                    "a": {"type": "decimal", "scale": 2},
+                    "b": {"type": "string", "optional": true, "pattern": "^b"},
+                },
+                "absent": ["b"],
+                "closed": true
+            }
+        });
+
+        assert!(matches!(
+            Value::from_interchange(&document),
+            Err(Error::LaterInterchangeVersion { version: 2, shape })
+                if shape.to_string() == "Object{a: Unknown, b?: Option<String>}"
+        ));
+        assert!(matches!(
+            Value::from_interchange(&json!({"version": 2, "shape": "Array<Null>"})),
+            Err(Error::LaterInterchangeVersion { version: 2, shape })
+                if *shape == Value::parse_shape("Array<Null>").unwrap()
+        ));
+    }
+}
@@ -8,23 +8,53 @@
 pub mod error;
 mod value;
 
+mod compatibility;
+mod diff;
+mod interchange;
 mod lexer;
+mod metrics;
+mod notation;
+mod options;
 pub(crate) mod parser;
+mod paths;
+mod pointer;
+mod pretty;
+mod query;
 /// [`serde_json`] related functions and types
 pub mod serde;
 pub(crate) mod shape;
+mod statistics;
+#[cfg(test)]
+mod test_utils;
+mod validation;
+mod visitor;
 
 use std::str::FromStr;
 
 use crate::{
     error::Error,
     parser::Parser,
-    shape::{merger::merge, parse_cst},
+    shape::{
+        apply_limits, infer_root, merger::merger, new_tags, parse_cst_with_options, root_node,
+    },
     value::Value,
 };
 
+pub use compatibility::{Compatibility, Incompatibility};
+pub use diff::{ChangeKind, ShapeChange};
+pub use interchange::{INTERCHANGE_VERSION, ShapeEncoding};
+pub use metrics::ShapeMetrics;
+pub use options::InferenceOptions;
+pub use paths::LeafPath;
+pub use pretty::{PrettyOptions, PrettyStyle};
+pub use statistics::{PathStatistics, ShapeStatistics};
+pub use validation::ValidationError;
 pub use value::Similar;
 pub use value::Value as JsonShape;
+pub use value::{
+    Examples, KeyPattern, LengthRange, LiteralValue, NumberKind, NumberRange, StringFormat,
+};
+pub use visitor::{ShapeVisitor, ShapeVisitorMut, walk_shape, walk_shape_mut};
 
 /// Creates a [`JsonShape`] from a single Json source
 /// ```
@@ -38,11 +68,7 @@ impl FromStr for Value {
     type Err = Error;
 
     fn from_str(source: &str) -> Result<Self, Self::Err> {
-        let cst = Parser::parse(source, &mut Vec::new());
-
-        let value = parse_cst(&cst, source)?;
-
-        Ok(value)
+        Self::from_str_with_options(source, &InferenceOptions::default())
     }
 }
 
@@ -53,34 +79,127 @@ impl Value {
     ///
     /// Will return `Err` if failed to parse Json or if shapes don't align.
     pub fn from_sources(sources: &[String]) -> Result<Self, Error> {
+        Self::from_sources_with_options(sources, &InferenceOptions::default())
+    }
+
+    /// Creates a [`JsonShape`] from a single Json source, using the specified [`InferenceOptions`]
+    /// ```
+    /// use json_shape::{InferenceOptions, JsonShape};
+    ///
+    /// let options = InferenceOptions::default().with_literal_limit(4);
+    /// let json_shape = JsonShape::from_str_with_options("[200, 404, 404]", &options).unwrap();
+    ///
+    /// assert_eq!(json_shape.to_string(), "Array<Literal[200 | 404]>");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if failed to parse Json.
+    pub fn from_str_with_options(source: &str, options: &InferenceOptions) -> Result<Self, Error> {
+        let cst = Parser::parse(source, &mut Vec::new());
+
+        let value = parse_cst_with_options(&cst, source, options)?;
+
+        Ok(value)
+    }
+
+    /// Creates a [`JsonShape`] from multiple Json sources, using the specified [`InferenceOptions`]
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if failed to parse Json or if shapes don't align.
+    pub fn from_sources_with_options(
+        sources: &[String],
+        options: &InferenceOptions,
+    ) -> Result<Self, Error> {
         let mut diags = Vec::new();
-        let mut values = Vec::new();
+        let mut known = options.clone();
+        'sources: loop {
+            let mut shape: Option<Self> = None;
+            for source in sources {
+                let cst = Parser::parse(source, &mut diags);
+                let root = root_node(&cst, source)?;
+                let tags = new_tags(&cst, root, source, &known);
+                if !tags.is_empty() {
+                    known = known.with_tags(tags);
+                    // Objects of the previous sources must know the values of the new tags to merge into their unions
+                    if shape.is_some() {
+                        continue 'sources;
+                    }
+                }
+                let value = infer_root(&cst, root, source, &known)?;
+                shape = Some(match shape {
+                    Some(shape) => apply_limits(merger(shape, value)?, &known),
+                    None => value,
+                });
+            }
+
+            // Widens the literals of the tags that no union kept
+            return match shape {
+                Some(shape) if known.tags != options.tags => Ok(apply_limits(shape, options)),
+                shape => shape.ok_or(Error::EmptyFile),
+            };
+        }
+    }
+
+    /// Picks the first Json source of every distinct [`JsonShape`] among `sources`, eg one event of each type in a log,
+    /// inferred with the specified [`InferenceOptions`] but without [`Examples`].
+    /// ```
+    /// use json_shape::{InferenceOptions, JsonShape};
+    ///
+    /// let sources = [
+    ///     r#"{"type": "click", "x": 1}"#.to_string(),
+    ///     r#"{"type": "click", "x": 2}"#.to_string(),
+    ///     r#"{"type": "scroll", "dy": 3}"#.to_string(),
+    /// ];
+    /// let representatives =
+    ///     JsonShape::representative_sources(&sources, &InferenceOptions::default()).unwrap();
+    ///
+    /// assert_eq!(representatives.len(), 2);
+    /// assert_eq!(representatives[1].0.to_string(), "Object{dy: Integer<unsigned>, type: String}");
+    /// assert_eq!(representatives[1].1, sources[2]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if failed to parse Json.
+    pub fn representative_sources(
+        sources: &[String],
+        options: &InferenceOptions,
+    ) -> Result<Vec<(Self, String)>, Error> {
+        let options = InferenceOptions {
+            example_limit: None,
+            ..options.clone()
+        };
+        let mut representatives: Vec<(Self, String)> = Vec::new();
         for source in sources {
-            let cst = Parser::parse(source, &mut diags);
-            values.push(parse_cst(&cst, source)?);
+            let shape = Self::from_str_with_options(source, &options)?;
+            if !representatives.iter().any(|(other, _)| *other == shape) {
+                representatives.push((shape, source.clone()));
+            }
         }
 
-        merge(&values)
+        Ok(representatives)
     }
 
     /// Checks if Json is subset of specific [`JsonShape`]
     /// ```rust
     /// use std::str::FromStr;
     ///
-    /// use json_shape::{IsSubset, JsonShape};
+    /// use json_shape::{IsSubset, JsonShape, NumberKind};
     /// let shape = JsonShape::Object { content: [
-    ///     ("name".to_string(), JsonShape::String { optional: false }),
-    ///     ("surname".to_string(), JsonShape::String { optional: false }),
-    ///     ("middle name".to_string(), JsonShape::String { optional: true }),
-    ///     ("age".to_string(), JsonShape::Number { optional: false }),
+    ///     ("name".to_string(), JsonShape::String { optional: false, format: None, length: None, examples: None }),
+    ///     ("surname".to_string(), JsonShape::String { optional: false, format: None, length: None, examples: None }),
+    ///     ("middle name".to_string(), JsonShape::String { optional: true, format: None, length: None, examples: None }),
+    ///     ("age".to_string(), JsonShape::Number { optional: false, kind: NumberKind::Float, range: None, examples: None }),
     ///     ("id".to_string(), JsonShape::OneOf { variants: [
     ///         JsonShape::Object { content: [
-    ///             ("number".to_string(), JsonShape::Number { optional: false }),
-    ///             ("state".to_string(), JsonShape::String { optional: false }),
-    ///         ].into(), optional: false },
-    ///         JsonShape::Array { r#type: Box::new(JsonShape::Number { optional: false }), optional: false }
+    ///             ("number".to_string(), JsonShape::Number { optional: false, kind: NumberKind::Float, range: None, examples: None }),
+    ///             ("state".to_string(), JsonShape::String { optional: false, format: None, length: None, examples: None }),
+    ///         ].into(), optional: false, absent: None },
+    ///         JsonShape::Array { r#type: Box::new(JsonShape::Number { optional: false, kind: NumberKind::Float, range: None, examples: None }), optional: false, length: None }
     ///     ].into(), optional: false })
-    /// ].into(), optional: false };
+    /// ].into(), optional: false, absent: None };
     ///
     /// let json = r#"{
     /// "name": "lorem",
@@ -103,7 +222,8 @@ impl Value {
     /// ```
     #[must_use]
     pub fn is_superset(&self, json: &str) -> bool {
-        let Ok(value) = Self::from_str(json) else {
+        let Ok(value) = Self::from_str_with_options(json, &InferenceOptions::validating(self))
+        else {
             return false;
         };
 
@@ -113,12 +233,14 @@ impl Value {
     /// Checks if Json is subset of specific [`JsonShape`]
     ///
     /// - Checked version of [`is_superset`]
+    /// - Json values outside the [`NumberRange`]s and [`LengthRange`]s of this [`JsonShape`] are not subsets
+    /// - [`JsonShape::validate`] locates the values that aren't subsets
     ///
     /// # Errors
     ///
     /// Returns `Err` if failed to parse Json
     pub fn is_superset_checked(&self, json: &str) -> Result<bool, Error> {
-        let value = Self::from_str(json)?;
+        let value = Self::from_str_with_options(json, &InferenceOptions::validating(self))?;
 
         Ok(value.is_subset(self))
     }
@@ -130,11 +252,15 @@ impl Value {
 /// - `JsonShape::Number` is subset of `JsonShape::Option<Number>`
 /// - `JsonShape::Null` is subset of `JsonShape::Option<Number>` and  `JsonShape::Null`
 /// - `JsonShape::Number` is subset of `JsonShape::OneOf[Number | String]`
+/// - `JsonShape::Integer<unsigned>` is subset of `JsonShape::Integer<signed>` and `JsonShape::Number`
+/// - `JsonShape::Literal["a"]` is subset of `JsonShape::Literal["a" | "b"]` and `JsonShape::String`
 /// - `JsonShape::Number` is *NOT* subset of `JsonShape::Array<Number>` => `1.23 != [1.23]`
 /// - `JsonShape::Array<Number>` is subset of `JsonShape::Array<OnOf<[Number | Boolean]>>`
 /// - `JsonShape::Object{"key_a": JsonShape::Number}` is *NOT* subset of `JsonShape::Object{"key_b": JsonShape::Number}` => `key_a != key_b`
 /// - `JsonShape::Object{"key_a": JsonShape::Number}` is subset of `JsonShape::Object{"key_a": JsonShape::Option<Number>}`
+/// - `JsonShape::Object{}` is subset of `JsonShape::Object{"key_a"?: JsonShape::Number}`, but *NOT* of `JsonShape::Object{"key_a": JsonShape::Option<Number>}`
 /// - `JsonShape::Object{"key_a": JsonShape::Number}` is subset of `JsonShape::Object{"key_a": JsonShape::OneOf[Number | Boolean]}`
+/// - `JsonShape::Object{"1": JsonShape::Number}` is subset of `JsonShape::Map<Integer, Number>`
 pub trait IsSubset {
     /// Determines if `T::self` is subset of other `T`
     fn is_subset(&self, other: &Self) -> bool;
@@ -0,0 +1,208 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    value::{Value, absent_keys},
+    visitor::{ShapeVisitor, walk_shape},
+};
+
+/// Size and complexity metrics of a [`JsonShape`](crate::JsonShape), eg to flag pathological payloads or budget generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ShapeMetrics {
+    /// Deepest nesting of values, `1` for a scalar, `2` for an array of scalars
+    pub depth: usize,
+    /// Number of shapes, counting the shape itself and every nested shape, including `OneOf` variants
+    pub nodes: usize,
+    /// Number of distinct object keys, including `Tagged` tags
+    pub distinct_keys: usize,
+    /// Largest number of keys of an object
+    pub max_width: usize,
+    /// Largest number of variants of a `OneOf` or `Tagged` shape
+    pub max_variants: usize,
+    /// Ratio of object fields that may be absent or `null`, `0.0` without fields
+    pub optional_ratio: f64,
+}
+
+impl Value {
+    /// Computes the [`ShapeMetrics`] of this [`JsonShape`](crate::JsonShape).
+    /// ```
+    /// use json_shape::{JsonShape, ShapeMetrics};
+    ///
+    /// let shape = JsonShape::from_sources(&[
+    ///     r#"{"id": 1, "tags": ["a"], "owner": {"id": 2, "name": null}}"#.to_string(),
+    ///     r#"{"id": "x", "tags": []}"#.to_string(),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     shape.metrics(),
+    ///     ShapeMetrics {
+    ///         depth: 3,
+    ///         nodes: 9,
+    ///         distinct_keys: 4,
+    ///         max_width: 3,
+    ///         max_variants: 2,
+    ///         optional_ratio: 0.4,
+    ///     }
+    /// );
+    /// ```
+    #[must_use]
+    pub fn metrics(&self) -> ShapeMetrics {
+        let mut measure = Measure {
+            depth: 1,
+            ..Measure::default()
+        };
+        measure.visit_shape(self, "$");
+        #[allow(clippy::cast_precision_loss)]
+        let optional_ratio = if measure.fields == 0 {
+            0.0
+        } else {
+            measure.optional_fields as f64 / measure.fields as f64
+        };
+        ShapeMetrics {
+            distinct_keys: measure.keys.len(),
+            optional_ratio,
+            ..measure.metrics
+        }
+    }
+}
+
+#[derive(Default)]
+struct Measure {
+    metrics: ShapeMetrics,
+    depth: usize,
+    keys: BTreeSet<String>,
+    fields: usize,
+    optional_fields: usize,
+}
+
+impl ShapeVisitor for Measure {
+    fn visit_shape(&mut self, shape: &Value, path: &str) {
+        self.metrics.nodes += 1;
+        self.metrics.depth = self.metrics.depth.max(self.depth);
+        match shape {
+            Value::Object {
+                content, absent, ..
+            } => {
+                let absent = absent_keys(content, absent.as_ref());
+                self.metrics.max_width = self.metrics.max_width.max(content.len());
+                self.keys.extend(content.keys().cloned());
+                self.fields += content.len();
+                self.optional_fields += content
+                    .iter()
+                    .filter(|(key, value)| absent.contains(*key) || is_nullable(value))
+                    .count();
+            }
+            Value::OneOf { variants, .. } => {
+                self.metrics.max_variants = self.metrics.max_variants.max(variants.len());
+            }
+            Value::Tagged { tag, variants, .. } => {
+                self.metrics.max_variants = self.metrics.max_variants.max(variants.len());
+                self.keys.insert(tag.clone());
+            }
+            _ => (),
+        }
+        // Variants and definitions describe the same values, only nested values are deeper
+        let nests = matches!(
+            shape,
+            Value::Array { .. } | Value::Tuple { .. } | Value::Map { .. } | Value::Object { .. }
+        );
+        if nests {
+            self.depth += 1;
+        }
+        walk_shape(self, shape, path);
+        if nests {
+            self.depth -= 1;
+        }
+    }
+}
+
+fn is_nullable(value: &Value) -> bool {
+    match value {
+        Value::OneOf { variants, optional } => *optional || variants.contains(&Value::Null),
+        value => value.is_optional(),
+    }
+}
+
+impl Display for ShapeMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "depth: {}, nodes: {}, distinct keys: {}, max width: {}, max variants: {}, optional ratio: {:.2}",
+            self.depth,
+            self.nodes,
+            self.distinct_keys,
+            self.max_width,
+            self.max_variants,
+            self.optional_ratio
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::InferenceOptions;
+
+    use super::*;
+
+    #[test]
+    fn scalars_have_a_single_node() {
+        assert_eq!(
+            Value::from_str("1").unwrap().metrics(),
+            ShapeMetrics {
+                depth: 1,
+                nodes: 1,
+                ..ShapeMetrics::default()
+            }
+        );
+    }
+
+    #[test]
+    fn variants_count_as_nodes_without_nesting() {
+        let shape = Value::from_sources(&[
+            "[1]".to_string(),
+            r#"["a"]"#.to_string(),
+            "[null]".to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            shape.to_string(),
+            "Array<OneOf[Null | Integer<unsigned> | String]>"
+        );
+        assert_eq!(
+            shape.metrics(),
+            ShapeMetrics {
+                depth: 2,
+                nodes: 5,
+                max_variants: 3,
+                ..ShapeMetrics::default()
+            }
+        );
+    }
+
+    #[test]
+    fn tagged_and_recursive_shapes() {
+        let options = InferenceOptions::default()
+            .with_discriminators()
+            .with_recursion();
+        let shape = Value::from_str_with_options(
+            r#"{"events": [{"type": "a", "x": 1}, {"type": "b"}], "tree": {"v": 1, "next": {"v": 2, "next": null}}}"#,
+            &options,
+        )
+        .unwrap();
+        let metrics = shape.metrics();
+
+        assert_eq!(metrics.depth, 4);
+        assert_eq!(metrics.distinct_keys, 6);
+        assert_eq!(metrics.max_variants, 2);
+        assert!((metrics.optional_ratio - 0.2).abs() < f64::EPSILON);
+        assert_eq!(
+            metrics.to_string(),
+            "depth: 4, nodes: 10, distinct keys: 6, max width: 2, max variants: 2, optional ratio: 0.20"
+        );
+    }
+}
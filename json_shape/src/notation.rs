@@ -0,0 +1,678 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    error::Error,
+    value::{KeyPattern, LengthRange, LiteralValue, NumberKind, NumberRange, StringFormat, Value},
+};
+
+const FORMATS: [StringFormat; 7] = [
+    StringFormat::DateTime,
+    StringFormat::Date,
+    StringFormat::Uuid,
+    StringFormat::Email,
+    StringFormat::Uri,
+    StringFormat::Ipv4,
+    StringFormat::Ipv6,
+];
+
+impl Value {
+    /// Parses a [`JsonShape`](crate::JsonShape) from the notation of its `Display` implementation,
+    /// eg `Object{a: Number, "b c"?: Option<String>}`, to hand-write shapes in configuration files and tests.
+    ///
+    /// Keys made of anything else than letters, digits, `_` and `-` are quoted as JSON strings.
+    /// A `Ref<X>` refers to an enclosing `Recursive<X: ...>`.
+    /// The notation doesn't describe [`Examples`](crate::Examples), and a [`NumberRange`] only has `zero` when one of its bounds is zero.
+    /// ```
+    /// use json_shape::JsonShape;
+    ///
+    /// let shape = JsonShape::parse_shape(r#"Object{id: Integer<unsigned>, "first name"?: Option<String>}"#).unwrap();
+    ///
+    /// assert!(shape.is_superset(r#"{"id": 1, "first name": null}"#));
+    /// assert!(shape.is_superset(r#"{"id": 2}"#));
+    /// assert_eq!(JsonShape::parse_shape(&shape.to_string()).unwrap(), shape);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidShape`] with the range of `notation` where it is invalid.
+    pub fn parse_shape(notation: &str) -> Result<Self, Error> {
+        let mut parser = Parser {
+            notation,
+            position: 0,
+            names: Vec::new(),
+        };
+        let shape = parser.shape()?;
+        parser.skip_blanks();
+        if parser.position < notation.len() {
+            return Err(parser.error("unexpected character"));
+        }
+        Ok(shape)
+    }
+}
+
+struct Parser<'a> {
+    notation: &'a str,
+    position: usize,
+    /// Names of the enclosing `Recursive` shapes, which `Ref` can refer to
+    names: Vec<String>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.notation[self.position..].chars().next()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_blanks();
+        let found = self.peek() == Some(expected);
+        if found {
+            self.position += expected.len_utf8();
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{expected}`")))
+        }
+    }
+
+    fn skip_blanks(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn error(&self, reason: &str) -> Error {
+        let end = self
+            .peek()
+            .map_or(self.position, |char| self.position + char.len_utf8());
+        self.error_at(reason, self.position..end)
+    }
+
+    fn error_at(&self, reason: &str, span: std::ops::Range<usize>) -> Error {
+        Error::InvalidShape {
+            shape: self.notation.to_string(),
+            reason: reason.to_string(),
+            span,
+        }
+    }
+
+    /// Letters, digits, `_` and `-`, as type names, formats and unquoted keys
+    fn word(&mut self) -> &'a str {
+        self.skip_blanks();
+        let start = self.position;
+        while let Some(char) = self.peek() {
+            if char.is_alphanumeric() || char == '_' || char == '-' {
+                self.position += char.len_utf8();
+            } else {
+                break;
+            }
+        }
+        &self.notation[start..self.position]
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn shape(&mut self) -> Result<Value, Error> {
+        self.skip_blanks();
+        let start = self.position;
+        let shape = match self.word() {
+            "Option" => {
+                self.expect('<')?;
+                let shape = self.shape()?;
+                self.expect('>')?;
+                shape.as_optional()
+            }
+            "Null" => Value::Null,
+            "Unknown" => Value::Unknown,
+            "Boolean" => Value::Bool { optional: false },
+            "Number" => Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: self.number_range()?,
+                examples: None,
+            },
+            "Integer" => {
+                self.expect('<')?;
+                let kind_start = self.position;
+                let kind = match self.word() {
+                    "unsigned" => NumberKind::Unsigned,
+                    "signed" => NumberKind::Signed,
+                    "u64" => NumberKind::LargeUnsigned,
+                    _ => {
+                        return Err(self.error_at(
+                            "expected `unsigned`, `signed` or `u64`",
+                            kind_start..self.position.max(kind_start + 1),
+                        ));
+                    }
+                };
+                self.expect('>')?;
+                Value::Number {
+                    optional: false,
+                    kind,
+                    range: self.number_range()?,
+                    examples: None,
+                }
+            }
+            "String" => {
+                let format = if self.eat('<') {
+                    let format = self.format()?;
+                    self.expect('>')?;
+                    Some(format)
+                } else {
+                    None
+                };
+                Value::String {
+                    optional: false,
+                    format,
+                    length: self.length()?,
+                    examples: None,
+                }
+            }
+            "Array" => {
+                self.expect('<')?;
+                let r#type = self.shape()?;
+                self.expect('>')?;
+                Value::Array {
+                    r#type: Box::new(r#type),
+                    optional: false,
+                    length: self.length()?,
+                }
+            }
+            "Object" => self.object()?,
+            "OneOf" => {
+                self.expect('[')?;
+                let variants = self.list(']', '|', Self::shape)?;
+                Value::OneOf {
+                    variants: variants.into_iter().collect(),
+                    optional: false,
+                }
+            }
+            "Tuple" => {
+                self.expect('(')?;
+                Value::Tuple {
+                    elements: self.list(')', ',', Self::shape)?,
+                    optional: false,
+                }
+            }
+            "Literal" => {
+                self.expect('[')?;
+                let values = self.list(']', '|', Self::literal)?;
+                if values.is_empty() {
+                    return Err(
+                        self.error_at("literals need at least one value", start..self.position)
+                    );
+                }
+                Value::Literal {
+                    values: values.into_iter().collect(),
+                    optional: false,
+                }
+            }
+            "Map" => {
+                self.expect('<')?;
+                let key = self.key_pattern()?;
+                self.expect(',')?;
+                let r#type = self.shape()?;
+                self.expect('>')?;
+                Value::Map {
+                    r#type: Box::new(r#type),
+                    key,
+                    optional: false,
+                }
+            }
+            "Recursive" => {
+                self.expect('<')?;
+                let name = self.name()?;
+                self.expect(':')?;
+                self.names.push(name.clone());
+                let shape = self.shape()?;
+                self.names.pop();
+                self.expect('>')?;
+                Value::Recursive {
+                    name,
+                    shape: Box::new(shape),
+                }
+            }
+            "Ref" => {
+                self.expect('<')?;
+                let name = self.name()?;
+                self.expect('>')?;
+                if !self.names.contains(&name) {
+                    return Err(self.error_at(
+                        &format!(
+                            "unresolved `Ref<{name}>`, expected in a `Recursive<{name}: ...>`"
+                        ),
+                        start..self.position,
+                    ));
+                }
+                Value::Ref {
+                    name,
+                    optional: false,
+                }
+            }
+            "Tagged" => self.tagged()?,
+            "" => return Err(self.error("expected a shape")),
+            word => {
+                return Err(self.error_at(&format!("unknown shape `{word}`"), start..self.position));
+            }
+        };
+        Ok(shape)
+    }
+
+    /// Items up to `close`, separated by `separator`
+    fn list<T>(
+        &mut self,
+        close: char,
+        separator: char,
+        item: fn(&mut Self) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
+        let mut items = Vec::new();
+        if self.eat(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.eat(close) {
+                return Ok(items);
+            }
+            if !self.eat(separator) {
+                return Err(self.error(&format!("expected `{separator}` or `{close}`")));
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, Error> {
+        self.expect('{')?;
+        let mut content = BTreeMap::new();
+        let mut absent = BTreeSet::new();
+        for (key, span, is_absent, value) in self.list('}', ',', Self::field)? {
+            if content.contains_key(&key) {
+                return Err(self.error_at(&format!("duplicate key `{key}`"), span));
+            }
+            if is_absent {
+                absent.insert(key.clone());
+            }
+            content.insert(key, value);
+        }
+        Ok(Value::Object {
+            absent: Some(absent),
+            content,
+            optional: false,
+        })
+    }
+
+    /// `key: shape`, or `key?: shape` when the key may be absent
+    fn field(&mut self) -> Result<(String, std::ops::Range<usize>, bool, Value), Error> {
+        self.skip_blanks();
+        let start = self.position;
+        let key = self.key()?;
+        let span = start..self.position;
+        let absent = self.eat('?');
+        self.expect(':')?;
+        Ok((key, span, absent, self.shape()?))
+    }
+
+    fn tagged(&mut self) -> Result<Value, Error> {
+        self.expect('<')?;
+        let tag = self.key()?;
+        self.expect('>')?;
+        self.expect('[')?;
+        let mut variants = BTreeMap::new();
+        for (value, span, variant) in self.list(']', '|', Self::variant)? {
+            if !variant.is_object() || variant.is_optional() {
+                return Err(self.error_at("tagged variants must be objects", span));
+            }
+            if variants.insert(value.clone(), variant).is_some() {
+                return Err(self.error_at(&format!("duplicate tag value `{value}`"), span));
+            }
+        }
+        Ok(Value::Tagged {
+            tag,
+            variants,
+            optional: false,
+        })
+    }
+
+    /// `"value": Object{..}`
+    fn variant(&mut self) -> Result<(String, std::ops::Range<usize>, Value), Error> {
+        self.skip_blanks();
+        let start = self.position;
+        let value = self.string()?;
+        let span = start..self.position;
+        self.expect(':')?;
+        Ok((value, span, self.shape()?))
+    }
+
+    fn key(&mut self) -> Result<String, Error> {
+        self.skip_blanks();
+        if self.peek() == Some('"') {
+            return self.string();
+        }
+        let key = self.word();
+        if key.is_empty() {
+            return Err(self.error("expected a key"));
+        }
+        Ok(key.to_string())
+    }
+
+    /// `Recursive` and `Ref` names
+    fn name(&mut self) -> Result<String, Error> {
+        let name = self.word();
+        if name.is_empty() {
+            return Err(self.error("expected a name"));
+        }
+        Ok(name.to_string())
+    }
+
+    /// JSON string, with its escapes
+    fn string(&mut self) -> Result<String, Error> {
+        self.skip_blanks();
+        let start = self.position;
+        if self.peek() != Some('"') {
+            return Err(self.error("expected `\"`"));
+        }
+        let mut chars = self.notation[start + 1..].char_indices();
+        loop {
+            match chars.next() {
+                None => {
+                    return Err(self.error_at("unterminated string", start..self.notation.len()));
+                }
+                Some((_, '\\')) => {
+                    chars.next();
+                }
+                Some((index, '"')) => {
+                    self.position = start + index + 2;
+                    break;
+                }
+                Some(_) => (),
+            }
+        }
+        serde_json::from_str(&self.notation[start..self.position]).map_err(|error| {
+            self.error_at(&format!("invalid string, {error}"), start..self.position)
+        })
+    }
+
+    fn literal(&mut self) -> Result<LiteralValue, Error> {
+        self.skip_blanks();
+        if self.peek() == Some('"') {
+            return self.string().map(LiteralValue::String);
+        }
+        let start = self.position;
+        self.eat('-');
+        while self.peek().is_some_and(|char| char.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.notation[start..self.position]
+            .parse()
+            .map(LiteralValue::Integer)
+            .map_err(|_| {
+                self.error_at(
+                    "expected a string or integer literal",
+                    start..self.position.max(start + 1),
+                )
+            })
+    }
+
+    fn format(&mut self) -> Result<StringFormat, Error> {
+        self.skip_blanks();
+        let start = self.position;
+        let word = self.word();
+        FORMATS
+            .into_iter()
+            .find(|format| format.to_string() == word)
+            .ok_or_else(|| {
+                self.error_at(
+                    "expected a string format, eg `date-time` or `uuid`",
+                    start..self.position.max(start + 1),
+                )
+            })
+    }
+
+    /// `String`, `Integer` or `String<format>` keys of a `Map`
+    fn key_pattern(&mut self) -> Result<Option<KeyPattern>, Error> {
+        self.skip_blanks();
+        let start = self.position;
+        match self.word() {
+            "Integer" => Ok(Some(KeyPattern::Integer)),
+            "String" if self.eat('<') => {
+                let format = self.format()?;
+                self.expect('>')?;
+                Ok(Some(KeyPattern::Format(format)))
+            }
+            "String" => Ok(None),
+            _ => Err(self.error_at(
+                "expected `String`, `Integer` or `String<format>` keys",
+                start..self.position.max(start + 1),
+            )),
+        }
+    }
+
+    /// Optional `[min..=max]` of a number
+    fn number_range(&mut self) -> Result<Option<NumberRange>, Error> {
+        if !self.eat('[') {
+            return Ok(None);
+        }
+        self.skip_blanks();
+        let start = self.position;
+        let min = self.number()?;
+        self.expect_range()?;
+        let max = self.number()?;
+        self.expect(']')?;
+        if min.as_f64() > max.as_f64() {
+            return Err(self.error_at(
+                "range minimum is larger than its maximum",
+                start..self.position,
+            ));
+        }
+        Ok(Some(NumberRange {
+            zero: NumberRange::new(&min).zero || NumberRange::new(&max).zero,
+            min,
+            max,
+        }))
+    }
+
+    /// Optional `[min..=max]` of a string or array length
+    fn length(&mut self) -> Result<Option<LengthRange>, Error> {
+        if !self.eat('[') {
+            return Ok(None);
+        }
+        self.skip_blanks();
+        let start = self.position;
+        let min = self.size()?;
+        self.expect_range()?;
+        let max = self.size()?;
+        self.expect(']')?;
+        if min > max {
+            return Err(self.error_at(
+                "range minimum is larger than its maximum",
+                start..self.position,
+            ));
+        }
+        Ok(Some(LengthRange { min, max }))
+    }
+
+    fn expect_range(&mut self) -> Result<(), Error> {
+        self.skip_blanks();
+        if self.notation[self.position..].starts_with("..=") {
+            self.position += 3;
+            Ok(())
+        } else {
+            Err(self.error("expected `..=`"))
+        }
+    }
+
+    fn size(&mut self) -> Result<usize, Error> {
+        self.skip_blanks();
+        let start = self.position;
+        while self.peek().is_some_and(|char| char.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.notation[start..self.position]
+            .parse()
+            .map_err(|_| self.error_at("expected a length", start..self.position.max(start + 1)))
+    }
+
+    /// JSON number, eg `-1.5e3`
+    fn number(&mut self) -> Result<serde_json::Number, Error> {
+        self.skip_blanks();
+        let start = self.position;
+        self.eat('-');
+        self.digits();
+        if self.notation[self.position..].starts_with('.')
+            && !self.notation[self.position..].starts_with("..")
+        {
+            self.position += 1;
+            self.digits();
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.position += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.position += 1;
+            }
+            self.digits();
+        }
+        self.notation[start..self.position]
+            .parse()
+            .map_err(|_| self.error_at("expected a number", start..self.position.max(start + 1)))
+    }
+
+    fn digits(&mut self) {
+        while self.peek().is_some_and(|char| char.is_ascii_digit()) {
+            self.position += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::InferenceOptions;
+
+    use super::*;
+
+    fn reason(notation: &str) -> (String, std::ops::Range<usize>) {
+        match Value::parse_shape(notation) {
+            Err(Error::InvalidShape { reason, span, .. }) => (reason, span),
+            other => panic!("expected invalid shape, got {other:?}"),
+        }
+    }
+
+    fn round_trip(shape: &Value) {
+        assert_eq!(
+            &Value::parse_shape(&shape.to_string()).unwrap(),
+            shape,
+            "{shape}"
+        );
+    }
+
+    #[test]
+    fn parses_every_shape() {
+        let shape = Value::parse_shape(
+            r#"Object{
+                a: Option<Integer<signed>[-3..=1.5e3]>,
+                "b c"?: String<date>[1..=10],
+                d: OneOf[Null | Boolean | Array<Unknown>[0..=2]],
+                e: Tuple(Number, Literal["x" | -1]),
+                f: Recursive<List: Map<Integer, Option<Ref<List>>>>,
+                g: Tagged<type>["a": Object{x: Number} | "b": Object{}],
+                h: Recursive<Node: Object{next: Option<Ref<Node>>}>,
+                i: Map<String<uuid>, Integer<u64>>,
+                j: Map<String, Object{}>
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            shape.to_string(),
+            r#"Object{a: Option<Integer<signed>[-3..=1500.0]>, "b c"?: String<date>[1..=10], d: OneOf[Null | Boolean | Array<Unknown>[0..=2]], e: Tuple(Number, Literal[-1 | "x"]), f: Recursive<List: Map<Integer, Option<Ref<List>>>>, g: Tagged<type>["a": Object{x: Number} | "b": Object{}], h: Recursive<Node: Object{next: Option<Ref<Node>>}>, i: Map<String<uuid>, Integer<u64>>, j: Map<String, Object{}>}"#
+        );
+        round_trip(&shape);
+    }
+
+    #[test]
+    fn quotes_and_escapes_keys() {
+        let shape =
+            Value::from_str(r#"{"say \"hi\"": 1, "": true, "a.b": null, "ünï-1_x": "s"}"#).unwrap();
+
+        assert_eq!(
+            shape.to_string(),
+            r#"Object{"": Boolean, "a.b": Null, "say \"hi\"": Integer<unsigned>, ünï-1_x: String}"#
+        );
+        round_trip(&shape);
+    }
+
+    #[test]
+    fn round_trips_inferred_refinements() {
+        let options = InferenceOptions::default()
+            .with_literal_limit(2)
+            .with_length_bounds()
+            .with_map_threshold(2)
+            .with_recursion()
+            .with_discriminators();
+        let shape = Value::from_sources_with_options(
+            &[
+                r#"{"status": 200, "tags": ["a"], "ids": {"1": "2024-01-31", "2": "2024-02-01"}, "tree": {"v": 1, "next": {"v": 2, "next": null}}}"#.to_string(),
+                r#"{"status": 404, "events": [{"type": "a", "x": 1}, {"type": "b"}], "ip": "::1"}"#.to_string(),
+            ],
+            &options,
+        )
+        .unwrap();
+
+        round_trip(&shape);
+    }
+
+    #[test]
+    fn rejects_invalid_notations() {
+        assert_eq!(reason(""), ("expected a shape".to_string(), 0..0));
+        assert_eq!(reason("Integer"), ("expected `<`".to_string(), 7..7));
+        assert_eq!(reason("Numbr"), ("unknown shape `Numbr`".to_string(), 0..5));
+        assert_eq!(
+            reason("Object{a: Number b: Null}"),
+            ("expected `,` or `}`".to_string(), 17..18)
+        );
+        assert_eq!(
+            reason("Object{a: Null, a: Number}"),
+            ("duplicate key `a`".to_string(), 16..17)
+        );
+        assert_eq!(
+            reason(r#"Object{"a: Null}"#),
+            ("unterminated string".to_string(), 7..16)
+        );
+        assert_eq!(
+            reason("String[3..=1]"),
+            (
+                "range minimum is larger than its maximum".to_string(),
+                7..13
+            )
+        );
+        assert_eq!(
+            reason(r#"Tagged<t>["a": Number]"#),
+            ("tagged variants must be objects".to_string(), 10..13)
+        );
+        assert_eq!(
+            reason("Object{a: Recursive<Node: Array<Ref<Node>>>, b: Ref<Node>}"),
+            (
+                "unresolved `Ref<Node>`, expected in a `Recursive<Node: ...>`".to_string(),
+                48..57
+            )
+        );
+        assert_eq!(
+            reason("Null Null"),
+            ("unexpected character".to_string(), 5..6)
+        );
+    }
+
+    #[test]
+    fn renders_errors_with_codespan() {
+        let error = Value::parse_shape("Array<Number").unwrap_err();
+
+        assert_eq!(error.span(), Some(12..12));
+        assert_eq!(
+            error.render("shape", "Array<Number"),
+            "error: invalid shape: expected `>`\n  ┌─ shape:1:13\n  │\n1 │ Array<Number\n  │             ^\n\n"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeSet;
+
+use crate::value::Value;
+
+/// Opt-in knobs for [`JsonShape`](crate::JsonShape) inference.
+///
+/// The [`Default`] options infer the same shapes as [`JsonShape::from_str`](std::str::FromStr::from_str)
+/// and [`JsonShape::from_sources`](crate::JsonShape::from_sources).
+///
+/// ```rust
+/// use json_shape::{InferenceOptions, JsonShape};
+///
+/// let options = InferenceOptions::default().with_literal_limit(3);
+/// let sources = [
+///     r#"{"status": "active"}"#.to_string(),
+///     r#"{"status": "disabled"}"#.to_string(),
+/// ];
+/// let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+///
+/// assert_eq!(shape.to_string(), r#"Object{status: Literal["active" | "disabled"]}"#);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct InferenceOptions {
+    /// Maximum number of distinct string and integer values tracked per path as a [`JsonShape::Literal`](crate::JsonShape::Literal).
+    /// Literal unions growing past this limit fall back to `String`/`Integer`. `None` disables literal inference.
+    pub literal_limit: Option<usize>,
+    /// Records the [`NumberRange`](crate::NumberRange) of every [`JsonShape::Number`](crate::JsonShape::Number), eg `Integer<unsigned>[0..=120]`.
+    pub number_ranges: bool,
+    /// Records the [`LengthRange`](crate::LengthRange) of every [`JsonShape::String`](crate::JsonShape::String) and [`JsonShape::Array`](crate::JsonShape::Array), eg `Array<String>[1..=3]`.
+    pub length_bounds: bool,
+    /// Minimum number of keys of an object with values of the same type to be inferred as a [`JsonShape::Map`](crate::JsonShape::Map), at least two,
+    /// keyed by the [`KeyPattern`](crate::KeyPattern) its keys share, eg `"123"` or `"2024-01-31"`. `None` disables map inference.
+    pub map_threshold: Option<usize>,
+    /// Detects objects nested inside themselves, eg comments with `replies`, and infers them once as a [`JsonShape::Recursive`](crate::JsonShape::Recursive)
+    /// definition referenced with [`JsonShape::Ref`](crate::JsonShape::Ref).
+    pub recursive: bool,
+    /// Detects arrays of objects discriminated by a key with constant string values, eg `{"type": "click", ...}` and `{"type": "scroll", ...}`,
+    /// and infers them as a [`JsonShape::Tagged`](crate::JsonShape::Tagged) union instead of merging them into a single object.
+    pub discriminators: bool,
+    /// Maximum number of distinct [`Examples`](crate::Examples) kept per [`JsonShape::Number`](crate::JsonShape::Number)
+    /// and [`JsonShape::String`](crate::JsonShape::String), in the order they were first seen. `None` disables examples.
+    pub example_limit: Option<usize>,
+    /// Tags of the [`JsonShape::Tagged`](crate::JsonShape::Tagged) unions a Json is validated against,
+    /// whose string values are inferred as literals and which are preferred as discriminators.
+    pub(crate) tags: BTreeSet<String>,
+}
+
+impl InferenceOptions {
+    /// Infers every refinement checked by [`IsSubset`](crate::IsSubset), used to validate Json against a [`JsonShape`](crate::JsonShape).
+    pub(crate) const VALIDATION: Self = Self {
+        literal_limit: None,
+        number_ranges: true,
+        length_bounds: true,
+        map_threshold: None,
+        recursive: false,
+        discriminators: true,
+        example_limit: None,
+        tags: BTreeSet::new(),
+    };
+
+    /// Adds `tags` to the tags whose string values are inferred as literals, see [`InferenceOptions::validating`].
+    pub(crate) fn with_tags(mut self, tags: BTreeSet<String>) -> Self {
+        self.tags.extend(tags);
+        self
+    }
+
+    /// [`InferenceOptions::VALIDATION`] for Json validated against `shape`, so that the values of its tags are known.
+    pub(crate) fn validating(shape: &Value) -> Self {
+        let tags = shape.fold(BTreeSet::new(), |mut tags, shape, _| {
+            if let Value::Tagged { tag, .. } = shape {
+                tags.insert(tag.clone());
+            }
+            tags
+        });
+        Self {
+            tags,
+            ..Self::VALIDATION
+        }
+    }
+
+    /// Enables literal union inference for paths with up to `limit` distinct values.
+    #[must_use]
+    pub const fn with_literal_limit(mut self, limit: usize) -> Self {
+        self.literal_limit = Some(limit);
+        self
+    }
+
+    /// Enables numeric range inference.
+    #[must_use]
+    pub const fn with_number_ranges(mut self) -> Self {
+        self.number_ranges = true;
+        self
+    }
+
+    /// Enables string and array length inference.
+    #[must_use]
+    pub const fn with_length_bounds(mut self) -> Self {
+        self.length_bounds = true;
+        self
+    }
+
+    /// Enables map inference for objects with at least `threshold` keys, and at least two.
+    #[must_use]
+    pub const fn with_map_threshold(mut self, threshold: usize) -> Self {
+        self.map_threshold = Some(threshold);
+        self
+    }
+
+    /// Enables recursive shape inference.
+    #[must_use]
+    pub const fn with_recursion(mut self) -> Self {
+        self.recursive = true;
+        self
+    }
+
+    /// Enables tagged union inference.
+    #[must_use]
+    pub const fn with_discriminators(mut self) -> Self {
+        self.discriminators = true;
+        self
+    }
+
+    /// Enables keeping up to `limit` examples per number and string.
+    #[must_use]
+    pub const fn with_examples(mut self, limit: usize) -> Self {
+        self.example_limit = Some(limit);
+        self
+    }
+}
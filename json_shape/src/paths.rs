@@ -0,0 +1,282 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    statistics::key_path,
+    value::{LiteralValue, Value, absent_keys},
+    visitor::{ShapeVisitor, walk_shape},
+};
+
+/// Path of a leaf of a [`JsonShape`](crate::JsonShape), a shape without nested shapes, eg `$.orders[*].items[*].price -> Option<Number>`.
+///
+/// Displayed with a `?` after the path when its key may be absent, eg `$.orders[*].note? -> String`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeafPath {
+    /// `JSONPath` path of the values described by the leaf, eg `$.orders[*].items[*].price`
+    pub path: String,
+    /// Shape of the leaf, `Option` when the leaf may be `null`
+    pub shape: Value,
+    /// If the key of the leaf may be absent from its object
+    pub absent: bool,
+    /// If some ancestor may be `null` or absent, so that the leaf may be missing even when it isn't optional
+    pub optional_ancestor: bool,
+    /// If the path goes through a `OneOf` or `Tagged` variant, so that the leaf only exists in some of the values
+    pub one_of: bool,
+}
+
+impl Value {
+    /// Iterates over the [`LeafPath`] of every leaf of this [`JsonShape`](crate::JsonShape), the "gron" view of its types.
+    ///
+    /// Each variant of a `OneOf` is walked at the same path, optional when the `OneOf` may be `null`,
+    /// and the tag of a `Tagged` shape is a leaf of its literal values.
+    /// ```
+    /// use json_shape::JsonShape;
+    ///
+    /// let shape = JsonShape::from_sources(&[
+    ///     r#"{"orders": [{"id": 1, "items": [{"price": 1.5}]}]}"#.to_string(),
+    ///     r#"{"orders": [{"id": "a", "items": [{"price": null}]}]}"#.to_string(),
+    ///     r#"{"orders": [{"id": 2, "items": [{}]}]}"#.to_string(),
+    ///     r#"{"orders": null}"#.to_string(),
+    /// ])
+    /// .unwrap();
+    /// let paths = shape.leaf_paths().map(|leaf| leaf.to_string()).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     paths,
+    ///     [
+    ///         "$.orders[*].id -> Integer<unsigned>",
+    ///         "$.orders[*].id -> String",
+    ///         "$.orders[*].items[*].price? -> Option<Number>",
+    ///     ]
+    /// );
+    /// assert!(shape.leaf_paths().all(|leaf| leaf.optional_ancestor));
+    /// ```
+    pub fn leaf_paths(&self) -> impl Iterator<Item = LeafPath> {
+        let mut leaves = Leaves::default();
+        leaves.visit_shape(self, "$");
+        leaves.paths.into_iter()
+    }
+}
+
+#[derive(Default)]
+struct Leaves {
+    paths: Vec<LeafPath>,
+    absent: bool,
+    optional_ancestor: bool,
+    one_of: bool,
+}
+
+impl Leaves {
+    fn push(&mut self, path: String, shape: Value) {
+        self.paths.push(LeafPath {
+            path,
+            shape,
+            absent: self.absent,
+            optional_ancestor: self.optional_ancestor,
+            one_of: self.one_of,
+        });
+    }
+
+    /// Walks the shapes nested in `shape`, marking them with an optional ancestor when `shape` may be missing
+    fn walk_nested(&mut self, shape: &Value, path: &str, one_of: bool) {
+        let saved = (self.absent, self.optional_ancestor, self.one_of);
+        self.optional_ancestor |= self.absent || shape.is_optional();
+        self.one_of |= one_of;
+        self.absent = false;
+        match shape {
+            Value::Object {
+                content, absent, ..
+            } => {
+                let absent = absent_keys(content, absent.as_ref());
+                for (key, value) in content {
+                    self.absent = absent.contains(key);
+                    self.visit_shape(value, &key_path(path, key));
+                }
+            }
+            Value::Tagged { tag, variants, .. } => {
+                let values = variants
+                    .keys()
+                    .map(|value| LiteralValue::String(value.clone()))
+                    .collect();
+                self.push(
+                    key_path(path, tag),
+                    Value::Literal {
+                        values,
+                        optional: false,
+                    },
+                );
+                // Variants without other keys than the tag are described by the tag leaf
+                for variant in variants.values() {
+                    if !matches!(variant, Value::Object { content, .. } if content.is_empty()) {
+                        self.visit_shape(variant, path);
+                    }
+                }
+            }
+            shape => walk_shape(self, shape, path),
+        }
+        (self.absent, self.optional_ancestor, self.one_of) = saved;
+    }
+}
+
+impl ShapeVisitor for Leaves {
+    fn visit_shape(&mut self, shape: &Value, path: &str) {
+        match shape {
+            Value::OneOf { variants, optional } => {
+                let nullable = *optional || variants.contains(&Value::Null);
+                let one_of = std::mem::replace(&mut self.one_of, true);
+                for variant in variants.iter().filter(|variant| !variant.is_null()) {
+                    if nullable {
+                        self.visit_shape(&variant.clone().as_optional(), path);
+                    } else {
+                        self.visit_shape(variant, path);
+                    }
+                }
+                self.one_of = one_of;
+            }
+            Value::Object { content, .. } if !content.is_empty() => {
+                self.walk_nested(shape, path, false);
+            }
+            Value::Tagged { .. } => self.walk_nested(shape, path, true),
+            Value::Array { .. }
+            | Value::Tuple { .. }
+            | Value::Map { .. }
+            | Value::Recursive { .. } => self.walk_nested(shape, path, false),
+            leaf => self.push(path.to_string(), leaf.clone()),
+        }
+    }
+}
+
+impl Display for LeafPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let absent = if self.absent { "?" } else { "" };
+        write!(f, "{}{absent} -> {}", self.path, self.shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::InferenceOptions;
+
+    use super::*;
+
+    fn leaves(shape: &Value) -> Vec<(String, bool, bool, bool)> {
+        shape
+            .leaf_paths()
+            .map(|leaf| {
+                (
+                    leaf.to_string(),
+                    leaf.absent,
+                    leaf.optional_ancestor,
+                    leaf.one_of,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn absent_keys_and_optional_parents_mark_descendants() {
+        let shape = Value::from_sources(&[
+            r#"{"a": {"b": 1, "c": [true]}, "d": {"e": "x"}, "f": null}"#.to_string(),
+            r#"{"a": null, "d": {"e": "y"}, "f": 1.5}"#.to_string(),
+            r#"{"a": {"b": 2, "c": []}}"#.to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            leaves(&shape),
+            [
+                ("$.a.b -> Integer<unsigned>".to_string(), false, true, false),
+                ("$.a.c[*] -> Boolean".to_string(), false, true, false),
+                ("$.d.e -> String".to_string(), false, true, false),
+                ("$.f? -> Option<Number>".to_string(), true, false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn one_of_variants_branch_at_the_same_path() {
+        let shape = Value::from_sources(&[
+            r#"{"v": {"x": 1}, "w": [1, "a"]}"#.to_string(),
+            r#"{"v": "s", "w": [2, "b"]}"#.to_string(),
+            r#"{"v": null, "w": [3, "c"]}"#.to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            leaves(&shape),
+            [
+                ("$.v -> Option<String>".to_string(), false, false, true),
+                ("$.v.x -> Integer<unsigned>".to_string(), false, true, true),
+                (
+                    "$.w[0] -> Integer<unsigned>".to_string(),
+                    false,
+                    false,
+                    false
+                ),
+                ("$.w[1] -> String".to_string(), false, false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn tagged_maps_and_recursive_shapes_have_leaves() {
+        let options = InferenceOptions::default()
+            .with_map_threshold(2)
+            .with_discriminators();
+        let shape = Value::from_str_with_options(
+            r#"{"m": {"1": true, "2": false}, "e": [{"type": "a", "x": 1}, {"type": "b"}], "empty": {}}"#,
+            &options,
+        )
+        .unwrap();
+        let recursive = Value::from_str_with_options(
+            r#"{"v": 1, "next": {"v": 2, "next": null}}"#,
+            &InferenceOptions::default().with_recursion(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            leaves(&shape),
+            [
+                (
+                    r#"$.e[*].type -> Literal["a" | "b"]"#.to_string(),
+                    false,
+                    false,
+                    true
+                ),
+                (
+                    "$.e[*].x -> Integer<unsigned>".to_string(),
+                    false,
+                    false,
+                    true
+                ),
+                ("$.empty -> Object{}".to_string(), false, false, false),
+                ("$.m.* -> Boolean".to_string(), false, false, false),
+            ]
+        );
+        assert_eq!(
+            leaves(&recursive),
+            [
+                (
+                    "$.next -> Option<Ref<Root>>".to_string(),
+                    false,
+                    false,
+                    false
+                ),
+                ("$.v -> Integer<unsigned>".to_string(), false, false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn scalar_shapes_are_their_own_leaf() {
+        let shape = Value::from_str("1").unwrap();
+
+        assert_eq!(
+            leaves(&shape),
+            [("$ -> Integer<unsigned>".to_string(), false, false, false)]
+        );
+    }
+}
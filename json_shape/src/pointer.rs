@@ -0,0 +1,316 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    shape::{merger::normalize, recursion::unfold},
+    value::{LiteralValue, Value, absent_keys},
+};
+
+impl Value {
+    /// Looks up the shape of the values at a [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer, eg `/orders/0/items`.
+    /// Returns `None` when the pointer is malformed or no value described by this [`JsonShape`](crate::JsonShape) contains it.
+    ///
+    /// - `Array` elements are reached by any index, `Tuple` elements by their position.
+    /// - `Map` values are reached by any key matching its pattern, and are optional as the key may be missing.
+    /// - `OneOf` and `Tagged` resolve to the union of the matching variants, optional when some variants don't match.
+    /// - Optional parents and keys that may be absent make the resolved shape optional.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use json_shape::JsonShape;
+    ///
+    /// let shape = JsonShape::from_sources(&[
+    ///     r#"{"orders": [{"items": [1, 2]}], "a/b": [1, "x"]}"#.to_string(),
+    ///     r#"{"orders": [{"items": null}], "a/b": [2, "y"]}"#.to_string(),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(shape.get("/orders/0/items").unwrap().to_string(), "Option<Array<Integer<unsigned>>>");
+    /// assert_eq!(shape.get("/orders/0/items/3").unwrap().to_string(), "Option<Integer<unsigned>>");
+    /// assert_eq!(shape.get("/a~1b/1").unwrap().to_string(), "String");
+    /// assert_eq!(shape.get("/a~1b/2"), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, pointer: &str) -> Option<Self> {
+        tokens(pointer)?
+            .iter()
+            .try_fold(self.clone(), |shape, token| step(&shape, token))
+            .map(normalize)
+    }
+
+    /// Mutably looks up the shape stored at a [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer, as [`JsonShape::get`](Self::get).
+    ///
+    /// As the stored shape is returned, optionality of parents is not propagated and unions are not built:
+    /// - `OneOf` variants can't be reached, as changing them could break their ordering.
+    /// - `Tagged` variants are reached only when a single variant contains the key.
+    /// - `Recursive` definitions are not unfolded, pointers through a `Ref` return `None`.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use json_shape::JsonShape;
+    ///
+    /// let mut shape = JsonShape::from_str(r#"{"user": {"name": "a"}}"#).unwrap();
+    /// *shape.get_mut("/user/name").unwrap() = JsonShape::Null;
+    ///
+    /// assert_eq!(shape.to_string(), "Object{user: Object{name: Null}}");
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, pointer: &str) -> Option<&mut Self> {
+        tokens(pointer)?
+            .iter()
+            .try_fold(self, |shape, token| step_mut(shape, token))
+    }
+}
+
+/// Reference tokens of `pointer`, with `~1` unescaped as `/` and `~0` as `~`
+fn tokens(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    Some(
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect(),
+    )
+}
+
+/// Reference token of `key`, with `~` escaped as `~0` and `/` as `~1`
+pub(crate) fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Array index of a reference token, without leading zeros. `-` refers to the element after the last one, which never exists.
+fn index(token: &str) -> Option<usize> {
+    if token.len() > 1 && token.starts_with('0') || !token.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
+fn step(shape: &Value, token: &str) -> Option<Value> {
+    match shape {
+        Value::Object {
+            content,
+            optional,
+            absent,
+        } => content.get(token).map(|value| {
+            let absent = absent_keys(content, absent.as_ref());
+            optional_if(value.clone(), *optional || absent.contains(token))
+        }),
+        Value::Array {
+            r#type, optional, ..
+        } => {
+            index(token)?;
+            Some(optional_if(*r#type.clone(), *optional))
+        }
+        Value::Tuple { elements, optional } => elements
+            .get(index(token)?)
+            .map(|element| optional_if(element.clone(), *optional)),
+        Value::Map { r#type, key, .. } => key
+            .as_ref()
+            .is_none_or(|key| key.matches(token))
+            .then(|| r#type.clone().as_optional()),
+        Value::OneOf { variants, optional } => union(
+            variants.iter().map(|variant| step(variant, token)),
+            *optional,
+        ),
+        Value::Tagged {
+            tag,
+            variants,
+            optional,
+        } if tag == token => Some(Value::Literal {
+            values: variants
+                .keys()
+                .map(|value| LiteralValue::String(value.clone()))
+                .collect(),
+            optional: *optional,
+        }),
+        Value::Tagged {
+            variants, optional, ..
+        } => union(
+            variants.values().map(|variant| step(variant, token)),
+            *optional,
+        ),
+        Value::Recursive { name, shape } => step(&unfold(name, shape), token),
+        _ => None,
+    }
+}
+
+fn step_mut<'a>(shape: &'a mut Value, token: &str) -> Option<&'a mut Value> {
+    match shape {
+        Value::Object { content, .. } => content.get_mut(token),
+        Value::Array { r#type, .. } => {
+            index(token)?;
+            Some(r#type)
+        }
+        Value::Tuple { elements, .. } => elements.get_mut(index(token)?),
+        Value::Map { r#type, key, .. } => key
+            .as_ref()
+            .is_none_or(|key| key.matches(token))
+            .then_some(r#type),
+        Value::Tagged { tag, variants, .. } if tag != token => {
+            let mut matching = variants
+                .values_mut()
+                .filter_map(|variant| step_mut(variant, token));
+            let first = matching.next()?;
+            matching.next().is_none().then_some(first)
+        }
+        Value::Recursive { shape, .. } => step_mut(shape, token),
+        _ => None,
+    }
+}
+
+/// Union of the shapes resolved in each variant, optional when any variant doesn't resolve
+fn union(resolved: impl Iterator<Item = Option<Value>>, optional: bool) -> Option<Value> {
+    let mut missing = optional;
+    let mut variants = BTreeSet::new();
+    for shape in resolved {
+        match shape {
+            Some(shape) => {
+                variants.insert(shape);
+            }
+            None => missing = true,
+        }
+    }
+    if variants.is_empty() {
+        return None;
+    }
+    Some(optional_if(
+        normalize(Value::OneOf {
+            variants,
+            optional: false,
+        }),
+        missing,
+    ))
+}
+
+fn optional_if(shape: Value, optional: bool) -> Value {
+    if optional { shape.as_optional() } else { shape }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{InferenceOptions, test_utils::shape};
+
+    use super::*;
+
+    fn get(shape: &Value, pointer: &str) -> Option<String> {
+        shape.get(pointer).map(|shape| shape.to_string())
+    }
+
+    #[test]
+    fn pointers_follow_rfc_6901() {
+        let shape = Value::from_str(r#"{"a/b": {"m~n": [true]}, "": 1, "01": null}"#).unwrap();
+
+        assert_eq!(get(&shape, ""), Some(shape.to_string()));
+        assert_eq!(get(&shape, "/a~1b/m~0n/0"), Some("Boolean".to_string()));
+        assert_eq!(get(&shape, "/"), Some("Integer<unsigned>".to_string()));
+        assert_eq!(get(&shape, "/01"), Some("Null".to_string()));
+        assert_eq!(get(&shape, "a~1b"), None);
+        assert_eq!(get(&shape, "/a~1b/m~0n/01"), None);
+        assert_eq!(get(&shape, "/a~1b/m~0n/-"), None);
+        assert_eq!(get(&shape, "/a~1b/m~0n/0/x"), None);
+        assert_eq!(get(&shape, "/missing"), None);
+    }
+
+    #[test]
+    fn optional_parents_and_absent_keys_propagate_optionality() {
+        let shape = shape(
+            &[
+                r#"{"user": {"name": "a", "tags": [1, "x"]}}"#,
+                r#"{"user": null}"#,
+                r#"{"user": {"tags": [2, "y"]}}"#,
+            ],
+            &InferenceOptions::default(),
+        );
+
+        assert_eq!(
+            get(&shape, "/user/name"),
+            Some("Option<String>".to_string())
+        );
+        assert_eq!(
+            get(&shape, "/user/tags/1"),
+            Some("Option<String>".to_string())
+        );
+        assert_eq!(get(&shape, "/user/tags/2"), None);
+    }
+
+    #[test]
+    fn one_of_resolves_to_the_union_of_matching_variants() {
+        let shape = shape(
+            &[r#"[{"a": 1}, [2], "x"]"#, r#"[{"a": "b"}, [true]]"#],
+            &InferenceOptions::default(),
+        );
+
+        assert_eq!(
+            shape.to_string(),
+            "Array<OneOf[String | Array<OneOf[Boolean | Integer<unsigned>]> | Object{a: OneOf[Integer<unsigned> | String]}]>"
+        );
+        assert_eq!(
+            get(&shape, "/0/a"),
+            Some("OneOf[Null | Integer<unsigned> | String]".to_string())
+        );
+        assert_eq!(
+            get(&shape, "/0/0"),
+            Some("OneOf[Null | Boolean | Integer<unsigned>]".to_string())
+        );
+        assert_eq!(get(&shape, "/0/b"), None);
+    }
+
+    #[test]
+    fn maps_tagged_and_recursive_shapes_resolve_their_values() {
+        let options = InferenceOptions::default()
+            .with_map_threshold(2)
+            .with_discriminators()
+            .with_recursion();
+        let shape = shape(
+            &[
+                r#"{"scores": {"1": 2.5, "2": 3}, "events": [{"type": "click", "x": 1}, {"type": "key", "key": "a"}]}"#,
+                r#"{"scores": {}, "events": [], "tree": {"value": 1, "children": [{"value": 2, "children": []}]}}"#,
+            ],
+            &options,
+        );
+
+        assert_eq!(get(&shape, "/scores/7"), Some("Option<Number>".to_string()));
+        assert_eq!(get(&shape, "/scores/x"), None);
+        assert_eq!(
+            get(&shape, "/events/0/type"),
+            Some(r#"Literal["click" | "key"]"#.to_string())
+        );
+        assert_eq!(
+            get(&shape, "/events/0/x"),
+            Some("Option<Integer<unsigned>>".to_string())
+        );
+        assert_eq!(
+            get(&shape, "/tree/children/0/children/0/value"),
+            Some("Option<Integer<unsigned>>".to_string())
+        );
+    }
+
+    #[test]
+    fn get_mut_reaches_stored_shapes() {
+        let mut shape = shape(
+            &[
+                r#"{"a": [{"b": 1}], "c": [1, "x"], "d": 1}"#,
+                r#"{"d": "x"}"#,
+            ],
+            &InferenceOptions::default(),
+        );
+
+        *shape.get_mut("/a/0/b").unwrap() = Value::Null;
+        *shape.get_mut("/c/1").unwrap() = Value::Unknown;
+
+        assert_eq!(
+            shape.to_string(),
+            "Object{a?: Array<Object{b: Null}>, c?: Tuple(Integer<unsigned>, Unknown), d: OneOf[Integer<unsigned> | String]}"
+        );
+        assert_eq!(shape.get_mut("/d/0"), None);
+        assert_eq!(shape.get_mut("/c/2"), None);
+    }
+}
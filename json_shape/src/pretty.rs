@@ -0,0 +1,570 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::value::{LiteralValue, Value, absent_keys, display_key, display_length};
+
+const ELLIPSIS: &str = "…";
+
+/// Notation of [`JsonShape::pretty`](crate::JsonShape::pretty).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PrettyStyle {
+    /// Rust-like notation of `Display`, eg `Option<Array<String>>`.
+    #[default]
+    Rust,
+    /// TypeScript-like notation, eg `string[] | null`, without number kinds, ranges and string formats.
+    TypeScript,
+}
+
+/// Opt-in knobs for [`JsonShape::pretty`](crate::JsonShape::pretty).
+///
+/// The [`Default`] options print every object field on its own line, indented by 2 spaces,
+/// in the notation parsed by [`JsonShape::parse_shape`](crate::JsonShape::parse_shape).
+///
+/// ```rust
+/// use json_shape::{JsonShape, PrettyOptions};
+///
+/// let shape = JsonShape::parse_shape("Object{a: Option<Object{b: Array<Object{c: Number}>}>}").unwrap();
+///
+/// assert_eq!(
+///     shape.pretty(&PrettyOptions::default().with_max_depth(2)),
+///     "Object{\n  a: Option<Object{\n    b: Array<…>\n  }>\n}"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyOptions {
+    /// Number of spaces per nesting level.
+    pub indent: usize,
+    /// Nesting level of objects, arrays, tuples and maps from which their content is elided as `…`, `0` eliding the content of the root. `None` prints every level.
+    pub max_depth: Option<usize>,
+    /// Maximum number of fields, elements and variants printed per shape, the others being elided as `… N more`. `None` prints all of them.
+    pub max_width: Option<usize>,
+    /// Prints sibling fields and `Tagged` variants with the same shape once, eg `id, parent_id: Integer<unsigned>`.
+    pub collapse_repeated: bool,
+    /// Colours shapes by type with ANSI escape codes, eg numbers in cyan and strings in green.
+    pub colors: bool,
+    /// Notation of the printed shapes.
+    pub style: PrettyStyle,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            max_depth: None,
+            max_width: None,
+            collapse_repeated: false,
+            colors: false,
+            style: PrettyStyle::Rust,
+        }
+    }
+}
+
+impl PrettyOptions {
+    /// Indents nesting levels by `indent` spaces.
+    #[must_use]
+    pub const fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Elides the content of shapes nested `depth` levels deep.
+    #[must_use]
+    pub const fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Prints at most `width` fields, elements and variants per shape.
+    #[must_use]
+    pub const fn with_max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Enables collapsing sibling fields and variants with the same shape.
+    #[must_use]
+    pub const fn with_collapsed_repeats(mut self) -> Self {
+        self.collapse_repeated = true;
+        self
+    }
+
+    /// Enables ANSI colours.
+    #[must_use]
+    pub const fn with_colors(mut self) -> Self {
+        self.colors = true;
+        self
+    }
+
+    /// Prints shapes in `style` notation.
+    #[must_use]
+    pub const fn with_style(mut self, style: PrettyStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Value {
+    /// Prints this [`JsonShape`](crate::JsonShape) over multiple lines, with the layout of [`PrettyOptions`].
+    ///
+    /// Objects print a field per line, while tuples, `OneOf`s and `Tagged` unions only break lines when one of their elements does.
+    /// ```
+    /// use json_shape::{JsonShape, PrettyOptions, PrettyStyle};
+    ///
+    /// let shape = JsonShape::from_sources(&[
+    ///     r#"{"id": 1, "tags": ["a"], "owner": {"name": "x"}}"#.to_string(),
+    ///     r#"{"id": 2, "tags": [], "owner": null}"#.to_string(),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     shape.pretty(&PrettyOptions::default()),
+    ///     "Object{\n  id: Integer<unsigned>,\n  owner: Option<Object{\n    name: String\n  }>,\n  tags: Array<String>\n}"
+    /// );
+    /// assert_eq!(
+    ///     shape.pretty(&PrettyOptions::default().with_style(PrettyStyle::TypeScript)),
+    ///     "{\n  id: number;\n  owner: {\n    name: string;\n  } | null;\n  tags: string[];\n}"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn pretty(&self, options: &PrettyOptions) -> String {
+        Printer { options }.shape(self, 0)
+    }
+}
+
+struct Printer<'a> {
+    options: &'a PrettyOptions,
+}
+
+impl Printer<'_> {
+    const fn is_typescript(&self) -> bool {
+        matches!(self.options.style, PrettyStyle::TypeScript)
+    }
+
+    fn is_elided(&self, depth: usize) -> bool {
+        self.options.max_depth.is_some_and(|max| depth >= max)
+    }
+
+    fn paint(&self, text: String, color: u8) -> String {
+        if self.options.colors {
+            format!("\x1b[{color}m{text}\x1b[0m")
+        } else {
+            text
+        }
+    }
+
+    fn optional(&self, text: String, optional: bool) -> String {
+        match (optional, self.is_typescript()) {
+            (false, _) => text,
+            (true, false) => format!("Option<{text}>"),
+            (true, true) => format!("{text} | null"),
+        }
+    }
+
+    /// Keeps at most `max_width` items, followed by `… N more`
+    fn limit(&self, mut items: Vec<String>) -> Vec<String> {
+        if let Some(width) = self.options.max_width
+            && items.len() > width
+        {
+            let more = items.len() - width;
+            items.truncate(width);
+            items.push(format!("{ELLIPSIS} {more} more"));
+        }
+        items
+    }
+
+    /// `open` and `close` around `items`, on their own lines when `lines` or when an item is multi-line
+    fn block(
+        &self,
+        open: &str,
+        items: &[String],
+        separator: &str,
+        close: &str,
+        lines: bool,
+    ) -> String {
+        if items.is_empty() {
+            format!("{open}{close}")
+        } else if lines || items.iter().any(|item| item.contains('\n')) {
+            let padding = " ".repeat(self.options.indent);
+            let items = items
+                .iter()
+                .map(|item| {
+                    item.lines()
+                        .map(|line| format!("{padding}{line}"))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .collect::<Vec<_>>()
+                .join(&format!("{separator}\n"));
+            format!("{open}\n{items}\n{close}")
+        } else {
+            format!("{open}{}{close}", items.join(&format!("{separator} ")))
+        }
+    }
+
+    fn shape(&self, shape: &Value, depth: usize) -> String {
+        match shape {
+            Value::Array {
+                r#type,
+                optional,
+                length,
+            } => {
+                let array = if self.is_elided(depth) {
+                    if self.is_typescript() {
+                        format!("{ELLIPSIS}[]")
+                    } else {
+                        format!("Array<{ELLIPSIS}>{}", display_length(length.as_ref()))
+                    }
+                } else {
+                    let item = self.shape(r#type, depth + 1);
+                    if !self.is_typescript() {
+                        format!("Array<{item}>{}", display_length(length.as_ref()))
+                    } else if is_union(r#type) {
+                        format!("({item})[]")
+                    } else {
+                        format!("{item}[]")
+                    }
+                };
+                self.optional(array, *optional)
+            }
+            Value::Object {
+                content,
+                optional,
+                absent,
+            } => self.optional(
+                self.object(content, &absent_keys(content, absent.as_ref()), depth, None),
+                *optional,
+            ),
+            Value::Tuple { elements, optional } => {
+                let elements = if self.is_elided(depth) {
+                    vec![ELLIPSIS.to_string()]
+                } else {
+                    self.limit(
+                        elements
+                            .iter()
+                            .map(|element| self.shape(element, depth + 1))
+                            .collect(),
+                    )
+                };
+                let tuple = if self.is_typescript() {
+                    self.block("[", &elements, ",", "]", false)
+                } else {
+                    self.block("Tuple(", &elements, ",", ")", false)
+                };
+                self.optional(tuple, *optional)
+            }
+            Value::OneOf { variants, optional } => {
+                let items = self.limit(
+                    variants
+                        .iter()
+                        .map(|variant| self.shape(variant, depth))
+                        .collect(),
+                );
+                if self.is_typescript() {
+                    let nullable = *optional && !variants.contains(&Value::Null);
+                    self.optional(items.join(" | "), nullable)
+                } else {
+                    self.optional(self.block("OneOf[", &items, " |", "]", false), *optional)
+                }
+            }
+            Value::Map {
+                r#type,
+                key,
+                optional,
+            } => {
+                let value = if self.is_elided(depth) {
+                    ELLIPSIS.to_string()
+                } else {
+                    self.shape(r#type, depth + 1)
+                };
+                let map = if self.is_typescript() {
+                    format!("Record<string, {value}>")
+                } else {
+                    let key = key.map_or_else(|| "String".to_string(), |key| key.to_string());
+                    format!("Map<{key}, {value}>")
+                };
+                self.optional(map, *optional)
+            }
+            Value::Recursive { name, shape } => {
+                let name = self.paint(name.clone(), 34);
+                let shape = self.shape(shape, depth);
+                if self.is_typescript() {
+                    format!("/* {name} */ {shape}")
+                } else {
+                    format!("Recursive<{name}: {shape}>")
+                }
+            }
+            Value::Tagged {
+                tag,
+                variants,
+                optional,
+            } => self.optional(self.tagged(tag, variants, depth), *optional),
+            leaf => self.leaf(leaf),
+        }
+    }
+
+    fn leaf(&self, leaf: &Value) -> String {
+        let color = match leaf {
+            Value::Bool { .. } => 33,
+            Value::Number { .. } => 36,
+            Value::String { .. } => 32,
+            Value::Literal { .. } => 35,
+            Value::Ref { .. } => 34,
+            _ => 90,
+        };
+        if !self.is_typescript() {
+            return self.paint(leaf.to_string(), color);
+        }
+        let text = match leaf {
+            Value::Null => "null".to_string(),
+            Value::Bool { .. } => "boolean".to_string(),
+            Value::Number { .. } => "number".to_string(),
+            Value::String { .. } => "string".to_string(),
+            Value::Literal { values, .. } => values
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Value::Ref { name, .. } => name.clone(),
+            _ => "unknown".to_string(),
+        };
+        self.paint(
+            self.optional(text, leaf.is_optional() && !leaf.is_null()),
+            color,
+        )
+    }
+
+    /// Fields of an object, after the `tag` field of a `Tagged` variant
+    fn object(
+        &self,
+        content: &BTreeMap<String, Value>,
+        absent: &BTreeSet<String>,
+        depth: usize,
+        tag: Option<String>,
+    ) -> String {
+        let (open, close) = if self.is_typescript() {
+            ("{", "}")
+        } else {
+            ("Object{", "}")
+        };
+        if content.is_empty() && tag.is_none() {
+            return format!("{open}{close}");
+        }
+        if self.is_elided(depth) {
+            return if self.is_typescript() {
+                format!("{{ {ELLIPSIS} }}")
+            } else {
+                format!("Object{{{ELLIPSIS}}}")
+            };
+        }
+        let fields = self.group(content).into_iter().map(|(keys, value)| {
+            let keys = keys
+                .iter()
+                .map(|key| {
+                    let presence = if absent.contains(*key) { "?" } else { "" };
+                    format!("{}{presence}", self.key(key))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{keys}: {}", self.shape(value, depth + 1))
+        });
+        let fields = self.limit(tag.into_iter().chain(fields).collect());
+        if self.is_typescript() {
+            let fields = fields
+                .into_iter()
+                .map(|field| format!("{field};"))
+                .collect::<Vec<_>>();
+            self.block(open, &fields, "", close, true)
+        } else {
+            self.block(open, &fields, ",", close, true)
+        }
+    }
+
+    fn tagged(&self, tag: &str, variants: &BTreeMap<String, Value>, depth: usize) -> String {
+        let variants = self.group(variants).into_iter().map(|(values, variant)| {
+            let values = values
+                .iter()
+                .map(|value| self.paint(LiteralValue::String((*value).clone()).to_string(), 35))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            match (self.is_typescript(), variant) {
+                (
+                    true,
+                    Value::Object {
+                        content, absent, ..
+                    },
+                ) => self.object(
+                    content,
+                    &absent_keys(content, absent.as_ref()),
+                    depth,
+                    Some(format!("{}: {values}", self.key(tag))),
+                ),
+                _ => format!("{values}: {}", self.shape(variant, depth)),
+            }
+        });
+        let variants = self.limit(variants.collect());
+        if self.is_typescript() {
+            variants.join(" | ")
+        } else {
+            self.block(
+                &format!("Tagged<{}>[", self.key(tag)),
+                &variants,
+                " |",
+                "]",
+                false,
+            )
+        }
+    }
+
+    /// Entries of `map`, grouping the keys of equal values when collapsing repeated shapes
+    fn group<'a>(&self, map: &'a BTreeMap<String, Value>) -> Vec<(Vec<&'a String>, &'a Value)> {
+        let mut groups: Vec<(Vec<&String>, &Value)> = Vec::new();
+        for (key, value) in map {
+            match groups.iter_mut().find(|(_, other)| *other == value) {
+                Some((keys, _)) if self.options.collapse_repeated => keys.push(key),
+                _ => groups.push((vec![key], value)),
+            }
+        }
+        groups
+    }
+
+    fn key(&self, key: &str) -> String {
+        let key = if self.is_typescript() {
+            let identifier = key
+                .starts_with(|char: char| char.is_ascii_alphabetic() || "_$".contains(char))
+                && key
+                    .chars()
+                    .all(|char| char.is_ascii_alphanumeric() || "_$".contains(char));
+            if identifier {
+                key.to_string()
+            } else {
+                serde_json::Value::from(key).to_string()
+            }
+        } else {
+            display_key(key)
+        };
+        self.paint(key, 1)
+    }
+}
+
+/// TypeScript unions need parentheses as array items, eg `(string | null)[]`
+fn is_union(shape: &Value) -> bool {
+    match shape {
+        Value::Null | Value::Unknown => false,
+        Value::OneOf { .. } | Value::Tagged { .. } => true,
+        Value::Literal { values, .. } => values.len() > 1 || shape.is_optional(),
+        Value::Recursive { shape, .. } => is_union(shape),
+        shape => shape.is_optional(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::InferenceOptions;
+
+    use super::*;
+
+    fn shape() -> Value {
+        Value::from_sources(&[
+            r#"{"id": 1, "parent": 2, "tags": ["a"], "owner": {"name": "x", "email": "y"}, "pair": [1, "a"]}"#
+                .to_string(),
+            r#"{"id": 2, "parent": 3, "tags": [null], "owner": null, "pair": [2, "b"]}"#.to_string(),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn default_pretty_print_parses_back() {
+        let shape = shape();
+        let pretty = shape.pretty(&PrettyOptions::default());
+
+        assert_eq!(
+            pretty,
+            "Object{
+  id: Integer<unsigned>,
+  owner: Option<Object{
+    email: String,
+    name: String
+  }>,
+  pair: Tuple(Integer<unsigned>, String),
+  parent: Integer<unsigned>,
+  tags: Array<Option<String>>
+}"
+        );
+        assert_eq!(Value::parse_shape(&pretty).unwrap(), shape);
+    }
+
+    #[test]
+    fn elides_depth_and_width_and_collapses_siblings() {
+        let options = PrettyOptions::default()
+            .with_indent(4)
+            .with_max_depth(1)
+            .with_max_width(3)
+            .with_collapsed_repeats();
+
+        assert_eq!(
+            shape().pretty(&options),
+            "Object{
+    id, parent: Integer<unsigned>,
+    owner: Option<Object{…}>,
+    pair: Tuple(…),
+    … 1 more
+}"
+        );
+        assert_eq!(
+            shape().pretty(&PrettyOptions::default().with_max_depth(0)),
+            "Object{…}"
+        );
+    }
+
+    #[test]
+    fn typescript_style() {
+        let options = InferenceOptions::default().with_discriminators();
+        let shape = Value::from_str_with_options(
+            r#"{"events": [{"type": "a", "x": 1}, {"type": "b", "x": 2}, {"type": "c"}], "kind": "k", "first name": null, "v": [1, "a", null]}"#,
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(
+            shape.pretty(
+                &PrettyOptions::default()
+                    .with_style(PrettyStyle::TypeScript)
+                    .with_collapsed_repeats()
+            ),
+            r#"{
+  events: ({
+    type: "a" | "b";
+    x: number;
+  } | {
+    type: "c";
+  })[];
+  "first name": null;
+  kind: string;
+  v: [number, string, null];
+}"#
+        );
+    }
+
+    #[test]
+    fn multi_line_unions_and_colors() {
+        let shape =
+            Value::from_sources(&[r#"{"a": 1}"#.to_string(), "[true]".to_string()]).unwrap();
+
+        assert_eq!(
+            shape.pretty(&PrettyOptions::default()),
+            "OneOf[
+  Array<Boolean> |
+  Object{
+    a: Integer<unsigned>
+  }
+]"
+        );
+        assert_eq!(
+            Value::from_str(r#"[1, "a"]"#)
+                .unwrap()
+                .pretty(&PrettyOptions::default().with_colors()),
+            "Tuple(\x1b[36mInteger<unsigned>\x1b[0m, \x1b[32mString\x1b[0m)"
+        );
+    }
+}
@@ -0,0 +1,370 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    error::Error,
+    shape::{merger::normalize, recursion::unfold},
+    value::{LiteralValue, Value},
+};
+
+use path::{Filter, JsonPath, Selector};
+
+mod path;
+
+impl Value {
+    /// Evaluates a [RFC 9535](https://www.rfc-editor.org/rfc/rfc9535) `JSONPath` query against this [`JsonShape`](crate::JsonShape),
+    /// returning the shape of its result nodelist as a [`JsonShape::Array`](Self::Array) of the shapes of every node it may select.
+    /// A query that can never match returns `Array<Unknown>`, the shape of an empty nodelist.
+    ///
+    /// - Child, wildcard, descendant, index and slice selectors are resolved against the shape, `Tuple` elements by their position.
+    /// - Filter selectors with an existence test, eg `[?@.isbn]`, keep the children where the relative query may match.
+    ///   Other filter expressions can't be decided from shapes, so every child may be selected.
+    ///
+    /// ```
+    /// use std::{fs::read_to_string, str::FromStr};
+    ///
+    /// use json_shape::JsonShape;
+    ///
+    /// let json = read_to_string("./testdata/rfc-9535-example-1.json").unwrap();
+    /// let shape = JsonShape::from_str(&json).unwrap();
+    ///
+    /// assert_eq!(shape.query("$.store.book[*].author").unwrap().to_string(), "Array<String>");
+    /// assert_eq!(shape.query("$..price").unwrap().to_string(), "Array<Number>");
+    /// assert_eq!(shape.query("$..book[?@.isbn].isbn").unwrap().to_string(), "Array<String>");
+    /// assert_eq!(shape.query("$.store.bicycle[0]").unwrap().to_string(), "Array<Unknown>");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `path` is not a valid `JSONPath` query.
+    pub fn query(&self, path: &str) -> Result<Self, Error> {
+        let nodes = evaluate(self, &JsonPath::parse(path)?);
+        let r#type = if nodes.is_empty() {
+            Self::Unknown
+        } else {
+            normalize(Self::OneOf {
+                variants: nodes,
+                optional: false,
+            })
+        };
+        Ok(Self::Array {
+            r#type: Box::new(r#type),
+            optional: false,
+            length: None,
+        })
+    }
+
+    /// Statically checks that a [RFC 9535](https://www.rfc-editor.org/rfc/rfc9535) `JSONPath` query can never match
+    /// any value described by this [`JsonShape`](crate::JsonShape), eg because of a misspelled key or an index out of a tuple.
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use json_shape::JsonShape;
+    ///
+    /// let shape = JsonShape::from_str(r#"{"user": {"name": "a", "roles": ["admin"]}}"#).unwrap();
+    ///
+    /// assert!(!shape.never_matches("$.user.roles[0]").unwrap());
+    /// assert!(shape.never_matches("$.user.nmae").unwrap());
+    /// assert!(shape.never_matches("$..roles[?@.id]").unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `path` is not a valid `JSONPath` query.
+    pub fn never_matches(&self, path: &str) -> Result<bool, Error> {
+        Ok(evaluate(self, &JsonPath::parse(path)?).is_empty())
+    }
+}
+
+/// Shapes of every node the query may select, starting from `shape`
+fn evaluate(shape: &Value, path: &JsonPath) -> BTreeSet<Value> {
+    path.segments
+        .iter()
+        .fold(BTreeSet::from([shape.clone()]), |nodes, segment| {
+            let nodes = if segment.descendant {
+                let mut descendants = BTreeSet::new();
+                for node in &nodes {
+                    collect_descendants(node, &mut descendants);
+                }
+                descendants
+            } else {
+                nodes
+            };
+            nodes
+                .iter()
+                .flat_map(|node| {
+                    segment
+                        .selectors
+                        .iter()
+                        .flat_map(|selector| select(node, selector))
+                })
+                .collect()
+        })
+}
+
+/// Collects `node` and every node nested in it. Equal nodes have equal descendants, so recursive shapes are visited once.
+fn collect_descendants(node: &Value, descendants: &mut BTreeSet<Value>) {
+    if descendants.insert(node.clone()) {
+        for child in select(node, &Selector::Wildcard) {
+            collect_descendants(&child, descendants);
+        }
+    }
+}
+
+fn select(node: &Value, selector: &Selector) -> Vec<Value> {
+    if let Selector::Filter(filter) = selector {
+        let children = select(node, &Selector::Wildcard);
+        return match filter {
+            Filter::Exists(relative) => children
+                .iter()
+                .flat_map(alternatives)
+                .filter(|child| !evaluate(child, relative).is_empty())
+                .collect(),
+            Filter::Expression(_) => children,
+        };
+    }
+    present(node)
+        .into_iter()
+        .flat_map(|value| select_present(&value, selector))
+        .collect()
+}
+
+fn select_present(value: &Value, selector: &Selector) -> Vec<Value> {
+    match (value, selector) {
+        (Value::Object { content, .. }, Selector::Name(name)) => {
+            content.get(name).cloned().into_iter().collect()
+        }
+        (Value::Object { content, .. }, Selector::Wildcard) => content.values().cloned().collect(),
+        (Value::Map { r#type, key, .. }, Selector::Name(name))
+            if key.as_ref().is_none_or(|key| key.matches(name)) =>
+        {
+            vec![*r#type.clone()]
+        }
+        (Value::Map { r#type, .. }, Selector::Wildcard) => vec![*r#type.clone()],
+        (Value::Tagged { tag, variants, .. }, Selector::Name(name)) if tag == name => {
+            vec![Value::Literal {
+                values: variants
+                    .keys()
+                    .map(|value| LiteralValue::String(value.clone()))
+                    .collect(),
+                optional: false,
+            }]
+        }
+        (Value::Tagged { tag, variants, .. }, Selector::Wildcard) => {
+            let mut children = select_present(value, &Selector::Name(tag.clone()));
+            children.extend(
+                variants
+                    .values()
+                    .flat_map(|variant| select(variant, selector)),
+            );
+            children
+        }
+        (Value::Tagged { variants, .. }, Selector::Name(_)) => variants
+            .values()
+            .flat_map(|variant| select(variant, selector))
+            .collect(),
+        (Value::Array { r#type, .. }, _) if r#type.is_unknown() => Vec::new(),
+        (Value::Array { r#type, length, .. }, Selector::Index(index)) => {
+            let fits = length.as_ref().is_none_or(|length| {
+                let max = i64::try_from(length.max).unwrap_or(i64::MAX);
+                if *index < 0 {
+                    -index <= max
+                } else {
+                    *index < max
+                }
+            });
+            if fits {
+                vec![*r#type.clone()]
+            } else {
+                Vec::new()
+            }
+        }
+        (Value::Array { .. }, Selector::Slice { step: Some(0), .. }) => Vec::new(),
+        (Value::Array { r#type, .. }, Selector::Wildcard | Selector::Slice { .. }) => {
+            vec![*r#type.clone()]
+        }
+        (Value::Tuple { elements, .. }, Selector::Index(index)) => {
+            let len = i64::try_from(elements.len()).unwrap_or(i64::MAX);
+            let index = if *index < 0 { len + index } else { *index };
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| elements.get(index))
+                .cloned()
+                .into_iter()
+                .collect()
+        }
+        (Value::Tuple { elements, .. }, Selector::Wildcard) => elements.clone(),
+        (Value::Tuple { elements, .. }, Selector::Slice { start, end, step }) => {
+            slice(elements.len(), *start, *end, step.unwrap_or(1))
+                .into_iter()
+                .map(|index| elements[index].clone())
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Non null shapes a node may assume, with `Recursive` definitions unfolded
+fn present(node: &Value) -> Vec<Value> {
+    match node {
+        Value::Null => Vec::new(),
+        Value::OneOf { variants, .. } => variants.iter().flat_map(present).collect(),
+        Value::Recursive { name, shape } => present(&unfold(name, shape)),
+        value => vec![value.clone().as_non_optional()],
+    }
+}
+
+/// Shapes a node may assume, including `Null` when optional, so filters can keep only the matching ones
+fn alternatives(node: &Value) -> Vec<Value> {
+    match node {
+        Value::OneOf { variants, optional } => variants
+            .iter()
+            .cloned()
+            .chain(optional.then_some(Value::Null))
+            .collect(),
+        Value::Recursive { .. } => vec![node.clone()],
+        value if value.is_optional() && !value.is_null() => {
+            vec![Value::Null, value.clone().as_non_optional()]
+        }
+        value => vec![value.clone()],
+    }
+}
+
+/// Indices selected by a slice of an array of length `len`, as in [RFC 9535](https://www.rfc-editor.org/rfc/rfc9535#name-array-slice-selector)
+fn slice(len: usize, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<usize> {
+    let len = i64::try_from(len).unwrap_or(i64::MAX);
+    let normalize = |index: i64| if index < 0 { len + index } else { index };
+    let mut indices = Vec::new();
+    if step > 0 {
+        let lower = normalize(start.unwrap_or(0)).clamp(0, len);
+        let upper = normalize(end.unwrap_or(len)).clamp(0, len);
+        let mut index = lower;
+        while index < upper {
+            indices.push(index);
+            index += step;
+        }
+    } else if step < 0 {
+        let upper = normalize(start.unwrap_or(len - 1)).clamp(-1, len - 1);
+        let lower = normalize(end.unwrap_or(-len - 1)).clamp(-1, len - 1);
+        let mut index = upper;
+        while lower < index {
+            indices.push(index);
+            index += step;
+        }
+    }
+    indices
+        .into_iter()
+        .filter_map(|index| usize::try_from(index).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::InferenceOptions;
+
+    use super::*;
+
+    fn query(shape: &Value, path: &str) -> String {
+        shape.query(path).unwrap().to_string()
+    }
+
+    #[test]
+    fn slices_follow_rfc_9535() {
+        assert_eq!(slice(7, Some(1), Some(3), 1), [1, 2]);
+        assert_eq!(slice(7, Some(5), None, 1), [5, 6]);
+        assert_eq!(slice(7, Some(1), Some(5), 2), [1, 3]);
+        assert_eq!(slice(7, Some(5), Some(1), -2), [5, 3]);
+        assert_eq!(slice(7, None, None, -1), [6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(slice(7, Some(-2), None, 1), [5, 6]);
+        assert_eq!(slice(3, Some(10), Some(-10), -1), [2, 1, 0]);
+        assert!(slice(7, None, None, 0).is_empty());
+    }
+
+    #[test]
+    fn tuples_are_selected_by_position() {
+        let shape = Value::from_str(r#"[1, "a", true, null]"#).unwrap();
+
+        assert_eq!(query(&shape, "$[1]"), "Array<String>");
+        assert_eq!(query(&shape, "$[-1]"), "Array<Null>");
+        assert_eq!(
+            query(&shape, "$[0:2]"),
+            "Array<OneOf[Integer<unsigned> | String]>"
+        );
+        assert_eq!(query(&shape, "$[4]"), "Array<Unknown>");
+        assert_eq!(query(&shape, "$[::0]"), "Array<Unknown>");
+    }
+
+    #[test]
+    fn nullable_and_union_nodes_select_from_their_present_shapes() {
+        let shape = Value::from_sources(&[
+            r#"{"a": {"b": 1}, "c": [1]}"#.to_string(),
+            r#"{"a": null, "c": "x"}"#.to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            query(&shape, "$.a"),
+            "Array<Option<Object{b: Integer<unsigned>}>>"
+        );
+        assert_eq!(query(&shape, "$.a.b"), "Array<Integer<unsigned>>");
+        assert_eq!(query(&shape, "$.c[0]"), "Array<Integer<unsigned>>");
+        assert_eq!(
+            query(&shape, "$[?@.b]"),
+            "Array<Object{b: Integer<unsigned>}>"
+        );
+        assert_eq!(
+            query(&shape, "$[?@.b == 1]"),
+            "Array<OneOf[Null | String | Array<Integer<unsigned>> | Object{b: Integer<unsigned>}]>"
+        );
+    }
+
+    #[test]
+    fn array_length_bounds_rule_out_indices() {
+        let options = InferenceOptions::default().with_length_bounds();
+        let shape =
+            Value::from_sources_with_options(&["[1, 2]".to_string(), "[3]".to_string()], &options)
+                .unwrap();
+
+        assert_eq!(query(&shape, "$[1]"), "Array<Integer<unsigned>>");
+        assert_eq!(query(&shape, "$[-2]"), "Array<Integer<unsigned>>");
+        assert!(shape.never_matches("$[2]").unwrap());
+        assert!(shape.never_matches("$[-3]").unwrap());
+    }
+
+    #[test]
+    fn descendants_of_maps_tagged_and_recursive_shapes() {
+        let options = InferenceOptions::default()
+            .with_map_threshold(2)
+            .with_discriminators()
+            .with_recursion();
+        let shape = Value::from_sources_with_options(
+            &[
+                r#"{"scores": {"1": 2.5, "2": 3}, "events": [{"type": "click", "x": 1}, {"type": "key", "key": "a"}]}"#.to_string(),
+                r#"{"tree": {"value": "a", "children": [{"value": "b", "children": []}]}}"#.to_string(),
+            ],
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(query(&shape, "$.scores['7']"), "Array<Number>");
+        assert!(shape.never_matches("$.scores.x").unwrap());
+        assert_eq!(
+            query(&shape, "$.events[*].type"),
+            r#"Array<Literal["click" | "key"]>"#
+        );
+        assert_eq!(query(&shape, "$..value"), "Array<String>");
+        assert_eq!(query(&shape, "$..children[*].value"), "Array<String>");
+        assert_eq!(query(&shape, "$..key"), "Array<String>");
+    }
+
+    #[test]
+    fn invalid_paths_are_errors() {
+        let shape = Value::from_str("[1]").unwrap();
+
+        assert_eq!(
+            shape.query("$[").unwrap_err().to_string(),
+            "invalid JSONPath `$[`: expected selector at 2..2"
+        );
+    }
+}
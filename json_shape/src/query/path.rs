@@ -0,0 +1,455 @@
+use crate::error::Error;
+
+/// Largest integer accepted in index and slice selectors, `2^53 - 1`
+const MAX_INTEGER: i64 = 9_007_199_254_740_991;
+
+/// Parsed [RFC 9535](https://www.rfc-editor.org/rfc/rfc9535) `JSONPath` query, relative to `$` or to `@` in filters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JsonPath {
+    pub(crate) segments: Vec<Segment>,
+}
+
+/// Child segment, `.name` or `[...]`, or descendant segment, `..name` or `..[...]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Segment {
+    pub(crate) descendant: bool,
+    pub(crate) selectors: Vec<Selector>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Selector {
+    /// `.name`, `['name']`
+    Name(String),
+    /// `.*`, `[*]`
+    Wildcard,
+    /// `[1]`, `[-1]`
+    Index(i64),
+    /// `[start:end:step]`
+    Slice {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+    },
+    /// `[?expression]`
+    Filter(Filter),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Filter {
+    /// Existence test of a relative query, eg `[?@.isbn]`
+    Exists(JsonPath),
+    /// Any other logical expression, which can't be decided from shapes alone
+    Expression(String),
+}
+
+impl JsonPath {
+    pub(crate) fn parse(path: &str) -> Result<Self, Error> {
+        let mut parser = Parser { path, position: 0 };
+        parser.expect('$', "expected root identifier `$`")?;
+        let segments = parser.segments()?;
+        if parser.position < path.len() {
+            return Err(parser.error("unexpected character"));
+        }
+        Ok(Self { segments })
+    }
+}
+
+struct Parser<'a> {
+    path: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.path[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.position += char.len_utf8();
+        Some(char)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        let found = self.peek() == Some(expected);
+        if found {
+            self.position += expected.len_utf8();
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: char, reason: &str) -> Result<(), Error> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(reason))
+        }
+    }
+
+    fn skip_blanks(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn error(&self, reason: &str) -> Error {
+        let end = self
+            .peek()
+            .map_or(self.position, |char| self.position + char.len_utf8());
+        Error::InvalidJsonPath {
+            path: self.path.to_string(),
+            reason: reason.to_string(),
+            span: self.position..end,
+        }
+    }
+
+    fn segments(&mut self) -> Result<Vec<Segment>, Error> {
+        let mut segments = Vec::new();
+        loop {
+            let before_blanks = self.position;
+            self.skip_blanks();
+            let segment = match self.peek() {
+                Some('.') => {
+                    self.position += 1;
+                    if self.eat('.') {
+                        let selectors = if self.peek() == Some('[') {
+                            self.bracketed()?
+                        } else {
+                            vec![self.shorthand()?]
+                        };
+                        Segment {
+                            descendant: true,
+                            selectors,
+                        }
+                    } else {
+                        Segment {
+                            descendant: false,
+                            selectors: vec![self.shorthand()?],
+                        }
+                    }
+                }
+                Some('[') => Segment {
+                    descendant: false,
+                    selectors: self.bracketed()?,
+                },
+                _ => {
+                    self.position = before_blanks;
+                    return Ok(segments);
+                }
+            };
+            segments.push(segment);
+        }
+    }
+
+    /// `*` or a member name shorthand, after `.` or `..`
+    fn shorthand(&mut self) -> Result<Selector, Error> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let start = self.position;
+        match self.peek() {
+            Some(char) if char.is_ascii_alphabetic() || char == '_' || !char.is_ascii() => {
+                self.bump();
+            }
+            _ => return Err(self.error("expected member name or `*`")),
+        }
+        while let Some(char) = self.peek() {
+            if char.is_ascii_alphanumeric() || char == '_' || !char.is_ascii() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        Ok(Selector::Name(self.path[start..self.position].to_string()))
+    }
+
+    fn bracketed(&mut self) -> Result<Vec<Selector>, Error> {
+        self.expect('[', "expected `[`")?;
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_blanks();
+            selectors.push(self.selector()?);
+            self.skip_blanks();
+            if self.eat(']') {
+                return Ok(selectors);
+            }
+            self.expect(',', "expected `,` or `]`")?;
+        }
+    }
+
+    fn selector(&mut self) -> Result<Selector, Error> {
+        match self.peek() {
+            Some(quote @ ('\'' | '"')) => Ok(Selector::Name(self.string(quote)?)),
+            Some('*') => {
+                self.position += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some('?') => {
+                self.position += 1;
+                self.filter()
+            }
+            _ => {
+                let start = self.integer()?;
+                self.skip_blanks();
+                if self.peek() != Some(':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("expected selector"));
+                }
+                self.position += 1;
+                self.skip_blanks();
+                let end = self.integer()?;
+                self.skip_blanks();
+                let step = if self.eat(':') {
+                    self.skip_blanks();
+                    self.integer()?
+                } else {
+                    None
+                };
+                Ok(Selector::Slice { start, end, step })
+            }
+        }
+    }
+
+    /// Optional integer, without leading zeros or `-0`, in the I-JSON range
+    fn integer(&mut self) -> Result<Option<i64>, Error> {
+        let start = self.position;
+        let negative = self.eat('-');
+        let digits = self.position;
+        while self.peek().is_some_and(|char| char.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let digits = &self.path[digits..self.position];
+        if digits.is_empty() {
+            if negative {
+                return Err(self.error("expected digits"));
+            }
+            return Ok(None);
+        }
+        if digits.starts_with('0') && (digits.len() > 1 || negative) {
+            self.position = start;
+            return Err(self.error("integers can't have leading zeros"));
+        }
+        match self.path[start..self.position].parse::<i64>() {
+            Ok(integer) if (-MAX_INTEGER..=MAX_INTEGER).contains(&integer) => Ok(Some(integer)),
+            _ => {
+                self.position = start;
+                Err(self.error("integer out of range"))
+            }
+        }
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, Error> {
+        self.position += 1;
+        let mut string = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(char) if char == quote => {
+                    self.position += 1;
+                    return Ok(string);
+                }
+                Some('\\') => {
+                    self.position += 1;
+                    string.push(self.escape(quote)?);
+                }
+                Some(char) if char < ' ' => {
+                    return Err(self.error("control characters must be escaped"));
+                }
+                Some(char) => {
+                    self.position += char.len_utf8();
+                    string.push(char);
+                }
+            }
+        }
+    }
+
+    fn escape(&mut self, quote: char) -> Result<char, Error> {
+        let escaped = match self.peek() {
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some(char @ ('/' | '\\')) => char,
+            Some(char) if char == quote => char,
+            Some('u') => {
+                self.position += 1;
+                let high = self.hex()?;
+                if !(0xD800..0xDC00).contains(&high) {
+                    return char::from_u32(high).ok_or_else(|| self.error("invalid escape"));
+                }
+                if !(self.eat('\\') && self.eat('u')) {
+                    return Err(self.error("expected low surrogate"));
+                }
+                let low = self.hex()?;
+                if !(0xDC00..0xE000).contains(&low) {
+                    return Err(self.error("expected low surrogate"));
+                }
+                return char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+                    .ok_or_else(|| self.error("invalid escape"));
+            }
+            _ => return Err(self.error("invalid escape")),
+        };
+        self.position += 1;
+        Ok(escaped)
+    }
+
+    fn hex(&mut self) -> Result<u32, Error> {
+        let digits = self
+            .path
+            .get(self.position..self.position + 4)
+            .filter(|digits| digits.chars().all(|char| char.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("expected 4 hexadecimal digits"))?;
+        self.position += 4;
+        u32::from_str_radix(digits, 16).map_err(|_| self.error("expected 4 hexadecimal digits"))
+    }
+
+    /// Logical expression after `?`, up to the `,` or `]` ending the selector
+    fn filter(&mut self) -> Result<Selector, Error> {
+        self.skip_blanks();
+        let start = self.position;
+        let mut depth = 0usize;
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated filter")),
+                Some(quote @ ('\'' | '"')) => {
+                    self.string(quote)?;
+                    continue;
+                }
+                Some('(' | '[') => depth += 1,
+                Some(')' | ']') if depth > 0 => depth -= 1,
+                Some(',' | ']') => break,
+                _ => (),
+            }
+            self.bump();
+        }
+        let expression = self.path[start..self.position].trim_end();
+        if expression.is_empty() {
+            return Err(self.error("expected filter expression"));
+        }
+        let mut relative = Parser {
+            path: expression,
+            position: 0,
+        };
+        let filter = match relative.eat('@').then(|| relative.segments()) {
+            Some(Ok(segments)) if relative.position == expression.len() => {
+                Filter::Exists(JsonPath { segments })
+            }
+            _ => Filter::Expression(expression.to_string()),
+        };
+        Ok(Selector::Filter(filter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selectors(path: &str) -> Vec<Selector> {
+        JsonPath::parse(path)
+            .unwrap()
+            .segments
+            .into_iter()
+            .flat_map(|segment| segment.selectors)
+            .collect()
+    }
+
+    fn reason(path: &str) -> (String, std::ops::Range<usize>) {
+        match JsonPath::parse(path) {
+            Err(Error::InvalidJsonPath { reason, span, .. }) => (reason, span),
+            other => panic!("expected invalid JSONPath, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_segments_and_selectors() {
+        let path = JsonPath::parse("$.store..book[0, -1, 1:3, ::-1, *]").unwrap();
+
+        assert_eq!(path.segments.len(), 3);
+        assert!(path.segments[1].descendant);
+        assert_eq!(
+            path.segments[2].selectors,
+            [
+                Selector::Index(0),
+                Selector::Index(-1),
+                Selector::Slice {
+                    start: Some(1),
+                    end: Some(3),
+                    step: None
+                },
+                Selector::Slice {
+                    start: None,
+                    end: None,
+                    step: Some(-1)
+                },
+                Selector::Wildcard,
+            ]
+        );
+        assert_eq!(JsonPath::parse("$").unwrap().segments, []);
+    }
+
+    #[test]
+    fn parses_names_with_escapes() {
+        assert_eq!(
+            selectors(r#"$['o']["j j"]['\'']["☺😀"].ünï_1"#),
+            [
+                Selector::Name("o".to_string()),
+                Selector::Name("j j".to_string()),
+                Selector::Name("'".to_string()),
+                Selector::Name("☺😀".to_string()),
+                Selector::Name("ünï_1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_filters() {
+        assert_eq!(
+            selectors("$..book[?@.isbn]"),
+            [
+                Selector::Name("book".to_string()),
+                Selector::Filter(Filter::Exists(JsonPath {
+                    segments: vec![Segment {
+                        descendant: false,
+                        selectors: vec![Selector::Name("isbn".to_string())]
+                    }]
+                }))
+            ]
+        );
+        assert_eq!(
+            selectors("$[?@.b == 'k,]' && (@[0] < 3)]"),
+            [Selector::Filter(Filter::Expression(
+                "@.b == 'k,]' && (@[0] < 3)".to_string()
+            ))]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_paths() {
+        assert_eq!(
+            reason("a"),
+            ("expected root identifier `$`".to_string(), 0..1)
+        );
+        assert_eq!(
+            reason("$.1"),
+            ("expected member name or `*`".to_string(), 2..3)
+        );
+        assert_eq!(
+            reason("$[01]"),
+            ("integers can't have leading zeros".to_string(), 2..3)
+        );
+        assert_eq!(reason("$[-0]"), reason("$[00]"));
+        assert_eq!(reason("$['a'"), ("expected `,` or `]`".to_string(), 5..5));
+        assert_eq!(reason("$['\\x']"), ("invalid escape".to_string(), 4..5));
+        assert_eq!(
+            reason("$[?]"),
+            ("expected filter expression".to_string(), 3..4)
+        );
+        assert_eq!(reason("$.a "), ("unexpected character".to_string(), 3..4));
+        assert_eq!(
+            reason("$[9007199254740992]"),
+            ("integer out of range".to_string(), 2..3)
+        );
+    }
+}
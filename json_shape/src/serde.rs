@@ -1,5 +1,10 @@
 #![allow(clippy::fallible_impl_from)]
-use crate::Value as JsonShape;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    InferenceOptions, Value as JsonShape,
+    shape::{apply_limits, array_shape, discriminator, number_shape, string_shape, tag_shape},
+};
 
 pub(crate) mod impls;
 
@@ -17,99 +22,120 @@ impl From<serde_json::Value> for JsonShape {
 
 impl From<&serde_json::Value> for JsonShape {
     fn from(value: &serde_json::Value) -> Self {
-        match value {
-            serde_json::Value::Null => Self::Null,
-            serde_json::Value::Bool(_) => Self::Bool { optional: false },
-            serde_json::Value::Number(_) => Self::Number { optional: false },
-            serde_json::Value::String(_) => Self::String { optional: false },
-            serde_json::Value::Array(values) => {
-                if values.len() > 1
-                    && values
-                        .iter()
-                        .map(Self::from)
-                        .all(|value| matches!(value, Self::Object { .. }))
-                {
-                    let mut iter = values.iter().map(Self::from);
-                    let Some(Self::Object { content, .. }) = iter.next() else {
-                        unreachable!("Guaranteed to be Object by all");
-                    };
-                    let content = iter
-                        .clone()
-                        .filter(|shape| matches!(shape, Self::Object { .. }))
-                        .filter_map(|content| {
-                            content.keys().map(|keys| keys.cloned().collect::<Vec<_>>())
-                        })
-                        .fold(content, |mut acc, keys| {
-                            for (key, value) in &mut acc {
-                                if !keys.iter().any(|k| k == key) {
-                                    value.to_optional_mut();
-                                }
-                            }
-                            acc
-                        });
-                    let object = iter.fold(content, |mut acc, content| {
-                        let Self::Object { content, .. } = content else {
-                            return acc;
-                        };
-                        for (key, value) in content {
-                            let old_value = acc
-                                .entry(key.clone())
-                                .or_insert_with(|| value.clone().as_optional());
-                            if let Self::OneOf { variants, .. } = old_value {
-                                variants.insert(value.clone());
-                            }
-                        }
-                        acc
-                    });
-
-                    Self::Array {
-                        r#type: Box::new(Self::Object {
-                            content: object,
-                            optional: false,
-                        }),
-                        optional: false,
-                    }
-                } else if values.len() == 1
-                    || values
-                        .windows(2)
-                        .map(|val| {
-                            (
-                                Self::from(val.first().unwrap()),
-                                Self::from(val.get(1).unwrap()),
-                            )
-                        })
-                        .all(|val| val.0 == val.1)
-                {
-                    Self::Array {
-                        r#type: Box::new(Self::from(values[0].clone())),
-                        optional: false,
-                    }
-                } else if values.len() > 1 {
-                    Self::Tuple {
-                        elements: values.iter().map(Self::from).collect(),
-                        optional: false,
-                    }
-                } else {
-                    Self::Array {
-                        r#type: Box::new(Self::Null),
-                        optional: true,
-                    }
+        Self::from_value_with_options(value, &InferenceOptions::default())
+    }
+}
+
+impl JsonShape {
+    /// Creates a [`JsonShape`] from a [`serde_json::Value`], using the specified [`InferenceOptions`]
+    /// ```
+    /// use json_shape::{InferenceOptions, JsonShape};
+    /// use serde_json::json;
+    ///
+    /// let options = InferenceOptions::default().with_literal_limit(2);
+    /// let json = json!([{"status": "active"}, {"status": "disabled"}]);
+    /// let json_shape = JsonShape::from_value_with_options(&json, &options);
+    ///
+    /// assert_eq!(
+    ///     json_shape.to_string(),
+    ///     r#"Array<Object{status: Literal["active" | "disabled"]}>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_value_with_options(value: &serde_json::Value, options: &InferenceOptions) -> Self {
+        let mut tags = BTreeSet::new();
+        if options.discriminators {
+            discover_tags(value, options, &mut tags);
+        }
+        let known = options.clone().with_tags(tags);
+        apply_limits(infer(value, &known), options)
+    }
+}
+
+/// Tags of the [`JsonShape::Tagged`] unions inferred from `value`, so that the values of the tags are known
+/// when objects are merged into their unions.
+fn discover_tags(
+    value: &serde_json::Value,
+    options: &InferenceOptions,
+    tags: &mut BTreeSet<String>,
+) {
+    match value {
+        serde_json::Value::Array(values) => {
+            let keys = values
+                .iter()
+                .map(|value| Some(value.as_object()?.keys().map(String::as_str).collect()))
+                .collect::<Option<Vec<_>>>();
+            if let Some(keys) = keys {
+                let discriminants = discriminants(values, options);
+                if let Some((tag, _)) = discriminator(&keys, &discriminants, options) {
+                    tags.insert(tag.clone());
                 }
             }
-            serde_json::Value::Object(map) => Self::Object {
-                content: map
-                    .into_iter()
-                    .map(|(k, v)| (k.clone(), Self::from(v)))
-                    .collect(),
-                optional: false,
-            },
+            for value in values {
+                discover_tags(value, options, tags);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for value in map.values() {
+                discover_tags(value, options, tags);
+            }
         }
+        _ => (),
+    }
+}
+
+fn infer(value: &serde_json::Value, options: &InferenceOptions) -> JsonShape {
+    match value {
+        serde_json::Value::Null => JsonShape::Null,
+        serde_json::Value::Bool(_) => JsonShape::Bool { optional: false },
+        serde_json::Value::Number(number) => number_shape(number, options),
+        serde_json::Value::String(value) => string_shape(value, options),
+        serde_json::Value::Array(values) => array_shape(
+            values.iter().map(|value| infer(value, options)).collect(),
+            &discriminants(values, options),
+            options,
+        ),
+        serde_json::Value::Object(map) => JsonShape::Object {
+            content: map
+                .into_iter()
+                .map(|(k, v)| match v {
+                    serde_json::Value::String(tag) if options.tags.contains(k) => {
+                        (k.clone(), tag_shape(tag.clone()))
+                    }
+                    v => (k.clone(), infer(v, options)),
+                })
+                .collect(),
+            optional: false,
+            absent: Some(BTreeSet::new()),
+        },
     }
 }
 
+/// String members of every object in `values`, when discriminators are enabled by the [`InferenceOptions`].
+fn discriminants(
+    values: &[serde_json::Value],
+    options: &InferenceOptions,
+) -> Vec<BTreeMap<String, String>> {
+    if !options.discriminators {
+        return Vec::new();
+    }
+    values
+        .iter()
+        .map(|value| {
+            value
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::value::{NumberKind, StringFormat};
     use insta::assert_snapshot;
     use serde_json::json;
 
@@ -127,8 +153,44 @@ mod tests {
         let json = json!(123.456);
         let ref_shape = JsonShape::from(&json);
         let shape = JsonShape::from(json);
-        assert_eq!(shape, JsonShape::Number { optional: false });
-        assert_eq!(ref_shape, JsonShape::Number { optional: false });
+        assert_eq!(
+            shape,
+            JsonShape::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+        );
+        assert_eq!(
+            ref_shape,
+            JsonShape::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_json_formatted_string() {
+        let json = json!(["2024-01-31T10:00:00+01:00", "2024-02-01T10:00:00Z"]);
+        let shape = JsonShape::from(&json);
+        assert_eq!(
+            shape,
+            JsonShape::Array {
+                r#type: Box::new(JsonShape::String {
+                    optional: false,
+                    format: Some(StringFormat::DateTime),
+                    length: None,
+                    examples: None
+                }),
+                optional: false,
+                length: None
+            }
+        );
+        assert_eq!(shape.to_string(), "Array<String<date-time>>");
     }
 
     #[test]
@@ -136,8 +198,24 @@ mod tests {
         let json = json!("string");
         let ref_shape = JsonShape::from(&json);
         let shape = JsonShape::from(json);
-        assert_eq!(shape, JsonShape::String { optional: false });
-        assert_eq!(ref_shape, JsonShape::String { optional: false });
+        assert_eq!(
+            shape,
+            JsonShape::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+        );
+        assert_eq!(
+            ref_shape,
+            JsonShape::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+        );
     }
 
     #[test]
@@ -157,15 +235,27 @@ mod tests {
         assert_eq!(
             shape,
             JsonShape::Array {
-                r#type: Box::new(JsonShape::Number { optional: false }),
-                optional: false
+                r#type: Box::new(JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                }),
+                optional: false,
+                length: None
             }
         );
         assert_eq!(
             ref_shape,
             JsonShape::Array {
-                r#type: Box::new(JsonShape::Number { optional: false }),
-                optional: false
+                r#type: Box::new(JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                }),
+                optional: false,
+                length: None
             }
         );
     }
@@ -179,8 +269,18 @@ mod tests {
             shape,
             JsonShape::Tuple {
                 elements: vec![
-                    JsonShape::Number { optional: false },
-                    JsonShape::String { optional: false },
+                    JsonShape::Number {
+                        optional: false,
+                        kind: NumberKind::Unsigned,
+                        range: None,
+                        examples: None,
+                    },
+                    JsonShape::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    },
                     JsonShape::Bool { optional: false },
                     JsonShape::Null
                 ],
@@ -191,8 +291,18 @@ mod tests {
             ref_shape,
             JsonShape::Tuple {
                 elements: vec![
-                    JsonShape::Number { optional: false },
-                    JsonShape::String { optional: false },
+                    JsonShape::Number {
+                        optional: false,
+                        kind: NumberKind::Unsigned,
+                        range: None,
+                        examples: None,
+                    },
+                    JsonShape::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    },
                     JsonShape::Bool { optional: false },
                     JsonShape::Null
                 ],
@@ -228,4 +338,30 @@ mod tests {
         let shape = JsonShape::from(json);
         assert_snapshot!(shape.to_string());
     }
+
+    #[test]
+    fn test_from_json_with_length_bounds() {
+        let json = json!({"name": "Naomi", "scores": [[1, 2], [3]]});
+        let options = InferenceOptions::default().with_length_bounds();
+
+        let shape = JsonShape::from_value_with_options(&json, &options);
+
+        assert_eq!(
+            shape.to_string(),
+            "Object{name: String[5..=5], scores: Array<Array<Integer<unsigned>>[1..=2]>[2..=2]}"
+        );
+    }
+
+    #[test]
+    fn test_from_json_with_discriminators() {
+        let json = json!({"events": [{"kind": "ping"}, {"kind": "message", "text": "hi"}]});
+        let options = InferenceOptions::default().with_discriminators();
+
+        let shape = JsonShape::from_value_with_options(&json, &options);
+
+        assert_eq!(
+            shape.to_string(),
+            r#"Object{events: Array<Tagged<kind>["message": Object{text: String} | "ping": Object{}]>}"#
+        );
+    }
 }
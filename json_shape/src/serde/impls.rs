@@ -24,6 +24,7 @@ impl JsonVisitor<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::value::NumberKind;
 
     #[test]
     fn from_value_null() {
@@ -38,6 +39,14 @@ mod tests {
         let value = serde_json::Value::Number(3.into());
         let visitor = JsonVisitor::from(&value);
         assert_eq!(visitor.value(), &value);
-        assert_eq!(visitor.shape(), &JsonShape::Number { optional: false });
+        assert_eq!(
+            visitor.shape(),
+            &JsonShape::Number {
+                optional: false,
+                kind: NumberKind::Unsigned,
+                range: None,
+                examples: None,
+            }
+        );
     }
 }
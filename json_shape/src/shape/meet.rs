@@ -0,0 +1,534 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    IsSubset,
+    shape::{
+        merger::normalize,
+        recursion::{rename, unfold},
+    },
+    value::{LengthRange, LiteralValue, NumberRange, StringFormat, Value, absent_keys},
+};
+
+/// Intersects two shapes into the most general shape describing only values of both, in the canonical form of [`normalize`].
+/// Returns `None` when no value fits both shapes.
+/// - `Number * Integer<unsigned> = Integer<unsigned>`
+/// - `Option<Number> * Option<String> = Null`
+/// - `OneOf[Number | String] * Option<String> = String`
+/// - `Object{a: T, b?: U} * Object{a: T, c?: V} = Object{a: T}`
+/// - `Array<Number> * Array<String> = Array<Unknown>` => only `[]` fits both
+pub(crate) fn meet(rhs: &Value, lhs: &Value) -> Option<Value> {
+    intersect(rhs, lhs).map(normalize)
+}
+
+fn intersect(rhs: &Value, lhs: &Value) -> Option<Value> {
+    if rhs == lhs {
+        return Some(rhs.clone());
+    }
+    match (rhs, lhs) {
+        // Unknown * T = Unknown
+        (Value::Unknown, _) | (_, Value::Unknown) => Some(Value::Unknown),
+        // OneOf[T | U] * V = OneOf[T * V | U * V]
+        (Value::OneOf { variants, optional }, other)
+        | (other, Value::OneOf { variants, optional }) => {
+            let variants = variants
+                .iter()
+                .chain(optional.then_some(&Value::Null))
+                .filter_map(|variant| intersect(variant, other))
+                .collect::<BTreeSet<_>>();
+            (!variants.is_empty()).then_some(Value::OneOf {
+                variants,
+                optional: false,
+            })
+        }
+        // Null * Option<T> = Null
+        (Value::Null, other) | (other, Value::Null) => other.is_optional().then_some(Value::Null),
+        (rhs, lhs) => {
+            let nullable = rhs.is_optional() && lhs.is_optional();
+            match intersect_values(
+                &rhs.clone().as_non_optional(),
+                &lhs.clone().as_non_optional(),
+            ) {
+                Some(value) if nullable => Some(value.as_optional()),
+                Some(value) => Some(value),
+                None => nullable.then_some(Value::Null),
+            }
+        }
+    }
+}
+
+/// Intersects the shapes of a value that must be present, such as tuple elements or object values,
+/// where [`Value::Unknown`] describes no value at all.
+fn intersect_present(rhs: &Value, lhs: &Value) -> Option<Value> {
+    intersect(rhs, lhs).filter(|value| !value.is_unknown())
+}
+
+/// Intersects two non optional shapes, neither of them being `Null`, `Unknown` or `OneOf`.
+#[expect(clippy::too_many_lines)]
+fn intersect_values(rhs: &Value, lhs: &Value) -> Option<Value> {
+    match (rhs, lhs) {
+        // Recursive<A: T> * Recursive<B: U> = Recursive<A: T * U>
+        (
+            Value::Recursive { name, shape },
+            Value::Recursive {
+                name: other_name,
+                shape: other_shape,
+            },
+        ) => Some(Value::Recursive {
+            shape: Box::new(intersect(
+                shape,
+                &rename((**other_shape).clone(), other_name, name),
+            )?),
+            name: name.clone(),
+        }),
+        // Recursive<A: T> * U = T[Ref<A> := Recursive<A: T>] * U
+        (Value::Recursive { name, shape }, other) | (other, Value::Recursive { name, shape }) => {
+            intersect(&unfold(name, shape), other)
+        }
+        (Value::Bool { .. }, Value::Bool { .. }) => Some(Value::Bool { optional: false }),
+        // Integer[0..=10] * Number[5..=20] = Integer[5..=10]
+        (
+            Value::Number { kind, range, .. },
+            Value::Number {
+                kind: other_kind,
+                range: other_range,
+                ..
+            },
+        ) => Some(Value::Number {
+            optional: false,
+            kind: kind.narrow(*other_kind),
+            range: intersect_ranges(range.as_ref(), other_range.as_ref())?,
+            examples: None,
+        }),
+        // String<uuid> * String = String<uuid>
+        (
+            Value::String { format, length, .. },
+            Value::String {
+                format: other_format,
+                length: other_length,
+                ..
+            },
+        ) => Some(Value::String {
+            optional: false,
+            format: intersect_formats(*format, *other_format)?,
+            length: intersect_lengths(length.as_ref(), other_length.as_ref())?,
+            examples: None,
+        }),
+        // Literal["a" | "b"] * Literal["b" | "c"] = Literal["b"]
+        (
+            Value::Literal { values, .. },
+            Value::Literal {
+                values: other_values,
+                ..
+            },
+        ) => literal(values.intersection(other_values).cloned().collect()),
+        // Literal["a" | 1] * String = Literal["a"]
+        (Value::Literal { values, .. }, other) | (other, Value::Literal { values, .. }) => literal(
+            values
+                .iter()
+                .filter(|value| value.shape().is_subset(other))
+                .cloned()
+                .collect(),
+        ),
+        // Array<T>[0..=5] * Array<U>[2..=8] = Array<T * U>[2..=5]
+        (
+            Value::Array { r#type, length, .. },
+            Value::Array {
+                r#type: ty,
+                length: other_length,
+                ..
+            },
+        ) => {
+            let length = intersect_lengths(length.as_ref(), other_length.as_ref())?;
+            let r#type = match intersect(r#type, ty) {
+                Some(r#type) => r#type,
+                // Only the empty array fits when elements are disjoint
+                None if length.is_none_or(|length| length.min == 0) => Value::Unknown,
+                None => return None,
+            };
+            Some(Value::Array {
+                r#type: Box::new(r#type),
+                optional: false,
+                length,
+            })
+        }
+        (
+            Value::Tuple { elements, .. },
+            Value::Tuple {
+                elements: other, ..
+            },
+        ) if elements.len() == other.len() => Some(Value::Tuple {
+            elements: elements
+                .iter()
+                .zip(other)
+                .map(|(element, other)| intersect_present(element, other))
+                .collect::<Option<_>>()?,
+            optional: false,
+        }),
+        // Tuple(T, U) * Array<V> = Tuple(T * V, U * V)
+        (Value::Tuple { elements, .. }, Value::Array { r#type, length, .. })
+        | (Value::Array { r#type, length, .. }, Value::Tuple { elements, .. })
+            if length.is_none_or(|length| length.contains(&LengthRange::new(elements.len()))) =>
+        {
+            Some(Value::Tuple {
+                elements: elements
+                    .iter()
+                    .map(|element| intersect_present(element, r#type))
+                    .collect::<Option<_>>()?,
+                optional: false,
+            })
+        }
+        (
+            Value::Object {
+                content, absent, ..
+            },
+            Value::Object {
+                content: other,
+                absent: other_absent,
+                ..
+            },
+        ) => intersect_objects(content, absent.as_ref(), other, other_absent.as_ref()),
+        // Map<Integer, T> * Map<String, U> = Map<Integer, T * U>
+        (
+            Value::Map { r#type, key, .. },
+            Value::Map {
+                r#type: ty,
+                key: other_key,
+                ..
+            },
+        ) => {
+            let key = match (key, other_key) {
+                (None, key) | (key, None) => *key,
+                (Some(key), Some(other_key)) if key == other_key => Some(*key),
+                // Only the empty object fits when keys are disjoint
+                _ => return Some(empty_object()),
+            };
+            Some(
+                intersect_present(r#type, ty).map_or_else(empty_object, |r#type| Value::Map {
+                    r#type: Box::new(r#type),
+                    key,
+                    optional: false,
+                }),
+            )
+        }
+        // Object{"1": T, b?: U} * Map<Integer, V> = Object{"1": T * V}
+        (
+            Value::Object {
+                content, absent, ..
+            },
+            Value::Map { r#type, key, .. },
+        )
+        | (
+            Value::Map { r#type, key, .. },
+            Value::Object {
+                content, absent, ..
+            },
+        ) => {
+            let other = content
+                .iter()
+                .filter(|(name, _)| key.is_none_or(|key| key.matches(name)))
+                .map(|(name, _)| (name.clone(), (**r#type).clone()))
+                .collect();
+            let absent = absent_keys(content, absent.as_ref());
+            intersect_objects(content, Some(&absent), &other, Some(&absent))
+        }
+        // Tagged<type>["a": T | "b": U] * Tagged<type>["b": V] = Tagged<type>["b": U * V]
+        (
+            Value::Tagged { tag, variants, .. },
+            Value::Tagged {
+                tag: other_tag,
+                variants: other_variants,
+                ..
+            },
+        ) if tag == other_tag => {
+            let variants = variants
+                .iter()
+                .filter_map(|(value, variant)| {
+                    let other = other_variants.get(value)?;
+                    Some((value.clone(), intersect_present(variant, other)?))
+                })
+                .collect::<BTreeMap<_, _>>();
+            (!variants.is_empty()).then(|| Value::Tagged {
+                tag: tag.clone(),
+                variants,
+                optional: false,
+            })
+        }
+        // Tagged<type>["a": T | "b": U] * Object{type: Literal["a"]} = Tagged<type>["a": T * Object{}]
+        (Value::Tagged { tag, variants, .. }, other @ Value::Object { .. })
+        | (other @ Value::Object { .. }, Value::Tagged { tag, variants, .. }) => {
+            let variants = variants
+                .iter()
+                .filter_map(|(value, variant)| {
+                    let Value::Object {
+                        content,
+                        optional,
+                        absent,
+                    } = variant
+                    else {
+                        return None;
+                    };
+                    let mut content = content.clone();
+                    content.insert(
+                        tag.clone(),
+                        Value::Literal {
+                            values: [LiteralValue::String(value.clone())].into(),
+                            optional: false,
+                        },
+                    );
+                    let object = Value::Object {
+                        content,
+                        optional: *optional,
+                        absent: absent.clone(),
+                    };
+                    let Value::Object {
+                        mut content,
+                        optional,
+                        mut absent,
+                    } = intersect(&object, other)?
+                    else {
+                        return None;
+                    };
+                    content.remove(tag);
+                    if let Some(absent) = &mut absent {
+                        absent.remove(tag);
+                    }
+                    Some((
+                        value.clone(),
+                        Value::Object {
+                            content,
+                            optional,
+                            absent,
+                        },
+                    ))
+                })
+                .collect::<BTreeMap<_, _>>();
+            (!variants.is_empty()).then(|| Value::Tagged {
+                tag: tag.clone(),
+                variants,
+                optional: false,
+            })
+        }
+        // Ref<A> * Ref<A> = Ref<A>, other shapes only intersect when one is a subset of the other
+        (rhs, lhs) => {
+            if rhs.is_subset(lhs) {
+                Some(rhs.clone())
+            } else if lhs.is_subset(rhs) {
+                Some(lhs.clone())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Keys of both objects are kept, required if required by either, while keys of a single object are only dropped if absent in it.
+/// - `Object{a: T, b?: U} * Object{a?: V, c?: W} = Object{a: T * V}`
+/// - `Object{a: T} * Object{b: U} = None`
+fn intersect_objects(
+    content: &BTreeMap<String, Value>,
+    absent: Option<&BTreeSet<String>>,
+    other: &BTreeMap<String, Value>,
+    other_absent: Option<&BTreeSet<String>>,
+) -> Option<Value> {
+    let absent = absent_keys(content, absent);
+    let other_absent = absent_keys(other, other_absent);
+    let mut intersection = BTreeMap::new();
+    let mut intersection_absent = BTreeSet::new();
+    for key in content.keys().chain(other.keys()) {
+        let is_absent = content.get(key).is_none_or(|_| absent.contains(key))
+            && other.get(key).is_none_or(|_| other_absent.contains(key));
+        let value = content
+            .get(key)
+            .zip(other.get(key))
+            .and_then(|(value, other)| intersect_present(value, other));
+        match value {
+            Some(value) => {
+                if is_absent {
+                    intersection_absent.insert(key.clone());
+                }
+                intersection.insert(key.clone(), value);
+            }
+            // Objects without the key fit both
+            None if is_absent => {}
+            None => return None,
+        }
+    }
+    Some(Value::Object {
+        absent: Some(intersection_absent),
+        content: intersection,
+        optional: false,
+    })
+}
+
+fn literal(values: BTreeSet<LiteralValue>) -> Option<Value> {
+    (!values.is_empty()).then_some(Value::Literal {
+        values,
+        optional: false,
+    })
+}
+
+const fn empty_object() -> Value {
+    Value::Object {
+        content: BTreeMap::new(),
+        optional: false,
+        absent: Some(BTreeSet::new()),
+    }
+}
+
+/// `Some(None)` when unbounded, `None` when both ranges are disjoint.
+#[allow(clippy::option_option)]
+fn intersect_ranges(
+    range: Option<&NumberRange>,
+    other: Option<&NumberRange>,
+) -> Option<Option<NumberRange>> {
+    match (range, other) {
+        (Some(range), Some(other)) => range.intersection(other).map(Some),
+        (range, other) => Some(range.or(other).cloned()),
+    }
+}
+
+/// `Some(None)` when unbounded, `None` when both ranges are disjoint.
+#[allow(clippy::option_option)]
+fn intersect_lengths(
+    length: Option<&LengthRange>,
+    other: Option<&LengthRange>,
+) -> Option<Option<LengthRange>> {
+    match (length, other) {
+        (Some(length), Some(other)) => length.intersection(other).map(Some),
+        (length, other) => Some(length.or(other).copied()),
+    }
+}
+
+/// `Some(None)` when any string fits, `None` when both formats differ.
+#[allow(clippy::option_option)]
+fn intersect_formats(
+    format: Option<StringFormat>,
+    other: Option<StringFormat>,
+) -> Option<Option<StringFormat>> {
+    match (format, other) {
+        (None, format) | (format, None) => Some(format),
+        (Some(format), Some(other)) => (format == other).then_some(Some(format)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InferenceOptions, test_utils};
+
+    fn shape(source: &str) -> Value {
+        let options = InferenceOptions::default()
+            .with_number_ranges()
+            .with_length_bounds();
+        Value::from_str_with_options(source, &options).unwrap()
+    }
+
+    fn sources(sources: &[&str]) -> Value {
+        test_utils::shape(sources, &InferenceOptions::default())
+    }
+
+    fn meet_display(rhs: &Value, lhs: &Value) -> Option<String> {
+        let met = meet(rhs, lhs);
+        assert_eq!(met, meet(lhs, rhs));
+        if let Some(met) = &met {
+            assert!(met.is_subset(rhs) && met.is_subset(lhs));
+        }
+        met.map(|met| met.to_string())
+    }
+
+    #[test]
+    fn meet_narrows_numbers_strings_and_lengths() {
+        assert_eq!(
+            meet_display(&shape("[-5, 10]"), &shape("[5, 20]")),
+            Some("Array<Integer<unsigned>[5..=10]>[2..=2]".to_string())
+        );
+        assert_eq!(
+            meet_display(&sources(&[r#"["a", "b"]"#]), &sources(&["[5, 20, 30]"])),
+            Some("Array<Unknown>".to_string())
+        );
+        assert_eq!(meet_display(&shape("[-5, -1]"), &shape("[5, 20]")), None);
+        assert_eq!(
+            meet_display(
+                &sources(&["\"2024-01-31\"", "\"a\""]),
+                &shape("\"2024-02-01\"")
+            ),
+            Some("String<date>[10..=10]".to_string())
+        );
+    }
+
+    #[test]
+    fn meet_keeps_null_only_when_both_are_optional() {
+        let number = sources(&["1.5", "null"]);
+        let string = sources(&["\"a\"", "null"]);
+
+        assert_eq!(meet_display(&number, &string), Some("Null".to_string()));
+        assert_eq!(meet_display(&shape("1.5"), &string), None);
+        assert_eq!(
+            meet_display(&sources(&["1", "\"a\"", "true"]), &string),
+            Some("String".to_string())
+        );
+    }
+
+    #[test]
+    fn meet_intersects_literals() {
+        let options = InferenceOptions::default().with_literal_limit(3);
+        let literals = |source: &str| {
+            let Value::Array { r#type, .. } =
+                Value::from_str_with_options(source, &options).unwrap()
+            else {
+                panic!("expected Array");
+            };
+            *r#type
+        };
+        let mixed = literals(r#"["active", "pending", "active"]"#);
+
+        assert_eq!(
+            meet_display(&mixed, &literals(r#"["active", "disabled"]"#)),
+            Some("Literal[\"active\"]".to_string())
+        );
+        assert_eq!(
+            meet_display(&mixed, &shape(r#""active""#)),
+            Some("Literal[\"active\" | \"pending\"]".to_string())
+        );
+        assert_eq!(meet_display(&mixed, &shape("\"2024-01-31\"")), None);
+    }
+
+    #[test]
+    fn meet_keeps_keys_every_object_accepts() {
+        let v1 = sources(&[r#"{"id": 1, "name": "a"}"#, r#"{"id": 2, "tags": ["a"]}"#]);
+        let v2 = sources(&[r#"{"id": 1.5, "name": "b"}"#, r#"{"id": 2, "extra": true}"#]);
+
+        assert_eq!(
+            meet_display(&v1, &v2),
+            Some("Object{id: Integer<unsigned>, name?: String}".to_string())
+        );
+        assert_eq!(
+            meet_display(&shape(r#"{"a": 1}"#), &shape(r#"{"b": 1}"#)),
+            None
+        );
+        assert_eq!(
+            meet_display(&shape(r#"{"a": 1}"#), &shape(r#"{"a": "b"}"#)),
+            None
+        );
+    }
+
+    #[test]
+    fn meet_of_recursive_shapes() {
+        let options = InferenceOptions::default().with_recursion();
+        let tree = Value::from_str_with_options(
+            r#"{"value": 1, "children": [{"value": 2, "children": []}]}"#,
+            &options,
+        )
+        .unwrap();
+        let leaf = shape(r#"{"value": 3, "children": []}"#);
+
+        assert_eq!(
+            meet_display(&tree, &leaf),
+            Some(
+                "Object{children: Array<Unknown>[0..=0], value: Integer<unsigned>[3..=3]}"
+                    .to_string()
+            )
+        );
+        assert_eq!(meet(&tree, &tree), Some(tree));
+    }
+}
@@ -3,7 +3,14 @@ use std::{
     ops::Deref,
 };
 
-use crate::{IsSubset, error::Error, value::Value};
+use crate::{
+    InferenceOptions, IsSubset,
+    error::Error,
+    shape::{number_shape, recursion::rename, string_shape},
+    statistics::key_path,
+    value::{Examples, KeyPattern, LengthRange, LiteralValue, NumberRange, Value, absent_keys},
+    visitor::{ShapeVisitorMut, walk_shape_mut},
+};
 
 pub fn merge(values: &[Value]) -> Result<Value, Error> {
     let mut iter = values.iter();
@@ -11,270 +18,928 @@ pub fn merge(values: &[Value]) -> Result<Value, Error> {
     iter.try_fold(first, |acc, v| merger(acc, v.to_owned()))
 }
 
-#[expect(clippy::match_same_arms)]
-#[expect(clippy::too_many_lines)]
-#[expect(clippy::cognitive_complexity)]
-pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
-    match (rhs, lhs) {
-        // Null + Null = Null
-        (Value::Null, Value::Null) => Ok(Value::Null),
-        // Null + T = Option<T>
-        (Value::Null, Value::Bool { .. }) => Ok(Value::Bool { optional: true }),
-        (Value::Null, Value::Number { .. }) => Ok(Value::Number { optional: true }),
-        (Value::Null, Value::String { .. }) => Ok(Value::String { optional: true }),
-        (Value::Null, Value::Array { r#type, .. }) => Ok(Value::Array {
-            r#type,
-            optional: true,
-        }),
-        (Value::Null, Value::Tuple { elements, .. }) => Ok(Value::Tuple {
-            elements,
-            optional: true,
-        }),
-        (Value::Null, Value::Object { content, .. }) => Ok(Value::Object {
-            content,
-            optional: true,
-        }),
-        (Value::Null, Value::OneOf { variants, .. }) => Ok(Value::OneOf {
-            variants,
-            optional: true,
-        }),
-        (Value::Bool { .. }, Value::Null) => Ok(Value::Bool { optional: true }),
-        // Bool + Option<Bool> = Option<Bool>
+/// Inserts `value` into `OneOf` variants, widening the variant it refines instead of duplicating it.
+/// - `OneOf[Integer | String] + Number = OneOf[Number | String]`
+/// - `OneOf[Number | String<uuid>] + String = OneOf[Number | String]`
+/// - `OneOf[Bool | Literal["a"]] + Literal["b"] = OneOf[Bool | Literal["a" | "b"]]`
+/// - `OneOf[Bool | Literal["a"]] + String = OneOf[Bool | String]`
+/// - `OneOf[Bool | Object{a: T}] + Object{b: U} = OneOf[Bool | Object{a?: T, b?: U}]`
+/// - `OneOf[Bool | Tuple(T, U)] + Array<V> = OneOf[Bool | Array<OneOf[T | U | V]>]`
+fn insert_variant(variants: &mut BTreeSet<Value>, value: Value) {
+    let is_array = |value: &Value| value.is_array() || value.is_tuple();
+    let refined = variants
+        .iter()
+        .find(|variant| {
+            variant.is_number() && value.is_number()
+                || variant.is_string() && value.is_string()
+                || variant.is_literal() && value.is_literal()
+                || variant.is_object() && value.is_object()
+                || is_array(variant) && is_array(&value)
+                || overlaps_literal(variant, &value)
+                || overlaps_literal(&value, variant)
+        })
+        .cloned();
+    let Some(refined) = refined else {
+        variants.insert(value);
+        return;
+    };
+    variants.remove(&refined);
+    match (refined, value) {
         (
-            Value::Bool { optional },
-            Value::Bool {
-                optional: other_opt,
+            Value::Number {
+                optional,
+                kind: refined_kind,
+                range: refined_range,
+                examples: refined_examples,
             },
-        ) => Ok(Value::Bool {
-            optional: (optional || other_opt),
-        }),
-        // Bool + Number = OneOf[Bool | Number]
-        (
-            Value::Bool { optional },
             Value::Number {
-                optional: other_opt,
+                kind,
+                range,
+                examples,
+                ..
             },
         ) => {
-            if optional || other_opt {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Number { optional: false },
-                        Value::Null,
-                    ]
-                    .into(),
-                    optional: false,
-                })
-            } else {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Number { optional: false },
-                    ]
-                    .into(),
-                    optional: false,
-                })
-            }
+            variants.insert(Value::Number {
+                optional,
+                kind: refined_kind.widen(kind),
+                range: merge_ranges(refined_range.as_ref(), range.as_ref()),
+                examples: merge_examples(refined_examples.as_deref(), examples.as_deref()),
+            });
         }
-        // Bool + String = OneOf[Bool | String]
         (
-            Value::Bool { optional },
             Value::String {
-                optional: other_opt,
+                optional,
+                format: refined_format,
+                length: refined_length,
+                examples: refined_examples,
+            },
+            Value::String {
+                format,
+                length,
+                examples,
+                ..
             },
         ) => {
-            if optional || other_opt {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::String { optional: false },
-                        Value::Null,
-                    ]
-                    .into(),
-                    optional: false,
-                })
-            } else {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::String { optional: false },
-                    ]
-                    .into(),
-                    optional: false,
-                })
-            }
+            variants.insert(Value::String {
+                optional,
+                format: refined_format.filter(|refined| Some(*refined) == format),
+                length: merge_lengths(refined_length.as_ref(), length.as_ref()),
+                examples: merge_examples(refined_examples.as_deref(), examples.as_deref()),
+            });
         }
-        // Bool + Array = OneOf[Bool | Array]
         (
-            Value::Bool { optional },
-            Value::Array {
-                r#type,
-                optional: other_opt,
+            Value::Literal {
+                values: mut refined_values,
+                optional,
             },
+            Value::Literal { values, .. },
         ) => {
-            if optional || other_opt {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Array {
-                            r#type,
-                            optional: false,
-                        },
-                        Value::Null,
-                    ]
-                    .into(),
-                    optional: false,
-                })
+            refined_values.extend(values);
+            variants.insert(Value::Literal {
+                values: refined_values,
+                optional,
+            });
+        }
+        (refined @ (Value::Object { .. } | Value::Array { .. } | Value::Tuple { .. }), value) => {
+            if let Ok(merged) = merger(refined.clone(), value.clone()) {
+                insert_variant(variants, merged);
             } else {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Array {
-                            r#type,
-                            optional: false,
-                        },
-                    ]
-                    .into(),
-                    optional: false,
-                })
+                variants.insert(refined);
+                variants.insert(value);
             }
         }
-        // Bool + Array = OneOf[Bool | Array]
-        (
-            Value::Bool { optional },
-            Value::Tuple {
-                elements,
-                optional: other_opt,
-            },
-        ) => {
-            if optional || other_opt {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Tuple {
-                            elements,
-                            optional: false,
-                        },
-                        Value::Null,
-                    ]
-                    .into(),
-                    optional: false,
-                })
-            } else {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Tuple {
-                            elements,
-                            optional: false,
-                        },
-                    ]
-                    .into(),
-                    optional: false,
-                })
+        (refined, value) => {
+            for variant in literal_variants(refined)
+                .into_iter()
+                .chain(literal_variants(value))
+            {
+                insert_variant(variants, variant);
             }
         }
-        // Bool + Object = OneOf[Bool | Object]
-        (
-            Value::Bool { optional },
-            Value::Object {
-                content,
-                optional: other_opt,
-            },
-        ) => {
-            if optional || other_opt {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Object {
-                            content,
-                            optional: false,
-                        },
-                        Value::Null,
-                    ]
-                    .into(),
-                    optional: false,
-                })
-            } else {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Object {
-                            content,
-                            optional: false,
-                        },
-                    ]
-                    .into(),
-                    optional: false,
-                })
+    }
+}
+
+/// Ranges are only kept when known for both numbers.
+/// - `Number[0..=1] + Number[-1..=0] = Number[-1..=1]`
+/// - `Number[0..=1] + Number = Number`
+fn merge_ranges(range: Option<&NumberRange>, other: Option<&NumberRange>) -> Option<NumberRange> {
+    range.zip(other).map(|(range, other)| range.union(other))
+}
+
+/// Length bounds are only kept when known for both values. Tuples never track them.
+/// - `String[1..=2] + String[3..=3] = String[1..=3]`
+/// - `Array<T>[1..=2] + Array<T> = Array<T>`
+fn merge_lengths(length: Option<&LengthRange>, other: Option<&LengthRange>) -> Option<LengthRange> {
+    length.zip(other).map(|(length, other)| length.union(other))
+}
+
+/// Examples are kept from whichever values tracked them, bounded later by [`limit_examples`].
+/// - `String["a"] + String["b", "a"] = String["a", "b"]`
+/// - `String["a"] + String = String["a"]`
+pub(crate) fn merge_examples(
+    examples: Option<&Examples>,
+    other: Option<&Examples>,
+) -> Option<Box<Examples>> {
+    match (examples, other) {
+        (Some(examples), Some(other)) => Some(Box::new(examples.union(other))),
+        (examples, other) => examples.or(other).cloned().map(Box::new),
+    }
+}
+
+/// Checks if `literal` tracks values of the same type as `other`, eg `Literal["a"]` and `String`.
+fn overlaps_literal(literal: &Value, other: &Value) -> bool {
+    let Value::Literal { values, .. } = literal else {
+        return false;
+    };
+    (other.is_string() && values.iter().any(LiteralValue::is_string))
+        || (other.is_number() && values.iter().any(LiteralValue::is_integer))
+}
+
+/// Widened `OneOf` variants of a [`Value::Literal`].
+fn literal_variants(literal: Value) -> Vec<Value> {
+    match literal.widen_literal().as_non_optional() {
+        Value::OneOf { variants, .. } => variants.into_iter().collect(),
+        value => vec![value],
+    }
+}
+
+/// Shape of the literal `values` when not tracked as literals.
+/// - `Literal["a" | "b"] = String`
+/// - `Literal[1 | -1] = Integer<signed>`
+/// - `Literal["a" | 1] = OneOf[Integer<unsigned> | String]`
+pub(crate) fn literal_shape(values: &BTreeSet<LiteralValue>, optional: bool) -> Value {
+    let mut variants = BTreeSet::default();
+    for value in values {
+        insert_variant(&mut variants, value.shape());
+    }
+    let shape = if variants.len() > 1 {
+        Value::OneOf {
+            variants,
+            optional: false,
+        }
+    } else {
+        variants.pop_first().unwrap_or(Value::Null)
+    };
+    if optional { shape.as_optional() } else { shape }
+}
+
+/// Shape of the literal `values` when not tracked as literals, with the refinements enabled by the [`InferenceOptions`],
+/// as if the values had been inferred without a literal limit.
+/// - `Literal[1 | 5]` => `Integer<unsigned>[1..=5]` with number ranges
+/// - `Literal["a" | "bb"]` => `String[1..=2]` with length bounds
+pub(crate) fn refined_literal_shape(
+    values: &BTreeSet<LiteralValue>,
+    optional: bool,
+    options: &InferenceOptions,
+) -> Value {
+    let options = InferenceOptions {
+        literal_limit: None,
+        ..options.clone()
+    };
+    let mut variants = BTreeSet::default();
+    for value in values {
+        let shape = match value {
+            LiteralValue::Integer(value) => number_shape(&(*value).into(), &options),
+            LiteralValue::String(value) => string_shape(value, &options),
+        };
+        insert_variant(&mut variants, shape);
+    }
+    let shape = if variants.len() > 1 {
+        Value::OneOf {
+            variants,
+            optional: false,
+        }
+    } else {
+        variants.pop_first().unwrap_or(Value::Null)
+    };
+    if optional { shape.as_optional() } else { shape }
+}
+
+/// [`InferenceOptions`] tracking the refinements of a number or string, so that literals merged into it keep them.
+fn refinements(value: &Value) -> InferenceOptions {
+    let (number_ranges, length_bounds, examples) = match value {
+        Value::Number {
+            range, examples, ..
+        } => (range.is_some(), false, examples.as_deref()),
+        Value::String {
+            length, examples, ..
+        } => (false, length.is_some(), examples.as_deref()),
+        _ => (false, false, None),
+    };
+    InferenceOptions {
+        number_ranges,
+        length_bounds,
+        example_limit: examples.map(Examples::len),
+        ..InferenceOptions::default()
+    }
+}
+
+/// Widens every [`Value::Literal`] tracking more than `limit` values into its [`refined_literal_shape`],
+/// except the values of the tag keys of objects, see [`InferenceOptions::tags`].
+pub(crate) fn limit_literals(mut value: Value, limit: usize, options: &InferenceOptions) -> Value {
+    LiteralLimiter { limit, options }.visit_shape_mut(&mut value, "$");
+    value
+}
+
+struct LiteralLimiter<'a> {
+    limit: usize,
+    options: &'a InferenceOptions,
+}
+
+impl ShapeVisitorMut for LiteralLimiter<'_> {
+    fn visit_shape_mut(&mut self, shape: &mut Value, path: &str) {
+        match shape {
+            Value::Object { content, .. } => {
+                for (key, value) in content {
+                    if !(value.is_literal() && self.options.tags.contains(key)) {
+                        self.visit_shape_mut(value, &key_path(path, key));
+                    }
+                }
             }
+            _ => walk_shape_mut(self, shape, path),
         }
-        // Bool + Oneof[T | U] = OneOf[Bool | T | U]
-        // Bool + Oneof[Bool | T | U] = OneOf[Bool | T | U]
-        // Option<Bool> + Oneof[Bool | T | U] = OneOf[Bool | T | U | Null]
-        (
-            Value::Bool { optional },
-            Value::OneOf {
-                mut variants,
-                optional: other_opt,
-            },
-        ) => {
-            if optional && !variants.contains(&Value::Null) {
-                variants.insert(Value::Null);
+        match shape {
+            Value::Literal { values, optional } if values.len() > self.limit => {
+                *shape = refined_literal_shape(values, *optional, self.options);
             }
-            variants.insert(Value::Bool { optional: false });
-            Ok(Value::OneOf {
-                variants,
-                optional: other_opt,
-            })
+            // Widened variants may be unions themselves
+            Value::OneOf { variants, .. } => {
+                for variant in std::mem::take(variants) {
+                    match variant {
+                        Value::OneOf {
+                            variants: nested, ..
+                        } => {
+                            for variant in nested {
+                                insert_variant(variants, variant);
+                            }
+                        }
+                        variant => insert_variant(variants, variant),
+                    }
+                }
+            }
+            _ => (),
         }
-        (Value::Number { .. }, Value::Null) => Ok(Value::Number { optional: true }),
-        (
-            Value::Number { optional },
-            Value::Bool {
-                optional: other_opt,
-            },
-        ) => {
-            if optional || other_opt {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Number { optional: false },
-                        Value::Null,
-                    ]
-                    .into(),
-                    optional: false,
-                })
-            } else {
-                Ok(Value::OneOf {
-                    variants: [
-                        Value::Bool { optional: false },
-                        Value::Number { optional: false },
-                    ]
-                    .into(),
-                    optional: false,
-                })
+    }
+}
+
+/// Keeps at most `limit` [`Examples`] per [`Value::Number`] and [`Value::String`], in the order they were first seen.
+pub(crate) fn limit_examples(value: Value, limit: usize) -> Value {
+    value.map(|shape, _| match shape {
+        Value::Number {
+            optional,
+            kind,
+            range,
+            examples,
+        } => Value::Number {
+            optional,
+            kind,
+            range,
+            examples: examples.map(|examples| Box::new(examples.truncate(limit))),
+        },
+        Value::String {
+            optional,
+            format,
+            length,
+            examples,
+        } => Value::String {
+            optional,
+            format,
+            length,
+            examples: examples.map(|examples| Box::new(examples.truncate(limit))),
+        },
+        shape => shape,
+    })
+}
+
+/// Replaces every [`Value::Object`] with values of the same type by a [`Value::Map`],
+/// when it has at least `threshold` keys, and at least two, keyed by the [`KeyPattern`] all its keys share.
+/// - `Object{"1": Integer, "2": Number}` => `Map<Integer, Number>`, with a `threshold` of 2
+/// - `Object{a: String, b: String, c: String}` => `Map<String, String>`, with a `threshold` of 3
+/// - `Object{"1": Integer}` stays an object, whatever the `threshold`
+pub(crate) fn detect_maps(mut value: Value, threshold: usize) -> Value {
+    MapDetector { threshold }.visit_shape_mut(&mut value, "$");
+    value
+}
+
+struct MapDetector {
+    threshold: usize,
+}
+
+impl ShapeVisitorMut for MapDetector {
+    fn visit_shape_mut(&mut self, shape: &mut Value, path: &str) {
+        match shape {
+            // The variants of tagged unions stay objects
+            Value::Tagged { variants, .. } => {
+                for variant in variants.values_mut() {
+                    walk_shape_mut(self, variant, path);
+                }
+            }
+            _ => walk_shape_mut(self, shape, path),
+        }
+        let Value::Object {
+            content, optional, ..
+        } = shape
+        else {
+            return;
+        };
+        if content.len() >= self.threshold.max(2)
+            && let Some(r#type) = map_type(content)
+        {
+            *shape = Value::Map {
+                r#type: Box::new(r#type),
+                key: KeyPattern::detect(content.keys().map(String::as_str)),
+                optional: *optional,
+            };
+        }
+    }
+}
+
+/// Merged type of the `content` values, if they are homogeneous, ignoring nulls.
+fn map_type(content: &BTreeMap<String, Value>) -> Option<Value> {
+    let values = content
+        .values()
+        .filter(|value| !value.is_null())
+        .map(|value| value.clone().as_non_optional())
+        .collect::<Vec<_>>();
+    let first = values.first()?;
+    let is_homogeneous = values.iter().all(|value| value.same_structure(first))
+        || values.iter().all(Value::is_object);
+    if is_homogeneous {
+        merge(&content.values().cloned().collect::<Vec<_>>()).ok()
+    } else {
+        None
+    }
+}
+
+/// Tags of an object member, when it is a [`Value::Literal`] of strings matching the variants of a [`Value::Tagged`].
+fn tag_values(member: Option<&Value>) -> Option<Vec<String>> {
+    let Some(Value::Literal {
+        values,
+        optional: false,
+    }) = member
+    else {
+        return None;
+    };
+    values
+        .iter()
+        .map(|value| match value {
+            LiteralValue::String(value) => Some(value.clone()),
+            LiteralValue::Integer(_) => None,
+        })
+        .collect()
+}
+
+/// Merges the variants of a [`Value::Tagged`] back into a single object, with the `tag` key as a literal of the tags,
+/// widened by [`limit_literals`] like the other literals.
+/// - `Tagged<type>["a": Object{b: T} | "c": Object{}]` => `Object{b: Option<T>, type: Literal["a" | "c"]}`
+///
+/// Other values are returned unchanged.
+pub(crate) fn untag(value: Value) -> Result<Value, Error> {
+    let Value::Tagged {
+        tag,
+        variants,
+        optional,
+    } = value
+    else {
+        return Ok(value);
+    };
+    let values = variants
+        .keys()
+        .map(|value| LiteralValue::String(value.clone()))
+        .collect::<BTreeSet<_>>();
+    let variants = variants
+        .into_values()
+        .map(|variant| match variant {
+            Value::Object {
+                mut content,
+                optional,
+                absent,
+            } => {
+                content.insert(
+                    tag.clone(),
+                    Value::Literal {
+                        values: values.clone(),
+                        optional: false,
+                    },
+                );
+                Value::Object {
+                    content,
+                    optional,
+                    absent,
+                }
+            }
+            variant => variant,
+        })
+        .collect::<Vec<_>>();
+    let object = merge(&variants)?;
+    Ok(if optional {
+        object.as_optional()
+    } else {
+        object
+    })
+}
+
+/// Merges two shapes into a single shape describing the values of both, in the canonical form of [`normalize`].
+pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
+    merge_values(rhs, lhs).map(normalize_joined)
+}
+
+/// Rewrites a shape into its canonical form, so that semantically equal shapes are equal,
+/// without changing the values it describes:
+/// - `Option<OneOf[T | U]> = OneOf[T | U | Null]`
+/// - `OneOf[T | OneOf[U | V]] = OneOf[T | U | V]`
+/// - `OneOf[T | Option<U>] = OneOf[T | U | Null]`
+/// - `OneOf[Integer | Option<Number>] = Option<Number>`, as variants covered by another variant are dropped
+/// - `OneOf[T] = T` and `OneOf[T | Null] = Option<T>`
+/// - `OneOf[T | Unknown] = T`
+/// - Absent keys of objects are always tracked, objects that didn't track them having their keys with optional values absent
+///
+/// Variants are never widened, `OneOf[Integer<signed> | Integer<u64>]` stays as is, see [`normalize_joined`].
+pub(crate) fn normalize(value: Value) -> Value {
+    canonical(value, false)
+}
+
+/// Rewrites a merged shape into the canonical form of [`normalize`], joining its variants as [`insert_variant`] does,
+/// eg `OneOf[Object{a: T} | Object{b: U}] = Object{a?: T, b?: U}`.
+pub(crate) fn normalize_joined(value: Value) -> Value {
+    canonical(value, true)
+}
+
+fn canonical(value: Value, join: bool) -> Value {
+    value.map(|shape, _| match shape {
+        Value::OneOf { variants, optional } => {
+            let mut nullable = optional;
+            let mut normalized = BTreeSet::default();
+            for variant in variants {
+                let variants = match variant {
+                    Value::OneOf { variants, .. } => variants,
+                    variant => [variant].into(),
+                };
+                for variant in variants {
+                    match variant {
+                        Value::Null => nullable = true,
+                        Value::Unknown => (),
+                        variant => {
+                            nullable |= variant.is_optional();
+                            if join {
+                                insert_variant(&mut normalized, variant.as_non_optional());
+                            } else {
+                                normalized.insert(variant.as_non_optional());
+                            }
+                        }
+                    }
+                }
+            }
+            let mut normalized = uncovered(normalized);
+            match (normalized.len(), nullable) {
+                (0, true) => Value::Null,
+                (0, false) => Value::Unknown,
+                (1, true) => normalized.remove(0).as_optional(),
+                (1, false) => normalized.remove(0),
+                (_, nullable) => {
+                    if nullable {
+                        normalized.push(Value::Null);
+                    }
+                    Value::OneOf {
+                        variants: normalized.into_iter().collect(),
+                        optional: false,
+                    }
+                }
             }
         }
+        Value::Object {
+            content,
+            optional,
+            absent,
+        } => Value::Object {
+            absent: Some(absent_keys(&content, absent.as_ref()).into_owned()),
+            content,
+            optional,
+        },
+        shape => shape,
+    })
+}
+
+/// The `variants` not covered by another variant, keeping the first of equivalent variants.
+fn uncovered(variants: BTreeSet<Value>) -> Vec<Value> {
+    let variants = variants.into_iter().collect::<Vec<_>>();
+    variants
+        .iter()
+        .enumerate()
+        .filter(|(index, variant)| {
+            !variants.iter().enumerate().any(|(other_index, other)| {
+                other_index != *index
+                    && variant.is_subset(other)
+                    && (other_index < *index || !other.is_subset(variant))
+            })
+        })
+        .map(|(_, variant)| variant.clone())
+        .collect()
+}
+
+#[expect(clippy::match_same_arms)]
+#[expect(clippy::too_many_lines)]
+#[expect(clippy::cognitive_complexity)]
+fn merge_values(rhs: Value, lhs: Value) -> Result<Value, Error> {
+    match (rhs, lhs) {
+        // Unknown + T = T
+        (Value::Unknown, other) | (other, Value::Unknown) => Ok(other),
+        // Recursive<A: T> + Recursive<B: U> = Recursive<A: T + U>
         (
-            Value::Number { optional },
-            Value::Number {
+            Value::Recursive { name, shape },
+            Value::Recursive {
+                name: other_name,
+                shape: other_shape,
+            },
+        ) => Ok(Value::Recursive {
+            shape: Box::new(merger(*shape, rename(*other_shape, &other_name, &name))?),
+            name,
+        }),
+        // Recursive<A: T> + U = Recursive<A: T + U>
+        (Value::Recursive { name, shape }, other) | (other, Value::Recursive { name, shape }) => {
+            Ok(Value::Recursive {
+                shape: Box::new(merger(*shape, other)?),
+                name,
+            })
+        }
+        // Ref<A> + Ref<A> = Ref<A>
+        (
+            Value::Ref { name, optional },
+            Value::Ref {
+                name: other_name,
+                optional: other_opt,
+            },
+        ) if name == other_name => Ok(Value::Ref {
+            name,
+            optional: optional || other_opt,
+        }),
+        // Ref<A> + Null = Option<Ref<A>>
+        (Value::Ref { name, .. }, Value::Null) | (Value::Null, Value::Ref { name, .. }) => {
+            Ok(Value::Ref {
+                name,
+                optional: true,
+            })
+        }
+        (reference @ Value::Ref { .. }, one_of @ Value::OneOf { .. }) => merger(one_of, reference),
+        // Ref<A> + Bool = OneOf[Bool | Ref<A>]
+        (reference @ Value::Ref { .. }, other)
+        | (
+            other @ (Value::Bool { .. }
+            | Value::Number { .. }
+            | Value::String { .. }
+            | Value::Array { .. }
+            | Value::Object { .. }
+            | Value::Tuple { .. }
+            | Value::Literal { .. }
+            | Value::Map { .. }),
+            reference @ Value::Ref { .. },
+        ) => {
+            let mut variants = BTreeSet::default();
+            if reference.is_optional() || other.is_optional() {
+                variants.insert(Value::Null);
+            }
+            variants.insert(reference.as_non_optional());
+            insert_variant(&mut variants, other.as_non_optional());
+            Ok(Value::OneOf {
+                variants,
+                optional: false,
+            })
+        }
+        // Literal["a"] + Literal["b"] = Literal["a" | "b"]
+        (
+            Value::Literal {
+                mut values,
+                optional,
+            },
+            Value::Literal {
+                values: other_values,
+                optional: other_opt,
+            },
+        ) => {
+            values.extend(other_values);
+            Ok(Value::Literal {
+                values,
+                optional: optional || other_opt,
+            })
+        }
+        // Literal["a"] + Null = Option<Literal["a"]>
+        (Value::Literal { values, .. }, Value::Null)
+        | (Value::Null, Value::Literal { values, .. }) => Ok(Value::Literal {
+            values,
+            optional: true,
+        }),
+        // Literal["a"] + String[2..=3] = String[1..=3]
+        (
+            Value::Literal { values, optional },
+            other @ (Value::String { .. } | Value::Number { .. }),
+        ) => {
+            let widened = refined_literal_shape(&values, optional, &refinements(&other));
+            merger(widened, other)
+        }
+        (
+            other @ (Value::String { .. } | Value::Number { .. }),
+            Value::Literal { values, optional },
+        ) => {
+            let widened = refined_literal_shape(&values, optional, &refinements(&other));
+            merger(other, widened)
+        }
+        (literal @ Value::Literal { .. }, one_of @ Value::OneOf { .. }) => merger(one_of, literal),
+        (
+            Value::OneOf {
+                mut variants,
+                optional,
+            },
+            literal @ Value::Literal { .. },
+        ) => {
+            if literal.is_optional() && !variants.contains(&Value::Null) {
+                variants.insert(Value::Null);
+            }
+            insert_variant(&mut variants, literal.as_non_optional());
+            Ok(Value::OneOf { variants, optional })
+        }
+        // Literal["a"] + Bool = OneOf[Literal["a"] | Bool]
+        (literal @ Value::Literal { .. }, other) | (other, literal @ Value::Literal { .. }) => {
+            let mut variants = BTreeSet::default();
+            if literal.is_optional() || other.is_optional() {
+                variants.insert(Value::Null);
+            }
+            insert_variant(&mut variants, literal.as_non_optional());
+            insert_variant(&mut variants, other.as_non_optional());
+            Ok(Value::OneOf {
+                variants,
+                optional: false,
+            })
+        }
+        // Map<K, T> + Map<K, U> = Map<K, T + U>
+        (
+            Value::Map {
+                r#type,
+                key,
+                optional,
+            },
+            Value::Map {
+                r#type: other_ty,
+                key: other_key,
+                optional: other_opt,
+            },
+        ) => Ok(Value::Map {
+            r#type: Box::new(merger(*r#type, *other_ty)?),
+            key: key.filter(|key| Some(*key) == other_key),
+            optional: optional || other_opt,
+        }),
+        // Map<K, T> + Null = Option<Map<K, T>>
+        (Value::Map { r#type, key, .. }, Value::Null)
+        | (Value::Null, Value::Map { r#type, key, .. }) => Ok(Value::Map {
+            r#type,
+            key,
+            optional: true,
+        }),
+        // Map<Integer, T> + Object{"1": U, "2": V} = Map<Integer, T + U + V>
+        (
+            Value::Map {
+                r#type,
+                key,
+                optional,
+            },
+            Value::Object {
+                content,
+                optional: other_opt,
+                ..
+            },
+        )
+        | (
+            Value::Object {
+                content,
+                optional: other_opt,
+                ..
+            },
+            Value::Map {
+                r#type,
+                key,
+                optional,
+            },
+        ) => {
+            let key = key.filter(|key| content.keys().all(|name| key.matches(name)));
+            let r#type = content.into_values().try_fold(*r#type, merger)?;
+            Ok(Value::Map {
+                r#type: Box::new(r#type),
+                key,
+                optional: optional || other_opt,
+            })
+        }
+        (map @ Value::Map { .. }, one_of @ Value::OneOf { .. }) => merger(one_of, map),
+        // Map<K, T> + Bool = OneOf[Map<K, T> | Bool]
+        (map @ Value::Map { .. }, other)
+        | (
+            other @ (Value::Bool { .. }
+            | Value::Number { .. }
+            | Value::String { .. }
+            | Value::Array { .. }
+            | Value::Tuple { .. }),
+            map @ Value::Map { .. },
+        ) => {
+            let mut variants = BTreeSet::default();
+            if map.is_optional() || other.is_optional() {
+                variants.insert(Value::Null);
+            }
+            variants.insert(map.as_non_optional());
+            insert_variant(&mut variants, other.as_non_optional());
+            Ok(Value::OneOf {
+                variants,
+                optional: false,
+            })
+        }
+        // Tagged<type>["a": T] + Tagged<type>["a": U | "b": V] = Tagged<type>["a": T + U | "b": V]
+        (
+            Value::Tagged {
+                tag,
+                mut variants,
+                optional,
+            },
+            Value::Tagged {
+                tag: other_tag,
+                variants: other,
                 optional: other_opt,
             },
+        ) if tag == other_tag => {
+            for (value, variant) in other {
+                let variant = match variants.remove(&value) {
+                    Some(existing) => merger(existing, variant)?,
+                    None => variant,
+                };
+                variants.insert(value, variant);
+            }
+            Ok(Value::Tagged {
+                tag,
+                variants,
+                optional: optional || other_opt,
+            })
+        }
+        // Tagged<type>[..] + Null = Option<Tagged<type>[..]>
+        (Value::Tagged { tag, variants, .. }, Value::Null)
+        | (Value::Null, Value::Tagged { tag, variants, .. }) => Ok(Value::Tagged {
+            tag,
+            variants,
+            optional: true,
+        }),
+        // Tagged<type>["a": Object{b: T}] + Object{type: Literal["a" | "c"], b: U} = Tagged<type>["a": Object{b: T + U} | "c": Object{b: U}]
+        (
+            Value::Tagged {
+                tag,
+                mut variants,
+                optional,
+            },
+            Value::Object {
+                mut content,
+                optional: other_opt,
+                absent,
+            },
+        )
+        | (
+            Value::Object {
+                mut content,
+                optional: other_opt,
+                absent,
+            },
+            Value::Tagged {
+                tag,
+                mut variants,
+                optional,
+            },
+        ) if !absent_keys(&content, absent.as_ref()).contains(&tag)
+            && tag_values(content.get(&tag)).is_some() =>
+        {
+            let values = tag_values(content.remove(&tag).as_ref()).unwrap_or_default();
+            let object = Value::Object {
+                content,
+                optional: false,
+                absent,
+            };
+            for value in values {
+                let variant = match variants.remove(&value) {
+                    Some(existing) => merger(existing, object.clone())?,
+                    None => object.clone(),
+                };
+                variants.insert(value, variant);
+            }
+            Ok(Value::Tagged {
+                tag,
+                variants,
+                optional: optional || other_opt,
+            })
+        }
+        // Tagged<type>["a": Object{b: T}] + Object{c: U} = Object{type: Literal["a"], b: Option<T>, c: Option<U>}
+        (tagged @ Value::Tagged { .. }, other @ (Value::Object { .. } | Value::Tagged { .. }))
+        | (other @ Value::Object { .. }, tagged @ Value::Tagged { .. }) => {
+            merger(untag(tagged)?, untag(other)?)
+        }
+        (tagged @ Value::Tagged { .. }, one_of @ Value::OneOf { .. }) => merger(one_of, tagged),
+        // Tagged<type>[..] + Bool = OneOf[Bool | Tagged<type>[..]]
+        (tagged @ Value::Tagged { .. }, other)
+        | (
+            other @ (Value::Bool { .. }
+            | Value::Number { .. }
+            | Value::String { .. }
+            | Value::Array { .. }
+            | Value::Tuple { .. }),
+            tagged @ Value::Tagged { .. },
+        ) => {
+            let mut variants = BTreeSet::default();
+            if tagged.is_optional() || other.is_optional() {
+                variants.insert(Value::Null);
+            }
+            variants.insert(tagged.as_non_optional());
+            insert_variant(&mut variants, other.as_non_optional());
+            Ok(Value::OneOf {
+                variants,
+                optional: false,
+            })
+        }
+        // Null + Null = Null
+        (Value::Null, Value::Null) => Ok(Value::Null),
+        // Null + T = Option<T>
+        (Value::Null, Value::Bool { .. }) => Ok(Value::Bool { optional: true }),
+        (
+            Value::Null,
+            Value::Number {
+                kind,
+                range,
+                examples,
+                ..
+            },
         ) => Ok(Value::Number {
-            optional: (optional || other_opt),
+            optional: true,
+            kind,
+            range,
+            examples,
         }),
         (
-            Value::Number { optional },
+            Value::Null,
             Value::String {
+                format,
+                length,
+                examples,
+                ..
+            },
+        ) => Ok(Value::String {
+            optional: true,
+            format,
+            length,
+            examples,
+        }),
+        (Value::Null, Value::Array { r#type, length, .. }) => Ok(Value::Array {
+            r#type,
+            optional: true,
+            length,
+        }),
+        (Value::Null, Value::Tuple { elements, .. }) => Ok(Value::Tuple {
+            elements,
+            optional: true,
+        }),
+        (
+            Value::Null,
+            Value::Object {
+                content, absent, ..
+            },
+        ) => Ok(Value::Object {
+            content,
+            optional: true,
+            absent,
+        }),
+        (Value::Null, Value::OneOf { variants, .. }) => Ok(Value::OneOf {
+            variants,
+            optional: true,
+        }),
+        (Value::Bool { .. }, Value::Null) => Ok(Value::Bool { optional: true }),
+        // Bool + Option<Bool> = Option<Bool>
+        (
+            Value::Bool { optional },
+            Value::Bool {
+                optional: other_opt,
+            },
+        ) => Ok(Value::Bool {
+            optional: (optional || other_opt),
+        }),
+        // Bool + Number = OneOf[Bool | Number]
+        (
+            Value::Bool { optional },
+            Value::Number {
                 optional: other_opt,
+                kind,
+                range,
+                examples: other_examples,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::String { optional: false },
-                        Value::Number { optional: false },
+                        Value::Bool { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples: other_examples,
+                        },
                         Value::Null,
                     ]
                     .into(),
@@ -283,28 +948,38 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::String { optional: false },
-                        Value::Number { optional: false },
+                        Value::Bool { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples: other_examples,
+                        },
                     ]
                     .into(),
                     optional: false,
                 })
             }
         }
+        // Bool + String = OneOf[Bool | String]
         (
-            Value::Number { optional },
-            Value::Array {
-                r#type,
+            Value::Bool { optional },
+            Value::String {
                 optional: other_opt,
+                format,
+                length,
+                examples: other_examples,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
-                        Value::Array {
-                            r#type,
+                        Value::Bool { optional: false },
+                        Value::String {
                             optional: false,
+                            format,
+                            length,
+                            examples: other_examples,
                         },
                         Value::Null,
                     ]
@@ -314,10 +989,50 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
+                        Value::Bool { optional: false },
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples: other_examples,
+                        },
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            }
+        }
+        // Bool + Array = OneOf[Bool | Array]
+        (
+            Value::Bool { optional },
+            Value::Array {
+                r#type,
+                optional: other_opt,
+                length,
+            },
+        ) => {
+            if optional || other_opt {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Bool { optional: false },
+                        Value::Array {
+                            r#type,
+                            optional: false,
+                            length,
+                        },
+                        Value::Null,
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            } else {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Bool { optional: false },
                         Value::Array {
                             r#type,
                             optional: false,
+                            length,
                         },
                     ]
                     .into(),
@@ -325,8 +1040,9 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                 })
             }
         }
+        // Bool + Array = OneOf[Bool | Array]
         (
-            Value::Number { optional },
+            Value::Bool { optional },
             Value::Tuple {
                 elements,
                 optional: other_opt,
@@ -335,7 +1051,7 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
+                        Value::Bool { optional: false },
                         Value::Tuple {
                             elements,
                             optional: false,
@@ -348,7 +1064,7 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
+                        Value::Bool { optional: false },
                         Value::Tuple {
                             elements,
                             optional: false,
@@ -359,20 +1075,23 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                 })
             }
         }
+        // Bool + Object = OneOf[Bool | Object]
         (
-            Value::Number { optional },
+            Value::Bool { optional },
             Value::Object {
                 content,
                 optional: other_opt,
+                absent,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
+                        Value::Bool { optional: false },
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                         Value::Null,
                     ]
@@ -382,10 +1101,11 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
+                        Value::Bool { optional: false },
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                     ]
                     .into(),
@@ -393,8 +1113,11 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                 })
             }
         }
+        // Bool + Oneof[T | U] = OneOf[Bool | T | U]
+        // Bool + Oneof[Bool | T | U] = OneOf[Bool | T | U]
+        // Option<Bool> + Oneof[Bool | T | U] = OneOf[Bool | T | U | Null]
         (
-            Value::Number { optional },
+            Value::Bool { optional },
             Value::OneOf {
                 mut variants,
                 optional: other_opt,
@@ -403,15 +1126,33 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             if optional && !variants.contains(&Value::Null) {
                 variants.insert(Value::Null);
             }
-            variants.insert(Value::Number { optional: false });
+            variants.insert(Value::Bool { optional: false });
             Ok(Value::OneOf {
                 variants,
                 optional: other_opt,
             })
         }
-        (Value::String { .. }, Value::Null) => Ok(Value::String { optional: true }),
         (
-            Value::String { optional },
+            Value::Number {
+                kind,
+                range,
+                examples,
+                ..
+            },
+            Value::Null,
+        ) => Ok(Value::Number {
+            optional: true,
+            kind,
+            range,
+            examples,
+        }),
+        (
+            Value::Number {
+                optional,
+                kind,
+                range,
+                examples,
+            },
             Value::Bool {
                 optional: other_opt,
             },
@@ -420,7 +1161,12 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                 Ok(Value::OneOf {
                     variants: [
                         Value::Bool { optional: false },
-                        Value::String { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                         Value::Null,
                     ]
                     .into(),
@@ -430,24 +1176,67 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                 Ok(Value::OneOf {
                     variants: [
                         Value::Bool { optional: false },
-                        Value::String { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                     ]
                     .into(),
                     optional: false,
                 })
             }
         }
+        // Integer + Number = Number
+        (
+            Value::Number {
+                optional,
+                kind,
+                range,
+                examples,
+            },
+            Value::Number {
+                optional: other_opt,
+                kind: other_kind,
+                range: other_range,
+                examples: other_examples,
+            },
+        ) => Ok(Value::Number {
+            optional: (optional || other_opt),
+            kind: kind.widen(other_kind),
+            range: merge_ranges(range.as_ref(), other_range.as_ref()),
+            examples: merge_examples(examples.as_deref(), other_examples.as_deref()),
+        }),
         (
-            Value::String { optional },
             Value::Number {
+                optional,
+                kind,
+                range,
+                examples,
+            },
+            Value::String {
                 optional: other_opt,
+                format,
+                length,
+                examples: other_examples,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
-                        Value::String { optional: false },
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples: other_examples,
+                        },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                         Value::Null,
                     ]
                     .into(),
@@ -456,8 +1245,18 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
-                        Value::String { optional: false },
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples: other_examples,
+                        },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                     ]
                     .into(),
                     optional: false,
@@ -465,27 +1264,31 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::String { optional },
-            Value::String {
-                optional: other_opt,
+            Value::Number {
+                optional,
+                kind,
+                range,
+                examples,
             },
-        ) => Ok(Value::String {
-            optional: (optional || other_opt),
-        }),
-        (
-            Value::String { optional },
             Value::Array {
                 r#type,
                 optional: other_opt,
+                length,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::String { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                         Value::Array {
                             r#type,
                             optional: false,
+                            length,
                         },
                         Value::Null,
                     ]
@@ -495,10 +1298,16 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::String { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                         Value::Array {
                             r#type,
                             optional: false,
+                            length,
                         },
                     ]
                     .into(),
@@ -507,7 +1316,12 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::String { optional },
+            Value::Number {
+                optional,
+                kind,
+                range,
+                examples,
+            },
             Value::Tuple {
                 elements,
                 optional: other_opt,
@@ -516,7 +1330,12 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::String { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                         Value::Tuple {
                             elements,
                             optional: false,
@@ -529,7 +1348,12 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::String { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                         Value::Tuple {
                             elements,
                             optional: false,
@@ -541,19 +1365,31 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::String { optional },
+            Value::Number {
+                optional,
+                kind,
+                range,
+                examples,
+            },
             Value::Object {
                 content,
                 optional: other_opt,
+                absent,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::String { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                         Value::Null,
                     ]
@@ -563,10 +1399,16 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::String { optional: false },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples,
+                        },
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                     ]
                     .into(),
@@ -575,7 +1417,12 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::String { optional },
+            Value::Number {
+                optional,
+                kind,
+                range,
+                examples,
+            },
             Value::OneOf {
                 mut variants,
                 optional: other_opt,
@@ -584,18 +1431,41 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             if optional && !variants.contains(&Value::Null) {
                 variants.insert(Value::Null);
             }
-            variants.insert(Value::String { optional: false });
+            insert_variant(
+                &mut variants,
+                Value::Number {
+                    optional: false,
+                    kind,
+                    range,
+                    examples,
+                },
+            );
             Ok(Value::OneOf {
                 variants,
                 optional: other_opt,
             })
         }
-        (Value::Array { r#type, .. }, Value::Null) => Ok(Value::Array {
-            r#type,
+        (
+            Value::String {
+                format,
+                length,
+                examples,
+                ..
+            },
+            Value::Null,
+        ) => Ok(Value::String {
             optional: true,
+            format,
+            length,
+            examples,
         }),
         (
-            Value::Array { r#type, optional },
+            Value::String {
+                optional,
+                format,
+                length,
+                examples,
+            },
             Value::Bool {
                 optional: other_opt,
             },
@@ -603,11 +1473,13 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Array {
-                            r#type,
+                        Value::Bool { optional: false },
+                        Value::String {
                             optional: false,
+                            format,
+                            length,
+                            examples,
                         },
-                        Value::Bool { optional: false },
                         Value::Null,
                     ]
                     .into(),
@@ -616,11 +1488,13 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Array {
-                            r#type,
+                        Value::Bool { optional: false },
+                        Value::String {
                             optional: false,
+                            format,
+                            length,
+                            examples,
                         },
-                        Value::Bool { optional: false },
                     ]
                     .into(),
                     optional: false,
@@ -628,19 +1502,34 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::Array { r#type, optional },
+            Value::String {
+                optional,
+                format,
+                length,
+                examples,
+            },
             Value::Number {
                 optional: other_opt,
+                kind,
+                range,
+                examples: other_examples,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Array {
-                            r#type,
+                        Value::Number {
                             optional: false,
+                            kind,
+                            range,
+                            examples: other_examples,
+                        },
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples,
                         },
-                        Value::Number { optional: false },
                         Value::Null,
                     ]
                     .into(),
@@ -649,11 +1538,18 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Array {
-                            r#type,
+                        Value::Number {
                             optional: false,
+                            kind,
+                            range,
+                            examples: other_examples,
+                        },
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples,
                         },
-                        Value::Number { optional: false },
                     ]
                     .into(),
                     optional: false,
@@ -661,19 +1557,52 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::Array { r#type, optional },
             Value::String {
+                optional,
+                format,
+                length,
+                examples,
+            },
+            Value::String {
+                optional: other_opt,
+                format: other_format,
+                length: other_length,
+                examples: other_examples,
+            },
+        ) => Ok(Value::String {
+            optional: (optional || other_opt),
+            // String<uuid> + String<date> = String
+            format: format.filter(|format| Some(*format) == other_format),
+            length: merge_lengths(length.as_ref(), other_length.as_ref()),
+            examples: merge_examples(examples.as_deref(), other_examples.as_deref()),
+        }),
+        (
+            Value::String {
+                optional,
+                format,
+                length,
+                examples,
+            },
+            Value::Array {
+                r#type,
                 optional: other_opt,
+                length: other_length,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples,
+                        },
                         Value::Array {
                             r#type,
                             optional: false,
+                            length: other_length,
                         },
-                        Value::String { optional: false },
                         Value::Null,
                     ]
                     .into(),
@@ -682,11 +1611,17 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples,
+                        },
                         Value::Array {
                             r#type,
                             optional: false,
+                            length: other_length,
                         },
-                        Value::String { optional: false },
                     ]
                     .into(),
                     optional: false,
@@ -694,65 +1629,80 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::Array { r#type, optional },
-            Value::Array {
-                r#type: other_ty,
-                optional: other_opt,
+            Value::String {
+                optional,
+                format,
+                length,
+                examples,
             },
-        ) => {
-            let ty = merger(*r#type, *other_ty)?;
-            Ok(Value::Array {
-                r#type: Box::new(ty),
-                optional: optional || other_opt,
-            })
-        }
-        (
-            Value::Array {
-                r#type,
-                optional: opt,
+            Value::Tuple {
+                elements,
+                optional: other_opt,
             },
-            Value::Tuple { elements, optional },
         ) => {
-            let mut variants = BTreeSet::default();
-            variants.insert(r#type.deref().clone());
-            for element in elements {
-                variants.insert(element.as_non_optional());
-            }
-            if opt || optional {
-                Ok(Value::Array {
-                    r#type: Box::new(Value::OneOf {
-                        variants,
-                        optional: false,
-                    }),
-                    optional: true,
+            if optional || other_opt {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples,
+                        },
+                        Value::Tuple {
+                            elements,
+                            optional: false,
+                        },
+                        Value::Null,
+                    ]
+                    .into(),
+                    optional: false,
                 })
             } else {
-                Ok(Value::Array {
-                    r#type: Box::new(Value::OneOf {
-                        variants,
-                        optional: false,
-                    }),
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples,
+                        },
+                        Value::Tuple {
+                            elements,
+                            optional: false,
+                        },
+                    ]
+                    .into(),
                     optional: false,
                 })
             }
         }
         (
-            Value::Array { r#type, optional },
+            Value::String {
+                optional,
+                format,
+                length,
+                examples,
+            },
             Value::Object {
                 content,
                 optional: other_opt,
+                absent,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Array {
-                            r#type,
+                        Value::String {
                             optional: false,
+                            format,
+                            length,
+                            examples,
                         },
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                         Value::Null,
                     ]
@@ -762,13 +1712,16 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Array {
-                            r#type,
+                        Value::String {
                             optional: false,
+                            format,
+                            length,
+                            examples,
                         },
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                     ]
                     .into(),
@@ -777,7 +1730,12 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::Array { r#type, optional },
+            Value::String {
+                optional,
+                format,
+                length,
+                examples,
+            },
             Value::OneOf {
                 mut variants,
                 optional: other_opt,
@@ -786,21 +1744,31 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             if optional && !variants.contains(&Value::Null) {
                 variants.insert(Value::Null);
             }
-            variants.insert(Value::Array {
-                r#type,
-                optional: false,
-            });
+            insert_variant(
+                &mut variants,
+                Value::String {
+                    optional: false,
+                    format,
+                    length,
+                    examples,
+                },
+            );
             Ok(Value::OneOf {
                 variants,
                 optional: other_opt,
             })
         }
-        (Value::Object { content, .. }, Value::Null) => Ok(Value::Object {
-            content,
+        (Value::Array { r#type, length, .. }, Value::Null) => Ok(Value::Array {
+            r#type,
             optional: true,
+            length,
         }),
         (
-            Value::Object { content, optional },
+            Value::Array {
+                r#type,
+                optional,
+                length,
+            },
             Value::Bool {
                 optional: other_opt,
             },
@@ -808,9 +1776,10 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Object {
-                            content,
+                        Value::Array {
+                            r#type,
                             optional: false,
+                            length,
                         },
                         Value::Bool { optional: false },
                         Value::Null,
@@ -821,9 +1790,10 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Object {
-                            content,
+                        Value::Array {
+                            r#type,
                             optional: false,
+                            length,
                         },
                         Value::Bool { optional: false },
                     ]
@@ -833,19 +1803,32 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::Object { content, optional },
+            Value::Array {
+                r#type,
+                optional,
+                length,
+            },
             Value::Number {
                 optional: other_opt,
+                kind,
+                range,
+                examples: other_examples,
             },
         ) => {
             if optional || other_opt {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Object {
-                            content,
+                        Value::Array {
+                            r#type,
+                            optional: false,
+                            length,
+                        },
+                        Value::Number {
                             optional: false,
+                            kind,
+                            range,
+                            examples: other_examples,
                         },
-                        Value::Number { optional: false },
                         Value::Null,
                     ]
                     .into(),
@@ -854,11 +1837,263 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             } else {
                 Ok(Value::OneOf {
                     variants: [
-                        Value::Object {
-                            content,
-                            optional: false,
+                        Value::Array {
+                            r#type,
+                            optional: false,
+                            length,
+                        },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples: other_examples,
+                        },
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            }
+        }
+        (
+            Value::Array {
+                r#type,
+                optional,
+                length,
+            },
+            Value::String {
+                optional: other_opt,
+                format,
+                length: other_length,
+                examples: other_examples,
+            },
+        ) => {
+            if optional || other_opt {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Array {
+                            r#type,
+                            optional: false,
+                            length,
+                        },
+                        Value::String {
+                            optional: false,
+                            format,
+                            length: other_length,
+                            examples: other_examples,
+                        },
+                        Value::Null,
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            } else {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Array {
+                            r#type,
+                            optional: false,
+                            length,
+                        },
+                        Value::String {
+                            optional: false,
+                            format,
+                            length: other_length,
+                            examples: other_examples,
+                        },
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            }
+        }
+        (
+            Value::Array {
+                r#type,
+                optional,
+                length,
+            },
+            Value::Array {
+                r#type: other_ty,
+                optional: other_opt,
+                length: other_length,
+            },
+        ) => {
+            let ty = merger(*r#type, *other_ty)?;
+            Ok(Value::Array {
+                r#type: Box::new(ty),
+                optional: optional || other_opt,
+                length: merge_lengths(length.as_ref(), other_length.as_ref()),
+            })
+        }
+        (array @ Value::Array { .. }, tuple @ Value::Tuple { .. }) => merger(tuple, array),
+        (
+            Value::Array {
+                r#type,
+                optional,
+                length,
+            },
+            Value::Object {
+                content,
+                optional: other_opt,
+                absent,
+            },
+        ) => {
+            if optional || other_opt {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Array {
+                            r#type,
+                            optional: false,
+                            length,
+                        },
+                        Value::Object {
+                            content,
+                            optional: false,
+                            absent,
+                        },
+                        Value::Null,
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            } else {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Array {
+                            r#type,
+                            optional: false,
+                            length,
+                        },
+                        Value::Object {
+                            content,
+                            optional: false,
+                            absent,
+                        },
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            }
+        }
+        (
+            Value::Array {
+                r#type,
+                optional,
+                length,
+            },
+            Value::OneOf {
+                mut variants,
+                optional: other_opt,
+            },
+        ) => {
+            if optional && !variants.contains(&Value::Null) {
+                variants.insert(Value::Null);
+            }
+            variants.insert(Value::Array {
+                r#type,
+                optional: false,
+                length,
+            });
+            Ok(Value::OneOf {
+                variants,
+                optional: other_opt,
+            })
+        }
+        (
+            Value::Object {
+                content, absent, ..
+            },
+            Value::Null,
+        ) => Ok(Value::Object {
+            content,
+            optional: true,
+            absent,
+        }),
+        (
+            Value::Object {
+                content,
+                optional,
+                absent,
+            },
+            Value::Bool {
+                optional: other_opt,
+            },
+        ) => {
+            if optional || other_opt {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Object {
+                            content,
+                            optional: false,
+                            absent,
+                        },
+                        Value::Bool { optional: false },
+                        Value::Null,
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            } else {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Object {
+                            content,
+                            optional: false,
+                            absent,
+                        },
+                        Value::Bool { optional: false },
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            }
+        }
+        (
+            Value::Object {
+                content,
+                optional,
+                absent,
+            },
+            Value::Number {
+                optional: other_opt,
+                kind,
+                range,
+                examples: other_examples,
+            },
+        ) => {
+            if optional || other_opt {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Object {
+                            content,
+                            optional: false,
+                            absent,
+                        },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples: other_examples,
+                        },
+                        Value::Null,
+                    ]
+                    .into(),
+                    optional: false,
+                })
+            } else {
+                Ok(Value::OneOf {
+                    variants: [
+                        Value::Object {
+                            content,
+                            optional: false,
+                            absent,
+                        },
+                        Value::Number {
+                            optional: false,
+                            kind,
+                            range,
+                            examples: other_examples,
                         },
-                        Value::Number { optional: false },
                     ]
                     .into(),
                     optional: false,
@@ -866,9 +2101,16 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::Object { content, optional },
+            Value::Object {
+                content,
+                optional,
+                absent,
+            },
             Value::String {
                 optional: other_opt,
+                format,
+                length,
+                examples: other_examples,
             },
         ) => {
             if optional || other_opt {
@@ -877,8 +2119,14 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
+                        },
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples: other_examples,
                         },
-                        Value::String { optional: false },
                         Value::Null,
                     ]
                     .into(),
@@ -890,8 +2138,14 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
+                        },
+                        Value::String {
+                            optional: false,
+                            format,
+                            length,
+                            examples: other_examples,
                         },
-                        Value::String { optional: false },
                     ]
                     .into(),
                     optional: false,
@@ -899,10 +2153,15 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::Object { content, optional },
+            Value::Object {
+                content,
+                optional,
+                absent,
+            },
             Value::Array {
                 r#type,
                 optional: other_opt,
+                length,
             },
         ) => {
             if optional || other_opt {
@@ -911,10 +2170,12 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                         Value::Array {
                             r#type,
                             optional: false,
+                            length,
                         },
                         Value::Null,
                     ]
@@ -927,10 +2188,12 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                         Value::Array {
                             r#type,
                             optional: false,
+                            length,
                         },
                     ]
                     .into(),
@@ -939,7 +2202,11 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::Object { content, optional },
+            Value::Object {
+                content,
+                optional,
+                absent,
+            },
             Value::Tuple {
                 elements,
                 optional: other_opt,
@@ -951,6 +2218,7 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                         Value::Tuple {
                             elements,
@@ -967,6 +2235,7 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         Value::Object {
                             content,
                             optional: false,
+                            absent,
                         },
                         Value::Tuple {
                             elements,
@@ -979,34 +2248,50 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             }
         }
         (
-            Value::Object { content, optional },
+            Value::Object {
+                content,
+                optional,
+                absent,
+            },
             Value::Object {
                 content: mut other_content,
                 optional: other_opt,
+                absent: other_absent,
             },
         ) => {
             let mut map = BTreeMap::default();
+            let mut absent = absent_keys(&content, absent.as_ref())
+                .union(&absent_keys(&other_content, other_absent.as_ref()))
+                .cloned()
+                .collect::<BTreeSet<_>>();
 
             for (key, value) in content {
                 if let Some(other_value) = other_content.remove(&key) {
                     let v = merger(value, other_value)?;
                     map.insert(key, v);
                 } else {
-                    map.insert(key, value.as_optional());
+                    absent.insert(key.clone());
+                    map.insert(key, value);
                 }
             }
 
             for (key, value) in other_content {
-                map.insert(key, value.as_optional());
+                absent.insert(key.clone());
+                map.insert(key, value);
             }
 
             Ok(Value::Object {
                 content: map,
                 optional: optional || other_opt,
+                absent: Some(absent),
             })
         }
         (
-            Value::Object { content, optional },
+            Value::Object {
+                content,
+                optional,
+                absent,
+            },
             Value::OneOf {
                 mut variants,
                 optional: other_opt,
@@ -1018,6 +2303,7 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             variants.insert(Value::Object {
                 content,
                 optional: false,
+                absent,
             });
             Ok(Value::OneOf {
                 variants,
@@ -1038,7 +2324,9 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                 optional: other_opt,
             },
         ) => {
-            variants.extend(other_var);
+            for variant in other_var {
+                insert_variant(&mut variants, variant);
+            }
             Ok(Value::OneOf {
                 variants,
                 optional: optional || other_opt,
@@ -1054,7 +2342,7 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             if value.is_optional() && !variants.contains(&Value::Null) {
                 variants.insert(Value::Null);
             }
-            variants.insert(value.as_non_optional());
+            insert_variant(&mut variants, value.as_non_optional());
             Ok(Value::OneOf { variants, optional })
         }
         (Value::Tuple { elements, .. }, Value::Null) => Ok(Value::Tuple {
@@ -1066,15 +2354,16 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
             Value::Array {
                 r#type,
                 optional: opt,
+                ..
             },
         ) => {
             let mut variants = BTreeSet::default();
             if elements.iter().any(Value::is_optional) || r#type.is_optional() {
                 variants.insert(Value::Null);
             }
-            variants.insert(r#type.deref().clone());
+            insert_variant(&mut variants, r#type.deref().clone());
             for element in elements {
-                variants.insert(element.as_non_optional());
+                insert_variant(&mut variants, element.as_non_optional());
             }
 
             if opt || optional {
@@ -1084,6 +2373,7 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         optional: false,
                     }),
                     optional: true,
+                    length: None,
                 })
             } else {
                 Ok(Value::Array {
@@ -1092,6 +2382,7 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         optional: false,
                     }),
                     optional: false,
+                    length: None,
                 })
             }
         }
@@ -1114,6 +2405,8 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         Some(a.clone().as_optional())
                     } else if a.is_null() {
                         Some(b.clone().as_optional())
+                    } else if a.same_structure(b) {
+                        merger(a.clone(), b.clone()).ok()
                     } else {
                         None
                     }
@@ -1132,11 +2425,8 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                 if elements.iter().any(Value::is_optional) || other.iter().any(Value::is_optional) {
                     variants.insert(Value::Null);
                 }
-                for element in elements {
-                    variants.insert(element.as_non_optional());
-                }
-                for element in other {
-                    variants.insert(element.as_non_optional());
+                for element in elements.into_iter().chain(other) {
+                    insert_variant(&mut variants, element.as_non_optional());
                 }
                 Ok(Value::Array {
                     r#type: Box::new(Value::OneOf {
@@ -1144,459 +2434,1741 @@ pub fn merger(rhs: Value, lhs: Value) -> Result<Value, Error> {
                         optional: false,
                     }),
                     optional: optional || opt,
+                    length: None,
                 })
             }
         }
-        (Value::Tuple { elements, .. }, other) => {
+        (tuple @ Value::Tuple { .. }, one_of @ Value::OneOf { .. }) => merger(one_of, tuple),
+        // Tuple + Bool = OneOf[Bool | Tuple]
+        (tuple @ Value::Tuple { .. }, other) => {
             let mut variants: BTreeSet<Value> = BTreeSet::default();
-            if elements.iter().any(Value::is_optional) || other.is_optional() {
+            if tuple.is_optional() || other.is_optional() {
                 variants.insert(Value::Null);
             }
-            variants.extend(elements.into_iter().map(Value::as_non_optional));
-            variants.insert(other.as_non_optional());
+            variants.insert(tuple.as_non_optional());
+            insert_variant(&mut variants, other.as_non_optional());
+
+            Ok(Value::OneOf {
+                variants,
+                optional: false,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, str::FromStr};
+
+    use super::*;
+    use crate::value::{KeyPattern, LengthRange, LiteralValue, NumberKind, StringFormat};
+
+    #[test]
+    fn merge_simple_json_objects_as_one_of() {
+        let value_1 = Value::Null;
+        let value_2 = Value::Bool { optional: false };
+        let value_3 = Value::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        };
+        let value_4 = Value::String {
+            optional: false,
+            format: None,
+            length: None,
+            examples: None,
+        };
+
+        let result = merge(&[value_1, value_2, value_3, value_4]).unwrap();
+
+        assert_eq!(
+            result,
+            Value::OneOf {
+                variants: BTreeSet::from_iter([
+                    Value::Null,
+                    Value::Bool { optional: false },
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
+                ]),
+                optional: false
+            }
+        );
+    }
+
+    #[test]
+    fn merge_simple_json_objects_with_optional_as_one_of() {
+        let value_2 = Value::Bool { optional: false };
+        let value_3 = Value::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        };
+        let value_4 = Value::String {
+            optional: false,
+            format: None,
+            length: None,
+            examples: None,
+        };
+        let value_5 = Value::Number {
+            optional: true,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        };
+
+        let result = merge(&[value_2, value_3, value_4, value_5]).unwrap();
+
+        assert_eq!(
+            result,
+            Value::OneOf {
+                variants: BTreeSet::from_iter([
+                    Value::Null,
+                    Value::Bool { optional: false },
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
+                ]),
+                optional: false
+            }
+        );
+    }
+
+    #[test]
+    fn merge_one_ofs() {
+        // Option<[Number | Bool]> + [Number | String] => [Number | Bool | String | Null]
+        let value_1 = Value::OneOf {
+            variants: [
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::Bool { optional: false },
+            ]
+            .into(),
+            optional: true,
+        };
+        let value_2 = Value::OneOf {
+            variants: [
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ]
+            .into(),
+            optional: false,
+        };
+
+        let expected = Value::OneOf {
+            variants: [
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::Bool { optional: false },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+                Value::Null,
+            ]
+            .into(),
+            optional: false,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_objects() {
+        let value_1 = Value::Object {
+            content: [
+                ("same_kv".to_string(), Value::Bool { optional: false }),
+                (
+                    "same_k_diff_v".to_string(),
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                ),
+                (
+                    "key".to_string(),
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ),
+            ]
+            .into(),
+            optional: false,
+            absent: Some(BTreeSet::new()),
+        };
+        let value_2 = Value::Object {
+            content: [
+                ("same_kv".to_string(), Value::Bool { optional: false }),
+                (
+                    "same_k_diff_v".to_string(),
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ),
+                ("other_key".to_string(), Value::Bool { optional: false }),
+            ]
+            .into(),
+            optional: false,
+            absent: Some(BTreeSet::new()),
+        };
+
+        let expected = Value::Object {
+            content: [
+                ("same_kv".to_string(), Value::Bool { optional: false }),
+                (
+                    "same_k_diff_v".to_string(),
+                    Value::OneOf {
+                        variants: [
+                            Value::String {
+                                optional: false,
+                                format: None,
+                                length: None,
+                                examples: None,
+                            },
+                            Value::Number {
+                                optional: false,
+                                kind: NumberKind::Float,
+                                range: None,
+                                examples: None,
+                            },
+                        ]
+                        .into(),
+                        optional: false,
+                    },
+                ),
+                ("other_key".to_string(), Value::Bool { optional: false }),
+                (
+                    "key".to_string(),
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ),
+            ]
+            .into(),
+            optional: false,
+            absent: Some(["key".to_string(), "other_key".to_string()].into()),
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_diff_arrays() {
+        let value_1 = Value::Array {
+            r#type: Box::new(Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None,
+            }),
+            optional: true,
+            length: None,
+        };
+        let value_2 = Value::Array {
+            r#type: Box::new(Value::Bool { optional: false }),
+            optional: false,
+            length: None,
+        };
+
+        let expected = Value::Array {
+            r#type: Box::new(Value::OneOf {
+                variants: [
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                    Value::Bool { optional: false },
+                    Value::Null,
+                ]
+                .into(),
+                optional: false,
+            }),
+            optional: true,
+            length: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_same_arrays() {
+        let value_1 = Value::Array {
+            r#type: Box::new(Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+        let value_2 = Value::Array {
+            r#type: Box::new(Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        let expected = Value::Array {
+            r#type: Box::new(Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    // AI GENERATED
+    #[test]
+    fn merge_objects_tracks_absent_keys_apart_from_null_values() {
+        let value = |source: &str| Value::from_str(source).unwrap();
+
+        let merged = merge(&[
+            value(r#"{"a": 1, "b": true}"#),
+            value(r#"{"a": null}"#),
+            value(r#"{"a": 2, "c": null}"#),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            merged.to_string(),
+            "Object{a: Option<Integer<unsigned>>, b?: Boolean, c?: Null}"
+        );
+    }
+
+    #[test]
+    fn merge_objects_with_different_keys() {
+        let value_1 = Value::Object {
+            content: [
+                (
+                    "key1".to_string(),
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ),
+                (
+                    "key2".to_string(),
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                ),
+            ]
+            .into(),
+            optional: false,
+            absent: Some(BTreeSet::new()),
+        };
+        let value_2 = Value::Object {
+            content: [
+                ("key3".to_string(), Value::Bool { optional: false }),
+                (
+                    "key4".to_string(),
+                    Value::Array {
+                        r#type: Box::new(Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None,
+                        }),
+                        optional: false,
+                        length: None,
+                    },
+                ),
+            ]
+            .into(),
+            optional: false,
+            absent: Some(BTreeSet::new()),
+        };
+
+        let expected = Value::Object {
+            content: [
+                (
+                    "key1".to_string(),
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ),
+                (
+                    "key2".to_string(),
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                ),
+                ("key3".to_string(), Value::Bool { optional: false }),
+                (
+                    "key4".to_string(),
+                    Value::Array {
+                        r#type: Box::new(Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None,
+                        }),
+                        optional: false,
+                        length: None,
+                    },
+                ),
+            ]
+            .into(),
+            optional: false,
+            absent: Some(["key1", "key2", "key3", "key4"].map(String::from).into()),
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_arrays_with_different_types() {
+        let value_1 = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+        let value_2 = Value::Array {
+            r#type: Box::new(Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        let expected = Value::Array {
+            r#type: Box::new(Value::OneOf {
+                variants: [
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ]
+                .into(),
+                optional: false,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_value_with_itself() {
+        let value = Value::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        };
+        assert_eq!(merge(&[value.clone(), value.clone()]).unwrap(), value);
+    }
+
+    #[test]
+    fn merge_empty_array_with_non_empty_array() {
+        let value_1 = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+        let value_2 = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        let expected = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_tuples_with_same_length() {
+        let value_1 = Value::Tuple {
+            elements: vec![
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ],
+            optional: false,
+        };
+        let value_2 = Value::Tuple {
+            elements: vec![
+                Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ],
+            optional: false,
+        };
+
+        let expected = Value::Tuple {
+            elements: vec![
+                Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ],
+            optional: false,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_tuples_with_same_length_optional_tuples() {
+        let value_1 = Value::Tuple {
+            elements: vec![
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ],
+            optional: false,
+        };
+        let value_2 = Value::Tuple {
+            elements: vec![
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ],
+            optional: true,
+        };
+
+        let expected = Value::Tuple {
+            elements: vec![
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ],
+            optional: true,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_tuples_with_different_lengths() {
+        let value_1 = Value::Tuple {
+            elements: vec![
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ],
+            optional: false,
+        };
+        let value_2 = Value::Tuple {
+            elements: vec![
+                Value::Bool { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ],
+            optional: false,
+        };
+
+        let expected = Value::Array {
+            r#type: Box::new(Value::OneOf {
+                variants: [
+                    Value::Bool { optional: false },
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                    Value::Null,
+                ]
+                .into(),
+                optional: false,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_tuple_with_array() {
+        let value_1 = Value::Tuple {
+            elements: vec![
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ],
+            optional: false,
+        };
+        let value_2 = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        let expected = Value::Array {
+            r#type: Box::new(Value::OneOf {
+                variants: [
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ]
+                .into(),
+                optional: false,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_arrays_with_same_type() {
+        let value_1 = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+        let value_2 = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
 
-            Ok(Value::OneOf {
-                variants,
+        let expected = Value::Array {
+            r#type: Box::new(Value::Number {
                 optional: false,
-            })
-        }
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::collections::BTreeSet;
+    #[test]
+    fn merge_arrays_with_same_type_but_one_optional() {
+        let value_1 = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+        let value_2 = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
 
-    use super::*;
+        let expected = Value::Array {
+            r#type: Box::new(Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
 
     #[test]
-    fn merge_simple_json_objects_as_one_of() {
-        let value_1 = Value::Null;
-        let value_2 = Value::Bool { optional: false };
-        let value_3 = Value::Number { optional: false };
-        let value_4 = Value::String { optional: false };
+    fn merge_integer_with_number_widens_to_number() {
+        let value_1 = Value::Number {
+            optional: false,
+            kind: NumberKind::Unsigned,
+            range: None,
+            examples: None,
+        };
+        let value_2 = Value::Number {
+            optional: true,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        };
 
-        let result = merge(&[value_1, value_2, value_3, value_4]).unwrap();
+        let expected = Value::Number {
+            optional: true,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_integers_widens_kind() {
+        let unsigned = Value::Number {
+            optional: false,
+            kind: NumberKind::Unsigned,
+            range: None,
+            examples: None,
+        };
+        let signed = Value::Number {
+            optional: false,
+            kind: NumberKind::Signed,
+            range: None,
+            examples: None,
+        };
+        let large = Value::Number {
+            optional: false,
+            kind: NumberKind::LargeUnsigned,
+            range: None,
+            examples: None,
+        };
 
         assert_eq!(
-            result,
-            Value::OneOf {
-                variants: BTreeSet::from_iter([
-                    Value::Null,
-                    Value::Bool { optional: false },
-                    Value::Number { optional: false },
-                    Value::String { optional: false }
-                ]),
-                optional: false
+            merge(&[signed.clone(), large.clone()]).unwrap(),
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
             }
         );
+        assert_eq!(merge(&[unsigned.clone(), signed.clone()]).unwrap(), signed);
+        assert_eq!(merge(&[unsigned, large.clone()]).unwrap(), large);
+    }
+
+    fn ranged(kind: NumberKind, min: i64, max: i64) -> Value {
+        Value::Number {
+            optional: false,
+            kind,
+            range: Some(NumberRange::new(&min.into()).union(&NumberRange::new(&max.into()))),
+            examples: None,
+        }
     }
 
     #[test]
-    fn merge_simple_json_objects_with_optional_as_one_of() {
-        let value_2 = Value::Bool { optional: false };
-        let value_3 = Value::Number { optional: false };
-        let value_4 = Value::String { optional: false };
-        let value_5 = Value::Number { optional: true };
+    fn merge_ranged_numbers_unions_ranges() {
+        let value_1 = ranged(NumberKind::Unsigned, 0, 1);
+        let value_2 = ranged(NumberKind::Signed, -1, 0);
 
-        let result = merge(&[value_2, value_3, value_4, value_5]).unwrap();
+        let value = merge(&[value_1, value_2]).unwrap();
 
         assert_eq!(
-            result,
-            Value::OneOf {
-                variants: BTreeSet::from_iter([
-                    Value::Null,
-                    Value::Bool { optional: false },
-                    Value::Number { optional: false },
-                    Value::String { optional: false }
-                ]),
-                optional: false
+            value,
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Signed,
+                range: Some(NumberRange {
+                    min: (-1).into(),
+                    max: 1.into(),
+                    zero: true,
+                }),
+                examples: None,
             }
         );
+        assert_eq!(value.to_string(), "Integer<signed>[-1..=1]");
     }
 
     #[test]
-    fn merge_one_ofs() {
-        // Option<[Number | Bool]> + [Number | String] => Option<[Number | Bool | String]>
+    fn merge_ranged_number_with_unranged_number_drops_range() {
+        let value_1 = ranged(NumberKind::Unsigned, 0, 1);
+        let value_2 = Value::Number {
+            optional: true,
+            kind: NumberKind::Unsigned,
+            range: None,
+            examples: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2.clone()]).unwrap(), value_2);
+    }
+
+    fn bounded_string(min: usize, max: usize) -> Value {
+        Value::String {
+            optional: false,
+            format: None,
+            length: Some(LengthRange { min, max }),
+            examples: None,
+        }
+    }
+
+    #[test]
+    fn merge_bounded_strings_unions_lengths() {
+        let value = merge(&[bounded_string(1, 2), bounded_string(3, 3)]).unwrap();
+
+        assert_eq!(value, bounded_string(1, 3));
+        assert_eq!(value.to_string(), "String[1..=3]");
+    }
+
+    #[test]
+    fn merge_bounded_arrays_unions_lengths_and_drops_them_for_tuples() {
+        let array = |min: usize, max: usize| Value::Array {
+            r#type: Box::new(Value::Bool { optional: false }),
+            optional: false,
+            length: Some(LengthRange { min, max }),
+        };
+        let tuple = Value::Tuple {
+            elements: vec![Value::Bool { optional: false }, Value::Null],
+            optional: false,
+        };
+
+        assert_eq!(merge(&[array(0, 1), array(2, 4)]).unwrap(), array(0, 4));
+        assert_eq!(
+            merge(&[array(0, 1), tuple]).unwrap().to_string(),
+            "Array<Option<Boolean>>"
+        );
+    }
+
+    #[test]
+    fn merge_number_into_one_of_widens_integer_variant() {
         let value_1 = Value::OneOf {
             variants: [
-                Value::Number { optional: false },
-                Value::Bool { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ]
+            .into(),
+            optional: false,
+        };
+        let value_2 = Value::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        };
+
+        let expected = Value::OneOf {
+            variants: [
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
             ]
             .into(),
+            optional: false,
+        };
+
+        assert_eq!(
+            merge(&[value_1.clone(), value_2.clone()]).unwrap(),
+            expected
+        );
+        assert_eq!(merge(&[value_2, value_1]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_strings_keeps_matching_format() {
+        let value_1 = Value::String {
+            optional: false,
+            format: Some(StringFormat::DateTime),
+            length: None,
+            examples: None,
+        };
+        let value_2 = Value::String {
             optional: true,
+            format: Some(StringFormat::DateTime),
+            length: None,
+            examples: None,
         };
-        let value_2 = Value::OneOf {
+
+        let expected = Value::String {
+            optional: true,
+            format: Some(StringFormat::DateTime),
+            length: None,
+            examples: None,
+        };
+
+        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_strings_with_mismatching_formats_is_plain_string() {
+        let uuid = Value::String {
+            optional: false,
+            format: Some(StringFormat::Uuid),
+            length: None,
+            examples: None,
+        };
+        let date = Value::String {
+            optional: false,
+            format: Some(StringFormat::Date),
+            length: None,
+            examples: None,
+        };
+        let plain = Value::String {
+            optional: false,
+            format: None,
+            length: None,
+            examples: None,
+        };
+
+        assert_eq!(merge(&[uuid.clone(), date]).unwrap(), plain);
+        assert_eq!(merge(&[plain.clone(), uuid]).unwrap(), plain);
+    }
+
+    #[test]
+    fn merge_string_into_one_of_drops_mismatching_format() {
+        let value_1 = Value::OneOf {
             variants: [
-                Value::Number { optional: false },
-                Value::String { optional: false },
+                Value::Bool { optional: false },
+                Value::String {
+                    optional: false,
+                    format: Some(StringFormat::Email),
+                    length: None,
+                    examples: None,
+                },
             ]
             .into(),
             optional: false,
         };
+        let value_2 = Value::String {
+            optional: false,
+            format: None,
+            length: None,
+            examples: None,
+        };
 
         let expected = Value::OneOf {
             variants: [
-                Value::Number { optional: false },
                 Value::Bool { optional: false },
-                Value::String { optional: false },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
             ]
             .into(),
+            optional: false,
+        };
+
+        assert_eq!(
+            merge(&[value_1.clone(), value_2.clone()]).unwrap(),
+            expected
+        );
+        assert_eq!(merge(&[value_2, value_1]).unwrap(), expected);
+    }
+
+    fn literal(values: &[&str]) -> Value {
+        Value::Literal {
+            values: values
+                .iter()
+                .map(|value| LiteralValue::String((*value).to_string()))
+                .collect(),
+            optional: false,
+        }
+    }
+
+    #[test]
+    fn merge_literals_unions_values() {
+        let value_1 = literal(&["active"]);
+        let value_2 = literal(&["disabled", "active"]).as_optional();
+
+        assert_eq!(
+            merge(&[value_1, value_2]).unwrap(),
+            literal(&["active", "disabled"]).as_optional()
+        );
+    }
+
+    #[test]
+    fn merge_literal_with_string_widens_to_string() {
+        let value_1 = literal(&["active"]);
+        let value_2 = Value::String {
             optional: true,
+            format: None,
+            length: None,
+            examples: None,
         };
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(
+            merge(&[value_1.clone(), value_2.clone()]).unwrap(),
+            Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None,
+            }
+        );
+        assert_eq!(
+            merge(&[value_2, value_1]).unwrap(),
+            Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None,
+            }
+        );
     }
 
     #[test]
-    fn merge_objects() {
-        let value_1 = Value::Object {
-            content: [
-                ("same_kv".to_string(), Value::Bool { optional: false }),
-                (
-                    "same_k_diff_v".to_string(),
-                    Value::Number { optional: false },
-                ),
-                ("key".to_string(), Value::String { optional: false }),
+    fn merge_literal_with_other_types_keeps_literal_variant() {
+        let value_1 = literal(&["active"]);
+        let value_2 = Value::Bool { optional: true };
+
+        let expected = Value::OneOf {
+            variants: [
+                Value::Bool { optional: false },
+                literal(&["active"]),
+                Value::Null,
             ]
             .into(),
             optional: false,
         };
-        let value_2 = Value::Object {
-            content: [
-                ("same_kv".to_string(), Value::Bool { optional: false }),
-                (
-                    "same_k_diff_v".to_string(),
-                    Value::String { optional: false },
-                ),
-                ("other_key".to_string(), Value::Bool { optional: false }),
-            ]
-            .into(),
+
+        assert_eq!(
+            merge(&[value_1.clone(), value_2.clone()]).unwrap(),
+            expected
+        );
+        assert_eq!(merge(&[value_2, value_1]).unwrap(), expected);
+    }
+
+    #[test]
+    fn merge_literal_into_one_of() {
+        let value_1 = Value::OneOf {
+            variants: [Value::Bool { optional: false }, literal(&["a"])].into(),
             optional: false,
         };
 
-        let expected = Value::Object {
-            content: [
-                ("same_kv".to_string(), Value::Bool { optional: false }),
-                (
-                    "same_k_diff_v".to_string(),
-                    Value::OneOf {
-                        variants: [
-                            Value::String { optional: false },
-                            Value::Number { optional: false },
-                        ]
-                        .into(),
+        assert_eq!(
+            merge(&[value_1.clone(), literal(&["b"])]).unwrap(),
+            Value::OneOf {
+                variants: [Value::Bool { optional: false }, literal(&["a", "b"])].into(),
+                optional: false,
+            }
+        );
+        assert_eq!(
+            merge(&[
+                value_1,
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+            ])
+            .unwrap(),
+            Value::OneOf {
+                variants: [
+                    Value::Bool { optional: false },
+                    Value::String {
                         optional: false,
-                    },
-                ),
-                ("other_key".to_string(), Value::Bool { optional: true }),
-                ("key".to_string(), Value::String { optional: true }),
-            ]
-            .into(),
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
+                ]
+                .into(),
+                optional: false,
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_rewrites_one_ofs_to_canonical_form() {
+        let number = |optional: bool, kind: NumberKind| Value::Number {
+            optional,
+            kind,
+            range: None,
+            examples: None,
+        };
+        let string = Value::String {
             optional: false,
+            format: None,
+            length: None,
+            examples: None,
+        };
+        let one_of = |variants: Vec<Value>, optional: bool| Value::OneOf {
+            variants: variants.into_iter().collect(),
+            optional,
         };
+        let canonical = one_of(
+            vec![
+                number(false, NumberKind::Float),
+                string.clone(),
+                Value::Null,
+            ],
+            false,
+        );
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(
+            normalize(one_of(
+                vec![number(false, NumberKind::Float), string.clone()],
+                true
+            )),
+            canonical
+        );
+        assert_eq!(
+            normalize(one_of(
+                vec![
+                    Value::Null,
+                    one_of(
+                        vec![number(false, NumberKind::Float), string.clone()],
+                        false
+                    )
+                ],
+                false
+            )),
+            canonical
+        );
+        assert_eq!(
+            normalize(one_of(
+                vec![
+                    number(true, NumberKind::Float),
+                    number(false, NumberKind::Unsigned),
+                    string.clone()
+                ],
+                false
+            )),
+            canonical
+        );
+        assert_eq!(
+            normalize(one_of(
+                vec![
+                    number(false, NumberKind::Signed),
+                    Value::Null,
+                    Value::Unknown
+                ],
+                false
+            )),
+            number(true, NumberKind::Signed)
+        );
+        assert_eq!(normalize(one_of(vec![string.clone()], false)), string);
+        assert_eq!(normalize(canonical.clone()), canonical);
     }
 
     #[test]
-    fn merge_diff_arrays() {
-        let value_1 = Value::Array {
-            r#type: Box::new(Value::String { optional: true }),
-            optional: true,
-        };
-        let value_2 = Value::Array {
-            r#type: Box::new(Value::Bool { optional: false }),
-            optional: false,
-        };
+    fn normalize_keeps_variants_without_widening_them() {
+        for notation in [
+            "OneOf[Object{a: Boolean} | Object{b: Boolean}]",
+            "OneOf[Array<String> | Tuple(Integer<unsigned>, Boolean)]",
+            "OneOf[Integer<signed> | Integer<u64>]",
+            "OneOf[String<email> | String<uuid>]",
+        ] {
+            let shape = Value::parse_shape(notation).unwrap();
 
-        let expected = Value::Array {
-            r#type: Box::new(Value::OneOf {
-                variants: [
-                    Value::String { optional: false },
-                    Value::Bool { optional: false },
-                    Value::Null,
-                ]
+            assert_eq!(normalize(shape.clone()), shape, "{notation}");
+        }
+        assert_eq!(
+            normalize(
+                Value::parse_shape("OneOf[Integer<signed> | Number | Literal[\"a\"] | String]")
+                    .unwrap()
+            )
+            .to_string(),
+            "OneOf[Number | String]"
+        );
+    }
+
+    #[test]
+    fn normalize_rewrites_nested_shapes() {
+        let value = Value::Array {
+            r#type: Box::new(Value::Object {
+                content: [(
+                    "a".to_string(),
+                    Value::OneOf {
+                        variants: [Value::Bool { optional: false }].into(),
+                        optional: true,
+                    },
+                )]
                 .into(),
                 optional: false,
+                absent: Some(BTreeSet::new()),
             }),
-            optional: true,
+            optional: false,
+            length: None,
         };
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(
+            normalize(value).to_string(),
+            "Array<Object{a: Option<Boolean>}>"
+        );
     }
 
     #[test]
-    fn merge_same_arrays() {
-        let value_1 = Value::Array {
-            r#type: Box::new(Value::String { optional: true }),
-            optional: false,
-        };
-        let value_2 = Value::Array {
-            r#type: Box::new(Value::String { optional: false }),
+    fn merge_unknown_is_identity() {
+        let array = |r#type: Value| Value::Array {
+            r#type: Box::new(r#type),
             optional: false,
+            length: None,
         };
-
-        let expected = Value::Array {
-            r#type: Box::new(Value::String { optional: true }),
+        let number = Value::Number {
             optional: false,
+            kind: NumberKind::Unsigned,
+            range: None,
+            examples: None,
         };
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(merger(Value::Unknown, Value::Null).unwrap(), Value::Null);
+        assert_eq!(
+            merger(array(Value::Unknown), array(number.clone())).unwrap(),
+            array(number.clone())
+        );
+        assert_eq!(
+            merger(array(number.clone()), array(Value::Unknown)).unwrap(),
+            array(number)
+        );
+        assert_eq!(
+            merger(array(Value::Unknown), array(Value::Unknown)).unwrap(),
+            array(Value::Unknown)
+        );
     }
 
-    // AI GENERATED
     #[test]
-    fn merge_objects_with_different_keys() {
-        let value_1 = Value::Object {
-            content: [
-                ("key1".to_string(), Value::String { optional: false }),
-                ("key2".to_string(), Value::Number { optional: false }),
-            ]
-            .into(),
+    fn merge_keeps_examples_of_numbers_and_strings() {
+        let number = |value: u64| Value::Number {
             optional: false,
+            kind: NumberKind::Unsigned,
+            range: None,
+            examples: Some(Box::new(Examples::new(&value.into()))),
         };
-        let value_2 = Value::Object {
-            content: [
-                ("key3".to_string(), Value::Bool { optional: false }),
-                (
-                    "key4".to_string(),
-                    Value::Array {
-                        r#type: Box::new(Value::String { optional: false }),
-                        optional: false,
-                    },
-                ),
-            ]
-            .into(),
+        let string = |value: &str| Value::String {
             optional: false,
+            format: None,
+            length: None,
+            examples: Some(Box::new(Examples::new(&value.into()))),
         };
 
-        let expected = Value::Object {
+        let merged = merge(&[
+            number(1),
+            Value::Null,
+            string("a"),
+            number(2),
+            number(1),
+            string("b"),
+        ])
+        .unwrap();
+
+        let Value::OneOf { variants, .. } = limit_examples(merged, 1) else {
+            panic!("expected OneOf");
+        };
+        let examples = variants
+            .iter()
+            .filter_map(|variant| match variant {
+                Value::Number { examples, .. } | Value::String { examples, .. } => {
+                    examples.as_ref().map(ToString::to_string)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(examples, ["1", r#""a""#]);
+    }
+
+    #[test]
+    fn limit_literals_widens_literals_past_limit() {
+        let value = Value::Object {
             content: [
-                ("key1".to_string(), Value::String { optional: true }),
-                ("key2".to_string(), Value::Number { optional: true }),
-                ("key3".to_string(), Value::Bool { optional: true }),
+                ("small".to_string(), literal(&["a", "b"])),
                 (
-                    "key4".to_string(),
+                    "large".to_string(),
                     Value::Array {
-                        r#type: Box::new(Value::String { optional: false }),
-                        optional: true,
+                        r#type: Box::new(literal(&["a", "b", "c"])),
+                        optional: false,
+                        length: None,
                     },
                 ),
             ]
             .into(),
             optional: false,
+            absent: Some(BTreeSet::new()),
         };
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(
+            limit_literals(value, 2, &InferenceOptions::default()).to_string(),
+            r#"Object{large: Array<String>, small: Literal["a" | "b"]}"#
+        );
     }
 
-    #[test]
-    fn merge_arrays_with_different_types() {
-        let value_1 = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: false,
-        };
-        let value_2 = Value::Array {
-            r#type: Box::new(Value::String { optional: false }),
-            optional: false,
-        };
-
-        let expected = Value::Array {
-            r#type: Box::new(Value::OneOf {
-                variants: [
-                    Value::Number { optional: false },
-                    Value::String { optional: false },
-                ]
-                .into(),
-                optional: false,
-            }),
+    fn object(keys: &[&str], value: &Value) -> Value {
+        Value::Object {
+            content: keys
+                .iter()
+                .map(|key| ((*key).to_string(), value.clone()))
+                .collect(),
             optional: false,
-        };
-
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
-    }
-
-    #[test]
-    fn merge_value_with_itself() {
-        let value = Value::Number { optional: false };
-        assert_eq!(merge(&[value.clone(), value.clone()]).unwrap(), value);
+            absent: Some(BTreeSet::new()),
+        }
     }
 
     #[test]
-    fn merge_empty_array_with_non_empty_array() {
-        let value_1 = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: false,
-        };
-        let value_2 = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
+    fn detect_maps_past_threshold_with_their_key_pattern() {
+        let number = Value::Number {
             optional: false,
+            kind: NumberKind::Unsigned,
+            range: None,
+            examples: None,
         };
-
-        let expected = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
+        let value = Value::Object {
+            content: [
+                ("by_id".to_string(), object(&["1", "2", "3"], &number)),
+                ("few_ids".to_string(), object(&["1", "2"], &number)),
+                ("few".to_string(), object(&["a", "b"], &number)),
+                ("many".to_string(), object(&["a", "b", "c"], &number)),
+            ]
+            .into(),
             optional: false,
+            absent: Some(BTreeSet::new()),
         };
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(
+            detect_maps(value, 3).to_string(),
+            "Object{by_id: Map<Integer, Integer<unsigned>>, few: Object{a: Integer<unsigned>, b: Integer<unsigned>}, few_ids: Object{1: Integer<unsigned>, 2: Integer<unsigned>}, many: Map<String, Integer<unsigned>>}"
+        );
     }
 
     #[test]
-    fn merge_tuples_with_same_length() {
-        let value_1 = Value::Tuple {
-            elements: vec![
-                Value::Number { optional: false },
-                Value::String { optional: true },
-            ],
-            optional: false,
-        };
-        let value_2 = Value::Tuple {
-            elements: vec![
-                Value::Number { optional: true },
-                Value::String { optional: false },
-            ],
-            optional: false,
-        };
-
-        let expected = Value::Tuple {
-            elements: vec![
-                Value::Number { optional: true },
-                Value::String { optional: true },
-            ],
+    fn detect_maps_keeps_single_key_objects() {
+        let number = Value::Number {
             optional: false,
+            kind: NumberKind::Unsigned,
+            range: None,
+            examples: None,
         };
+        for key in ["1", "a@b.co", "2024-01-01"] {
+            let value = object(&[key], &number);
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+            assert_eq!(detect_maps(value.clone(), 50), value);
+            assert_eq!(detect_maps(value.clone(), 1), value);
+        }
     }
 
     #[test]
-    fn merge_tuples_with_same_length_optional_tuples() {
-        let value_1 = Value::Tuple {
-            elements: vec![
-                Value::Number { optional: false },
-                Value::String { optional: false },
-            ],
+    fn detect_maps_ignores_heterogeneous_values() {
+        let value = Value::Object {
+            content: [
+                ("1".to_string(), Value::Bool { optional: false }),
+                (
+                    "2".to_string(),
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ),
+            ]
+            .into(),
             optional: false,
-        };
-        let value_2 = Value::Tuple {
-            elements: vec![
-                Value::Number { optional: false },
-                Value::String { optional: false },
-            ],
-            optional: true,
-        };
-
-        let expected = Value::Tuple {
-            elements: vec![
-                Value::Number { optional: false },
-                Value::String { optional: false },
-            ],
-            optional: true,
+            absent: Some(BTreeSet::new()),
         };
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(detect_maps(value.clone(), 1), value);
     }
 
     #[test]
-    fn merge_tuples_with_different_lengths() {
-        let value_1 = Value::Tuple {
-            elements: vec![
-                Value::Number { optional: false },
-                Value::String { optional: false },
-            ],
-            optional: false,
-        };
-        let value_2 = Value::Tuple {
-            elements: vec![
-                Value::Bool { optional: false },
-                Value::Number { optional: false },
-                Value::String { optional: true },
-            ],
+    fn merge_map_with_object_merges_values() {
+        let map = Value::Map {
+            r#type: Box::new(Value::Bool { optional: false }),
+            key: Some(KeyPattern::Integer),
             optional: false,
         };
 
-        let expected = Value::Array {
-            r#type: Box::new(Value::OneOf {
-                variants: [
-                    Value::Bool { optional: false },
-                    Value::Number { optional: false },
-                    Value::String { optional: false },
-                    Value::Null,
-                ]
-                .into(),
+        assert_eq!(
+            merge(&[map.clone(), object(&["3", "4"], &Value::Null)]).unwrap(),
+            Value::Map {
+                r#type: Box::new(Value::Bool { optional: true }),
+                key: Some(KeyPattern::Integer),
                 optional: false,
-            }),
+            }
+        );
+        assert_eq!(
+            merge(&[object(&["user_1"], &Value::Bool { optional: false }), map])
+                .unwrap()
+                .to_string(),
+            "Map<String, Boolean>"
+        );
+    }
+
+    #[test]
+    fn merge_maps_keeps_matching_key_pattern() {
+        let map = |key| Value::Map {
+            r#type: Box::new(Value::Bool { optional: false }),
+            key,
             optional: false,
         };
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(
+            merge(&[
+                map(Some(KeyPattern::Integer)),
+                map(Some(KeyPattern::Integer))
+            ])
+            .unwrap(),
+            map(Some(KeyPattern::Integer))
+        );
+        assert_eq!(
+            merge(&[map(Some(KeyPattern::Integer)), map(None), Value::Null]).unwrap(),
+            map(None).as_optional()
+        );
     }
 
     #[test]
-    fn merge_tuple_with_array() {
-        let value_1 = Value::Tuple {
-            elements: vec![
-                Value::Number { optional: false },
-                Value::String { optional: false },
-            ],
+    fn merge_recursive_with_object_merges_into_definition() {
+        let reference = Value::Ref {
+            name: "Root".to_string(),
             optional: false,
         };
-        let value_2 = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: false,
+        let recursive = |next: Value| Value::Recursive {
+            name: "Root".to_string(),
+            shape: Box::new(object(&["next"], &next)),
         };
 
-        let expected = Value::Array {
-            r#type: Box::new(Value::OneOf {
-                variants: [
-                    Value::Number { optional: false },
-                    Value::String { optional: false },
-                ]
-                .into(),
-                optional: false,
-            }),
+        assert_eq!(
+            merge(&[recursive(reference.clone()), Value::Null]).unwrap(),
+            recursive(reference.clone()).as_optional()
+        );
+        assert_eq!(
+            merge(&[
+                recursive(reference.clone()),
+                object(&["next"], &Value::Null)
+            ])
+            .unwrap(),
+            recursive(reference.clone().as_optional())
+        );
+        assert_eq!(
+            merge(&[
+                recursive(reference),
+                Value::Recursive {
+                    name: "Node".to_string(),
+                    shape: Box::new(object(
+                        &["next"],
+                        &Value::Ref {
+                            name: "Node".to_string(),
+                            optional: true,
+                        }
+                    )),
+                }
+            ])
+            .unwrap(),
+            recursive(Value::Ref {
+                name: "Root".to_string(),
+                optional: true,
+            })
+        );
+    }
+
+    fn tagged(variants: &[(&str, &[&str])]) -> Value {
+        Value::Tagged {
+            tag: "type".to_string(),
+            variants: variants
+                .iter()
+                .map(|(value, keys)| {
+                    (
+                        (*value).to_string(),
+                        object(keys, &Value::Bool { optional: false }),
+                    )
+                })
+                .collect(),
             optional: false,
-        };
+        }
+    }
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+    #[test]
+    fn merge_tagged_unions_merges_variants_by_tag_value() {
+        assert_eq!(
+            merge(&[
+                tagged(&[("a", &["x"])]),
+                tagged(&[("a", &["y"]), ("b", &[])])
+            ])
+            .unwrap()
+            .to_string(),
+            r#"Tagged<type>["a": Object{x?: Boolean, y?: Boolean} | "b": Object{}]"#
+        );
+        assert_eq!(
+            merge(&[tagged(&[("a", &["x"])]), Value::Null]).unwrap(),
+            tagged(&[("a", &["x"])]).as_optional()
+        );
     }
 
     #[test]
-    fn merge_arrays_with_same_type() {
-        let value_1 = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: false,
-        };
-        let value_2 = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: false,
-        };
+    fn merge_tagged_union_with_object_merges_variants_into_object() {
+        assert_eq!(
+            merge(&[
+                tagged(&[("a", &["x"]), ("b", &[])]),
+                object(
+                    &["type"],
+                    &Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
+                )
+            ])
+            .unwrap()
+            .to_string(),
+            "Object{type: String, x?: Boolean}"
+        );
+    }
 
-        let expected = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: false,
-        };
+    #[test]
+    fn merge_tuple_with_other_shapes_keeps_tuple_as_variant() {
+        let tuple = Value::from_str(r#"["a", null]"#).unwrap();
+        let bool = Value::Bool { optional: false };
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(
+            merger(tuple.clone(), bool.clone()).unwrap().to_string(),
+            "OneOf[Boolean | Tuple(String, Null)]"
+        );
+        assert_eq!(
+            merger(tuple.clone(), bool.clone()).unwrap(),
+            merger(bool, tuple).unwrap()
+        );
     }
 
     #[test]
-    fn merge_arrays_with_same_type_but_one_optional() {
-        let value_1 = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: false,
-        };
-        let value_2 = Value::Array {
-            r#type: Box::new(Value::Number { optional: true }),
-            optional: false,
-        };
-
-        let expected = Value::Array {
-            r#type: Box::new(Value::Number { optional: true }),
-            optional: false,
-        };
+    fn merge_one_of_refines_object_and_array_variants() {
+        let sources = [
+            "true",
+            r#"{"a": 1}"#,
+            r#"[1, "a"]"#,
+            r#"{"b": 2}"#,
+            "[true]",
+        ];
+        let merged = merge(
+            &sources
+                .iter()
+                .map(|source| Value::from_str(source).unwrap())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
 
-        assert_eq!(merge(&[value_1, value_2]).unwrap(), expected);
+        assert_eq!(
+            merged.to_string(),
+            "OneOf[Boolean | Array<OneOf[Boolean | Integer<unsigned> | String]> | Object{a?: Integer<unsigned>, b?: Integer<unsigned>}]"
+        );
     }
 }
@@ -1,15 +1,44 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
 
 use crate::{
     error::Error,
     lexer::Token,
+    options::InferenceOptions,
     parser::{Cst, Node, NodeRef, Rule},
-    value::Value,
+    shape::{
+        merger::{detect_maps, limit_examples, limit_literals, merge, normalize_joined},
+        recursion::detect_recursion,
+    },
+    value::{Examples, LengthRange, LiteralValue, NumberKind, NumberRange, StringFormat, Value},
 };
 
+pub(crate) mod meet;
 pub(crate) mod merger;
+pub(crate) mod recursion;
 
+#[cfg(test)]
 pub fn parse_cst(cst: &Cst<'_>, source: &str) -> Result<Value, Error> {
+    parse_cst_with_options(cst, source, &InferenceOptions::default())
+}
+
+pub fn parse_cst_with_options(
+    cst: &Cst<'_>,
+    source: &str,
+    options: &InferenceOptions,
+) -> Result<Value, Error> {
+    let root = root_node(cst, source)?;
+    let known = options
+        .clone()
+        .with_tags(new_tags(cst, root, source, options));
+    let value = parse_rule(cst, root, source, &known)?;
+    Ok(apply_limits(value, options))
+}
+
+/// The single Json node of a file, checking the file for errors.
+pub(crate) fn root_node(cst: &Cst<'_>, source: &str) -> Result<NodeRef, Error> {
     let Node::Rule(Rule::File, _) = cst.get(NodeRef::ROOT) else {
         let span = cst.span(NodeRef::ROOT);
         let value = source[span.clone()].to_string();
@@ -38,18 +67,109 @@ pub fn parse_cst(cst: &Cst<'_>, source: &str) -> Result<Value, Error> {
         }
         return Err(Error::TooManyRootNodes(cst.children(NodeRef::ROOT).count()));
     }
-    let Some(first_node_ref) = cst.children(NodeRef::ROOT).find(|node_ref| {
-        !matches!(
-            cst.get(*node_ref),
-            Node::Token(Token::Whitespace | Token::Newline, _)
-        )
-    }) else {
-        let span = cst.span(NodeRef::ROOT);
-        let value = source[span.clone()].to_string();
-        return Err(Error::InvalidJson { value, span });
+    cst.children(NodeRef::ROOT)
+        .find(|node_ref| {
+            !matches!(
+                cst.get(*node_ref),
+                Node::Token(Token::Whitespace | Token::Newline, _)
+            )
+        })
+        .ok_or_else(|| {
+            let span = cst.span(NodeRef::ROOT);
+            let value = source[span.clone()].to_string();
+            Error::InvalidJson { value, span }
+        })
+}
+
+/// Infers the shape of the `root` node of a file, with the limits of the [`InferenceOptions`] applied.
+pub(crate) fn infer_root(
+    cst: &Cst<'_>,
+    root: NodeRef,
+    source: &str,
+    options: &InferenceOptions,
+) -> Result<Value, Error> {
+    Ok(apply_limits(
+        parse_rule(cst, root, source, options)?,
+        options,
+    ))
+}
+
+/// Tags discriminating objects of the `root` node that aren't tags of the [`InferenceOptions`] yet,
+/// when discriminators are enabled.
+pub(crate) fn new_tags(
+    cst: &Cst<'_>,
+    root: NodeRef,
+    source: &str,
+    options: &InferenceOptions,
+) -> BTreeSet<String> {
+    let mut tags = BTreeSet::new();
+    if options.discriminators {
+        discover_tags(cst, root, source, options, &mut tags);
+    }
+    tags.retain(|tag| !options.tags.contains(tag));
+    tags
+}
+
+/// Applies the [`InferenceOptions`] limits to an inferred shape, returning it in its canonical form.
+pub(crate) fn apply_limits(value: Value, options: &InferenceOptions) -> Value {
+    let value = match options.map_threshold {
+        Some(threshold) => detect_maps(value, threshold),
+        None => value,
+    };
+    let value = if options.recursive {
+        detect_recursion(value)
+    } else {
+        value
     };
+    let value = match options.literal_limit {
+        Some(limit) => limit_literals(value, limit, options),
+        None if options.discriminators => limit_literals(value, 0, options),
+        None => value,
+    };
+    let value = match options.example_limit {
+        Some(limit) => limit_examples(value, limit),
+        None => value,
+    };
+    normalize_joined(value)
+}
 
-    parse_rule(cst, first_node_ref, source)
+/// Infers the shape of a JSON string, as a [`Value::Literal`] or with its [`LengthRange`] when enabled by the [`InferenceOptions`].
+pub(crate) fn string_shape(value: &str, options: &InferenceOptions) -> Value {
+    if options.literal_limit.is_some() {
+        Value::Literal {
+            values: [LiteralValue::String(value.to_string())].into(),
+            optional: false,
+        }
+    } else {
+        Value::String {
+            optional: false,
+            format: StringFormat::detect(value),
+            length: options
+                .length_bounds
+                .then(|| LengthRange::new(value.chars().count())),
+            examples: options
+                .example_limit
+                .map(|_| Box::new(Examples::new(&serde_json::Value::from(value)))),
+        }
+    }
+}
+
+/// Infers the shape of a JSON number, as a [`Value::Literal`] for `i64` integers or with its [`NumberRange`] when enabled by the [`InferenceOptions`].
+pub(crate) fn number_shape(number: &serde_json::Number, options: &InferenceOptions) -> Value {
+    match number.as_i64() {
+        Some(value) if options.literal_limit.is_some() => Value::Literal {
+            values: [LiteralValue::Integer(value)].into(),
+            optional: false,
+        },
+        _ => Value::Number {
+            optional: false,
+            kind: NumberKind::from(number),
+            range: options.number_ranges.then(|| NumberRange::new(number)),
+            examples: options
+                .example_limit
+                .map(|_| Box::new(Examples::new(&serde_json::Value::Number(number.clone())))),
+        },
+    }
 }
 
 fn has_errors(cst: &Cst<'_>, source: &str, root: NodeRef) -> Result<(), Error> {
@@ -72,7 +192,12 @@ fn has_errors(cst: &Cst<'_>, source: &str, root: NodeRef) -> Result<(), Error> {
 }
 
 #[allow(clippy::too_many_lines)]
-fn parse_rule(cst: &Cst<'_>, node_ref: NodeRef, source: &str) -> Result<Value, Error> {
+fn parse_rule(
+    cst: &Cst<'_>,
+    node_ref: NodeRef,
+    source: &str,
+    options: &InferenceOptions,
+) -> Result<Value, Error> {
     match cst.get(node_ref) {
         Node::Rule(Rule::Literal, ..) => {
             has_errors(cst, source, node_ref)?;
@@ -81,12 +206,15 @@ fn parse_rule(cst: &Cst<'_>, node_ref: NodeRef, source: &str) -> Result<Value, E
                 cst.children(node_ref)
                     .next()
                     .ok_or_else(|| Error::InvalidType("Empty".to_string()))?,
+                source,
+                options,
             )
         }
         Node::Rule(Rule::Boolean, ..) => Ok(Value::Bool { optional: false }),
         Node::Rule(Rule::Array, ..) => {
             has_errors(cst, source, node_ref)?;
             let mut elements = Vec::new();
+            let mut discriminants = Vec::new();
             for sub_node in cst.children(node_ref).filter(|node_ref| {
                 !matches!(
                     cst.get(*node_ref),
@@ -100,68 +228,14 @@ fn parse_rule(cst: &Cst<'_>, node_ref: NodeRef, source: &str) -> Result<Value, E
                     )
                 )
             }) {
-                let shape = parse_rule(cst, sub_node, source)?;
+                let shape = parse_rule(cst, sub_node, source, options)?;
+                if options.discriminators {
+                    discriminants.push(string_members(cst, sub_node, source));
+                }
                 elements.push(shape);
             }
 
-            if elements.len() == 1 || elements.windows(2).all(|w| w[0] == w[1]) {
-                Ok(Value::Array {
-                    r#type: Box::new(elements.first().cloned().unwrap()),
-                    optional: false,
-                })
-            } else if elements.len() > 1
-                && elements
-                    .iter()
-                    .all(|value| matches!(value, Value::Object { .. }))
-            {
-                let mut iter = elements.iter();
-                let Some(Value::Object { content, .. }) = iter.next().cloned() else {
-                    return Err(Error::Unknown);
-                };
-                let content =
-                    iter.clone()
-                        .filter_map(Value::keys)
-                        .fold(content, |mut acc, mut keys| {
-                            for (key, value) in &mut acc {
-                                if !keys.any(|k| k == key) {
-                                    value.to_optional_mut();
-                                }
-                            }
-                            acc
-                        });
-                let object = iter.fold(content, |mut acc, content| {
-                    let Value::Object { content, .. } = content else {
-                        return acc;
-                    };
-                    for (key, value) in content {
-                        let old_value = acc
-                            .entry(key.clone())
-                            .or_insert_with(|| value.clone().as_optional());
-                        if let Value::OneOf { variants, .. } = old_value {
-                            variants.insert(value.clone());
-                        }
-                    }
-                    acc
-                });
-
-                Ok(Value::Array {
-                    r#type: Box::new(Value::Object {
-                        content: object,
-                        optional: false,
-                    }),
-                    optional: false,
-                })
-            } else if elements.len() > 1 {
-                Ok(Value::Tuple {
-                    elements,
-                    optional: false,
-                })
-            } else {
-                Ok(Value::Array {
-                    r#type: Box::new(Value::Null),
-                    optional: true,
-                })
-            }
+            Ok(array_shape(elements, &discriminants, options))
         }
         Node::Rule(Rule::Object, ..) => {
             let mut content = BTreeMap::default();
@@ -170,12 +244,13 @@ fn parse_rule(cst: &Cst<'_>, node_ref: NodeRef, source: &str) -> Result<Value, E
                 .children(node_ref)
                 .filter(|node_ref| matches!(cst.get(*node_ref), Node::Rule(Rule::Member, _)))
             {
-                parse_member(cst, sub_node, source, &mut content)?;
+                parse_member(cst, sub_node, source, options, &mut content)?;
             }
 
             Ok(Value::Object {
                 content,
                 optional: false,
+                absent: Some(BTreeSet::new()),
             })
         }
         _ => {
@@ -186,15 +261,278 @@ fn parse_rule(cst: &Cst<'_>, node_ref: NodeRef, source: &str) -> Result<Value, E
     }
 }
 
-fn parse_token(cst: &Cst<'_>, node_ref: NodeRef) -> Result<Value, Error> {
+/// Infers the shape of a JSON array from the shapes of its elements:
+/// - `[]` => `Array<Unknown>`
+/// - `[T, T]` => `Array<T>`, widening refinements, eg `[Integer, Number] => Array<Number>`
+/// - `[Object{a: T}, Object{b: U}]` => `Array<Object{a?: T, b?: U}>`
+/// - `[Object{type: "a", b: T}, Object{type: "c", d: U}]` => `Array<Tagged<type>["a": Object{b: T} | "c": Object{d: U}]>`,
+///   when discriminators are enabled by the [`InferenceOptions`]
+/// - `[T, U]` => `Tuple(T, U)`
+///
+/// `discriminants` are the string members of each element, used to detect [`Value::Tagged`] unions.
+/// Arrays record their [`LengthRange`] when enabled by the [`InferenceOptions`], while tuples are always of fixed length.
+pub(crate) fn array_shape(
+    elements: Vec<Value>,
+    discriminants: &[BTreeMap<String, String>],
+    options: &InferenceOptions,
+) -> Value {
+    let length = options
+        .length_bounds
+        .then(|| LengthRange::new(elements.len()));
+    let Some(first) = elements.first() else {
+        return Value::Array {
+            r#type: Box::new(Value::Unknown),
+            optional: false,
+            length,
+        };
+    };
+    if let Some(tagged) = tagged_shape(&elements, discriminants, options) {
+        return Value::Array {
+            r#type: Box::new(tagged),
+            optional: false,
+            length,
+        };
+    }
+
+    let is_homogeneous = elements.iter().all(|element| element.same_structure(first))
+        || elements.iter().all(Value::is_object);
+    match merge(&elements) {
+        Ok(r#type) if is_homogeneous => Value::Array {
+            r#type: Box::new(r#type),
+            optional: false,
+            length,
+        },
+        _ => Value::Tuple {
+            elements,
+            optional: false,
+        },
+    }
+}
+
+/// Infers a [`Value::Tagged`] union from objects sharing a key with constant string values per structure, see [`discriminator`].
+fn tagged_shape(
+    elements: &[Value],
+    discriminants: &[BTreeMap<String, String>],
+    options: &InferenceOptions,
+) -> Option<Value> {
+    let contents = elements
+        .iter()
+        .map(|element| match element {
+            Value::Object { content, .. } => Some(content),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let keys = contents
+        .iter()
+        .map(|content| content.keys().map(String::as_str).collect())
+        .collect::<Vec<_>>();
+    let (tag, groups) = discriminator(&keys, discriminants, options)?;
+
+    let variants = groups
+        .into_iter()
+        .map(|(value, indices)| {
+            let objects = indices
+                .into_iter()
+                .map(|index| {
+                    let mut content = contents[index].clone();
+                    content.remove(tag);
+                    Value::Object {
+                        content,
+                        optional: false,
+                        absent: Some(BTreeSet::new()),
+                    }
+                })
+                .collect::<Vec<_>>();
+            Some((value.to_string(), merge(&objects).ok()?))
+        })
+        .collect::<Option<BTreeMap<_, _>>>()?;
+    Some(Value::Tagged {
+        tag: tag.clone(),
+        variants,
+        optional: false,
+    })
+}
+
+/// Key discriminating objects, from the `keys` and the string members of each object, with the indices of the objects of each of its values.
+/// A key discriminates the objects when every object has a string value for it,
+/// and the objects with different values don't all share the same keys. The key with fewer distinct values is preferred.
+/// The tags of the [`InferenceOptions`] always discriminate the objects, and are preferred to other keys.
+pub(crate) fn discriminator<'a>(
+    keys: &[BTreeSet<&str>],
+    discriminants: &'a [BTreeMap<String, String>],
+    options: &InferenceOptions,
+) -> Option<(&'a String, BTreeMap<&'a str, Vec<usize>>)> {
+    if keys.len() < 2 || discriminants.len() != keys.len() {
+        return None;
+    }
+    discriminants[0]
+        .keys()
+        .filter(|key| {
+            discriminants
+                .iter()
+                .all(|members| members.contains_key(*key))
+        })
+        .filter_map(|tag| {
+            let mut groups = BTreeMap::<&str, Vec<usize>>::new();
+            for (index, members) in discriminants.iter().enumerate() {
+                groups.entry(&members[tag]).or_default().push(index);
+            }
+            let group_keys = groups
+                .values()
+                .map(|indices| {
+                    indices
+                        .iter()
+                        .flat_map(|index| keys[*index].iter().copied())
+                        .collect::<BTreeSet<_>>()
+                })
+                .collect::<BTreeSet<_>>();
+            let is_tag = options.tags.contains(tag.as_str());
+            (groups.len() > 1 && (group_keys.len() > 1 || is_tag)).then_some((tag, groups))
+        })
+        .min_by_key(|(tag, groups)| (!options.tags.contains(tag.as_str()), groups.len()))
+}
+
+/// Tags of the [`Value::Tagged`] unions inferred from a Json node, so that the values of the tags are known
+/// when objects are merged into their unions, see [`InferenceOptions::tags`].
+fn discover_tags(
+    cst: &Cst<'_>,
+    node_ref: NodeRef,
+    source: &str,
+    options: &InferenceOptions,
+    tags: &mut BTreeSet<String>,
+) {
+    let values = |node_ref| {
+        cst.children(node_ref).filter(|node_ref| {
+            matches!(
+                cst.get(*node_ref),
+                Node::Rule(Rule::Array | Rule::Object | Rule::Member, _)
+            )
+        })
+    };
+    match cst.get(node_ref) {
+        Node::Rule(Rule::Array, _) => {
+            let elements = values(node_ref).collect::<Vec<_>>();
+            let keys = elements
+                .iter()
+                .map(|element| object_keys(cst, *element, source))
+                .collect::<Option<Vec<_>>>();
+            if let Some(keys) = keys {
+                let keys = keys
+                    .iter()
+                    .map(|keys| keys.iter().map(String::as_str).collect())
+                    .collect::<Vec<_>>();
+                let discriminants = elements
+                    .iter()
+                    .map(|element| string_members(cst, *element, source))
+                    .collect::<Vec<_>>();
+                if let Some((tag, _)) = discriminator(&keys, &discriminants, options) {
+                    tags.insert(tag.clone());
+                }
+            }
+            for element in elements {
+                discover_tags(cst, element, source, options, tags);
+            }
+        }
+        Node::Rule(Rule::Object | Rule::Member, _) => {
+            for value in values(node_ref) {
+                discover_tags(cst, value, source, options, tags);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Keys of an object node, `None` for other nodes.
+fn object_keys(cst: &Cst<'_>, node_ref: NodeRef, source: &str) -> Option<BTreeSet<String>> {
+    if !matches!(cst.get(node_ref), Node::Rule(Rule::Object, _)) {
+        return None;
+    }
+    cst.children(node_ref)
+        .filter(|node_ref| matches!(cst.get(*node_ref), Node::Rule(Rule::Member, _)))
+        .map(|member| {
+            let key = cst
+                .children(member)
+                .find(|node_ref| matches!(cst.get(*node_ref), Node::Token(Token::String, _)))?;
+            parse_json_object_key(source, cst.span(key)).ok()
+        })
+        .collect()
+}
+
+/// String values of the members of an object node, eg `{"type": "click", "x": 1}` => `{type: "click"}`.
+fn string_members(cst: &Cst<'_>, node_ref: NodeRef, source: &str) -> BTreeMap<String, String> {
+    if !matches!(cst.get(node_ref), Node::Rule(Rule::Object, _)) {
+        return BTreeMap::default();
+    }
+    cst.children(node_ref)
+        .filter(|node_ref| matches!(cst.get(*node_ref), Node::Rule(Rule::Member, _)))
+        .filter_map(|member| {
+            let mut strings = cst
+                .children(member)
+                .filter(|node_ref| matches!(cst.get(*node_ref), Node::Token(Token::String, _)));
+            let key = parse_json_object_key(source, cst.span(strings.next()?)).ok()?;
+            let value = cst
+                .children(member)
+                .find(|node_ref| matches!(cst.get(*node_ref), Node::Rule(Rule::Literal, _)))?;
+            Some((key, string_value(cst, value, source)?))
+        })
+        .collect()
+}
+
+/// String value of a literal node, eg `"click"` => `click`.
+fn string_value(cst: &Cst<'_>, node_ref: NodeRef, source: &str) -> Option<String> {
+    let value = cst
+        .children(node_ref)
+        .next()
+        .filter(|node_ref| matches!(cst.get(*node_ref), Node::Token(Token::String, _)))?;
+    serde_json::from_str::<String>(&source[cst.span(value)]).ok()
+}
+
+/// Shape of the string value of a tag, known when validating Json against a [`Value::Tagged`] union, eg `Literal["click"]`.
+pub(crate) fn tag_shape(value: String) -> Value {
+    Value::Literal {
+        values: [LiteralValue::String(value)].into(),
+        optional: false,
+    }
+}
+
+fn parse_token(
+    cst: &Cst<'_>,
+    node_ref: NodeRef,
+    source: &str,
+    options: &InferenceOptions,
+) -> Result<Value, Error> {
     match cst.get(node_ref) {
         Node::Rule(Rule::Boolean, _) | Node::Token(Token::False | Token::True, _) => {
             Ok(Value::Bool { optional: false })
         }
         Node::Rule(..) => Err(Error::Unknown),
         Node::Token(Token::Null, _) => Ok(Value::Null),
-        Node::Token(Token::String, _) => Ok(Value::String { optional: false }),
-        Node::Token(Token::Number, _) => Ok(Value::Number { optional: false }),
+        Node::Token(Token::String, _) => {
+            let raw = &source[cst.span(node_ref)];
+            Ok(serde_json::from_str::<String>(raw).map_or(
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+                |value| string_shape(&value, options),
+            ))
+        }
+        Node::Token(Token::Number, _) => {
+            // Numbers out of the range of `f64`, eg `1e400`, aren't represented by `serde_json`
+            Ok(
+                serde_json::Number::from_str(&source[cst.span(node_ref)]).map_or(
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    |number| number_shape(&number, options),
+                ),
+            )
+        }
         Node::Token(token, _) => Err(Error::InvalidType(token.to_string())),
     }
 }
@@ -213,6 +551,7 @@ fn parse_member(
     cst: &Cst<'_>,
     sub_node: NodeRef,
     source: &str,
+    options: &InferenceOptions,
     content: &mut BTreeMap<String, Value>,
 ) -> Result<(), Error> {
     let Some(key) = cst
@@ -237,22 +576,28 @@ fn parse_member(
         return Err(Error::InvalidObjectValue);
     };
 
-    let value = parse_rule(cst, member_value, source)?;
+    let value = match string_value(cst, member_value, source) {
+        Some(tag) if options.tags.contains(&key) => tag_shape(tag),
+        _ => parse_rule(cst, member_value, source, options)?,
+    };
     match content.get(&key) {
         Some(Value::OneOf { variants, .. }) => {
             if !variants.contains(&value) {
                 return Err(Error::InvalidObjectValueType(
-                    value,
-                    Value::OneOf {
+                    Box::new(value),
+                    Box::new(Value::OneOf {
                         variants: variants.clone(),
                         optional: false,
-                    },
+                    }),
                 ));
             }
         }
         Some(other) => {
-            if value != *other {
-                return Err(Error::InvalidObjectValueType(value, other.to_owned()));
+            if !value.same_structure(other) {
+                return Err(Error::InvalidObjectValueType(
+                    Box::new(value),
+                    Box::new(other.to_owned()),
+                ));
             }
         }
         None => {
@@ -267,6 +612,7 @@ mod tests {
     use crate::parser::Parser;
 
     use super::*;
+    use crate::value::NumberKind;
 
     #[test]
     fn parse_null() {
@@ -285,7 +631,224 @@ mod tests {
 
         let value = parse_cst(&cst, source).unwrap();
 
-        assert_eq!(value, Value::Number { optional: false });
+        assert_eq!(
+            value,
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Unsigned,
+                range: None,
+                examples: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_number_out_of_f64_range() {
+        let source = "1e400";
+        let cst = Parser::parse(source, &mut Vec::new());
+        let options = InferenceOptions::default()
+            .with_literal_limit(3)
+            .with_number_ranges()
+            .with_examples(3);
+
+        let value = parse_cst_with_options(&cst, source, &options).unwrap();
+
+        assert_eq!(
+            value,
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+        );
+        assert!(Value::from_str("1.5").unwrap().is_superset("1e400"));
+    }
+
+    #[test]
+    fn parse_integer_kinds() {
+        for (source, kind) in [
+            ("-123", NumberKind::Signed),
+            ("18446744073709551615", NumberKind::LargeUnsigned),
+            ("123.0", NumberKind::Float),
+            ("1e3", NumberKind::Float),
+        ] {
+            let cst = Parser::parse(source, &mut Vec::new());
+
+            let value = parse_cst(&cst, source).unwrap();
+
+            assert_eq!(
+                value,
+                Value::Number {
+                    optional: false,
+                    kind,
+                    range: None,
+                    examples: None,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn parse_array_of_integers_and_floats() {
+        let source = "[[1, -2], [3.5]]";
+        let cst = Parser::parse(source, &mut Vec::new());
+
+        let value = parse_cst(&cst, source).unwrap();
+
+        assert_eq!(
+            value,
+            Value::Array {
+                r#type: Box::new(Value::Array {
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
+                }),
+                optional: false,
+                length: None
+            }
+        );
+    }
+
+    #[test]
+    fn parse_formatted_strings() {
+        for (source, format) in [
+            ("\"2024-01-31T10:00:00Z\"", StringFormat::DateTime),
+            ("\"2024-01-31\"", StringFormat::Date),
+            (
+                "\"67e55044-10b1-426f-9247-bb680e5fe0c8\"",
+                StringFormat::Uuid,
+            ),
+            ("\"naomi@example.com\"", StringFormat::Email),
+            ("\"https:\\/\\/example.com\"", StringFormat::Uri),
+            ("\"10.0.0.1\"", StringFormat::Ipv4),
+        ] {
+            let cst = Parser::parse(source, &mut Vec::new());
+
+            let value = parse_cst(&cst, source).unwrap();
+
+            assert_eq!(
+                value,
+                Value::String {
+                    optional: false,
+                    format: Some(format),
+                    length: None,
+                    examples: None
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn parse_with_literal_limit() {
+        let source = r#"[{"status": "active", "code": 1}, {"status": "disabled", "code": -1}, {"status": "active", "code": 2}]"#;
+        let cst = Parser::parse(source, &mut Vec::new());
+        let options = InferenceOptions::default().with_literal_limit(2);
+
+        let value = parse_cst_with_options(&cst, source, &options).unwrap();
+
+        assert_eq!(
+            value.to_string(),
+            r#"Array<Object{code: Integer<signed>, status: Literal["active" | "disabled"]}>"#
+        );
+    }
+
+    #[test]
+    fn parse_with_number_ranges() {
+        let source = r#"[{"age": 30, "score": 0.5}, {"age": 120, "score": -2}]"#;
+        let cst = Parser::parse(source, &mut Vec::new());
+        let options = InferenceOptions::default().with_number_ranges();
+
+        let value = parse_cst_with_options(&cst, source, &options).unwrap();
+
+        assert_eq!(
+            value.to_string(),
+            "Array<Object{age: Integer<unsigned>[30..=120], score: Number[-2..=0.5]}>"
+        );
+    }
+
+    #[test]
+    fn parse_with_length_bounds() {
+        let source = r#"{"ids": ["ab", "cde"], "pair": [1, "x"]}"#;
+        let cst = Parser::parse(source, &mut Vec::new());
+        let options = InferenceOptions::default().with_length_bounds();
+
+        let value = parse_cst_with_options(&cst, source, &options).unwrap();
+
+        assert_eq!(
+            value.to_string(),
+            "Object{ids: Array<String[2..=3]>[2..=2], pair: Tuple(Integer<unsigned>, String[1..=1])}"
+        );
+    }
+
+    #[test]
+    fn parse_with_literal_limit_keeps_refinements_of_widened_literals() {
+        let source =
+            r#"{"codes": [1, 5, 9, 12], "names": ["a", "bb", "ccc"], "kinds": ["x", "y"]}"#;
+        let cst = Parser::parse(source, &mut Vec::new());
+        let options = InferenceOptions::default()
+            .with_literal_limit(2)
+            .with_number_ranges()
+            .with_length_bounds()
+            .with_examples(3);
+
+        let value = parse_cst_with_options(&cst, source, &options).unwrap();
+
+        assert_eq!(
+            value.to_string(),
+            r#"Object{codes: Array<Integer<unsigned>[1..=12]>[4..=4], kinds: Array<Literal["x" | "y"]>[2..=2], names: Array<String[1..=3]>[3..=3]}"#
+        );
+        let Value::Object { content, .. } = &value else {
+            panic!("expected Object, got {value}");
+        };
+        let examples = |key: &str| match &content[key] {
+            Value::Array { r#type, .. } => match r#type.as_ref() {
+                Value::Number { examples, .. } | Value::String { examples, .. } => {
+                    examples.as_ref().unwrap().iter().collect::<Vec<_>>()
+                }
+                shape => panic!("expected Number or String, got {shape}"),
+            },
+            shape => panic!("expected Array, got {shape}"),
+        };
+        assert_eq!(examples("codes").len(), 3);
+        assert_eq!(examples("names"), ["\"a\"", "\"bb\"", "\"ccc\""]);
+    }
+
+    #[test]
+    fn parse_with_discriminators() {
+        let source = r#"[
+            {"type": "click", "x": 1, "y": 2},
+            {"type": "scroll", "delta": 1.5},
+            {"type": "click", "x": 3, "y": 4, "button": "left"}
+        ]"#;
+        let cst = Parser::parse(source, &mut Vec::new());
+        let options = InferenceOptions::default().with_discriminators();
+
+        let value = parse_cst_with_options(&cst, source, &options).unwrap();
+
+        assert_eq!(
+            value.to_string(),
+            r#"Array<Tagged<type>["click": Object{button?: String, x: Integer<unsigned>, y: Integer<unsigned>} | "scroll": Object{delta: Number}]>"#
+        );
+    }
+
+    #[test]
+    fn parse_with_discriminators_merges_objects_with_same_keys() {
+        let source = r#"[{"type": "a", "id": 1}, {"type": "b", "id": 2}]"#;
+        let cst = Parser::parse(source, &mut Vec::new());
+        let options = InferenceOptions::default().with_discriminators();
+
+        let value = parse_cst_with_options(&cst, source, &options).unwrap();
+
+        assert_eq!(
+            value.to_string(),
+            "Array<Object{id: Integer<unsigned>, type: String}>"
+        );
     }
 
     #[test]
@@ -295,7 +858,15 @@ mod tests {
 
         let value = parse_cst(&cst, source).unwrap();
 
-        assert_eq!(value, Value::String { optional: false });
+        assert_eq!(
+            value,
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+        );
     }
 
     #[test]
@@ -318,8 +889,14 @@ mod tests {
         assert_eq!(
             value,
             Value::Array {
-                r#type: Box::new(Value::Number { optional: false }),
-                optional: false
+                r#type: Box::new(Value::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                }),
+                optional: false,
+                length: None
             }
         );
     }
@@ -335,7 +912,8 @@ mod tests {
             value,
             Value::Array {
                 r#type: Box::new(Value::Bool { optional: false }),
-                optional: false
+                optional: false,
+                length: None
             }
         );
     }
@@ -351,9 +929,19 @@ mod tests {
             value,
             Value::Tuple {
                 elements: vec![
-                    Value::Number { optional: false },
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Unsigned,
+                        range: None,
+                        examples: None,
+                    },
                     Value::Bool { optional: false },
-                    Value::String { optional: false }
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
                 ],
                 optional: false
             }
@@ -371,11 +959,20 @@ mod tests {
             value,
             Value::Object {
                 content: [
-                    ("key".to_string(), Value::Number { optional: false }),
+                    (
+                        "key".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Unsigned,
+                            range: None,
+                            examples: None,
+                        }
+                    ),
                     ("key2".to_string(), Value::Bool { optional: false })
                 ]
                 .into(),
-                optional: false
+                optional: false,
+                absent: Some(BTreeSet::new())
             }
         );
     }
@@ -392,14 +989,40 @@ mod tests {
             Value::Array {
                 r#type: Box::new(Value::Object {
                     content: [
-                        ("a".to_string(), Value::Number { optional: true }),
-                        ("b".to_string(), Value::Number { optional: true }),
-                        ("c".to_string(), Value::Number { optional: true })
+                        (
+                            "a".to_string(),
+                            Value::Number {
+                                optional: false,
+                                kind: NumberKind::Unsigned,
+                                range: None,
+                                examples: None,
+                            }
+                        ),
+                        (
+                            "b".to_string(),
+                            Value::Number {
+                                optional: false,
+                                kind: NumberKind::Unsigned,
+                                range: None,
+                                examples: None,
+                            }
+                        ),
+                        (
+                            "c".to_string(),
+                            Value::Number {
+                                optional: false,
+                                kind: NumberKind::Unsigned,
+                                range: None,
+                                examples: None,
+                            }
+                        )
                     ]
                     .into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(["a", "b", "c"].map(String::from).into())
                 }),
-                optional: false
+                optional: false,
+                length: None
             }
         );
     }
@@ -415,10 +1038,21 @@ mod tests {
             value,
             Value::Array {
                 r#type: Box::new(Value::Object {
-                    content: [("a".to_string(), Value::Number { optional: false })].into(),
-                    optional: false
+                    content: [(
+                        "a".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Unsigned,
+                            range: None,
+                            examples: None,
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }),
-                optional: false
+                optional: false,
+                length: None
             }
         );
     }
@@ -435,14 +1069,40 @@ mod tests {
             Value::Array {
                 r#type: Box::new(Value::Object {
                     content: [
-                        ("a".to_string(), Value::Number { optional: false }),
-                        ("b".to_string(), Value::Number { optional: true }),
-                        ("c".to_string(), Value::Number { optional: true })
+                        (
+                            "a".to_string(),
+                            Value::Number {
+                                optional: false,
+                                kind: NumberKind::Unsigned,
+                                range: None,
+                                examples: None,
+                            }
+                        ),
+                        (
+                            "b".to_string(),
+                            Value::Number {
+                                optional: false,
+                                kind: NumberKind::Unsigned,
+                                range: None,
+                                examples: None,
+                            }
+                        ),
+                        (
+                            "c".to_string(),
+                            Value::Number {
+                                optional: false,
+                                kind: NumberKind::Unsigned,
+                                range: None,
+                                examples: None,
+                            }
+                        )
                     ]
                     .into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(["b", "c"].map(String::from).into())
                 }),
-                optional: false
+                optional: false,
+                length: None
             }
         );
     }
@@ -459,9 +1119,11 @@ mod tests {
             Value::Array {
                 r#type: Box::new(Value::Object {
                     content: BTreeMap::default(),
-                    optional: false
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }),
-                optional: false
+                optional: false,
+                length: None
             }
         );
     }
@@ -477,9 +1139,33 @@ mod tests {
             value,
             Value::Object {
                 content: [
-                    ("\\u0000".to_string(), Value::String { optional: false }),
-                    ("a\\nb".to_string(), Value::Number { optional: false }),
-                    ("a\\u0041".to_string(), Value::Number { optional: false }),
+                    (
+                        "\\u0000".to_string(),
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None
+                        }
+                    ),
+                    (
+                        "a\\nb".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Unsigned,
+                            range: None,
+                            examples: None,
+                        }
+                    ),
+                    (
+                        "a\\u0041".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Unsigned,
+                            range: None,
+                            examples: None,
+                        }
+                    ),
                     (
                         "quoted_key\\\"x\\\"".to_string(),
                         Value::Bool { optional: false }
@@ -487,7 +1173,8 @@ mod tests {
                 ]
                 .into_iter()
                 .collect(),
-                optional: false
+                optional: false,
+                absent: Some(BTreeSet::new())
             }
         );
     }
@@ -0,0 +1,407 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    shape::merger::merge,
+    statistics::key_path,
+    value::Value,
+    visitor::{ShapeVisitorMut, walk_shape_mut},
+};
+
+/// Replaces every [`Value::Object`] nested inside itself by a [`Value::Recursive`] definition,
+/// with the nested copies replaced by a [`Value::Ref`] to it.
+/// - `Object{body: String, replies: Array<Object{body: String, replies: Array<Null>}>}` => `Recursive<Root: Object{body: String, replies: Array<Option<Ref<Root>>>}>`
+///
+/// A field is a recursive edge when it leads, through arrays, tuples, maps and `OneOf`s, to an object
+/// containing the same field and no other keys than its parent. Every object reached through these edges is merged into the definition.
+pub(crate) fn detect_recursion(value: Value) -> Value {
+    let names = value.fold(BTreeSet::default(), |mut names, shape, _| {
+        if let Value::Recursive { name, .. } = shape {
+            names.insert(name.clone());
+        }
+        names
+    });
+    Detector {
+        names,
+        hint: String::new(),
+    }
+    .detect(value, "Root")
+}
+
+/// Replaces every [`Value::Ref`] to `name` in `shape` by its definition, unfolding the recursion once.
+pub(crate) fn unfold(name: &str, shape: &Value) -> Value {
+    substitute(shape.clone(), name, &|optional| {
+        let definition = Value::Recursive {
+            name: name.to_string(),
+            shape: Box::new(shape.clone()),
+        };
+        if optional {
+            definition.as_optional()
+        } else {
+            definition.as_non_optional()
+        }
+    })
+}
+
+/// Renames every [`Value::Ref`] to `from` in `shape` as a reference to `to`.
+pub(crate) fn rename(shape: Value, from: &str, to: &str) -> Value {
+    substitute(shape, from, &|optional| Value::Ref {
+        name: to.to_string(),
+        optional,
+    })
+}
+
+fn substitute(mut value: Value, name: &str, definition: &impl Fn(bool) -> Value) -> Value {
+    Substitution { name, definition }.visit_shape_mut(&mut value, "$");
+    value
+}
+
+struct Substitution<'a, F> {
+    name: &'a str,
+    definition: &'a F,
+}
+
+impl<F: Fn(bool) -> Value> ShapeVisitorMut for Substitution<'_, F> {
+    fn visit_shape_mut(&mut self, shape: &mut Value, path: &str) {
+        match shape {
+            Value::Ref { name, optional } if name == self.name => {
+                *shape = (self.definition)(*optional);
+            }
+            // References inside a nested definition with the same name are shadowed by it
+            Value::Recursive { name, .. } if name == self.name => (),
+            _ => walk_shape_mut(self, shape, path),
+        }
+    }
+}
+
+struct Detector {
+    /// Names already used by a [`Value::Recursive`] definition
+    names: BTreeSet<String>,
+    /// Name given to the definitions found in the visited shape, from the key holding it
+    hint: String,
+}
+
+impl Detector {
+    fn detect(&mut self, mut value: Value, hint: &str) -> Value {
+        let hint = std::mem::replace(&mut self.hint, hint.to_string());
+        self.visit_shape_mut(&mut value, "$");
+        self.hint = hint;
+        value
+    }
+
+    fn detect_content(&mut self, content: &mut BTreeMap<String, Value>) {
+        for (key, value) in content {
+            *value = self.detect(std::mem::replace(value, Value::Unknown), key);
+        }
+    }
+
+    /// Folds every object reached through the recursive edges of `shape` into a single definition.
+    fn define(&mut self, name: String, shape: Value) -> Value {
+        let Value::Object {
+            content,
+            optional,
+            absent,
+        } = shape
+        else {
+            return Value::Recursive {
+                shape: Box::new(self.detect(shape, &name)),
+                name,
+            };
+        };
+        let keys = content.keys().cloned().collect::<BTreeSet<_>>();
+        let edges = content
+            .iter()
+            .filter(|(key, value)| is_edge(Some(&name), key, value, &keys))
+            .map(|(key, _)| key.clone())
+            .collect::<BTreeSet<_>>();
+        let mut folder = Folder {
+            name: &name,
+            keys: &keys,
+            edges: &edges,
+            pending: vec![Value::Object {
+                content: content.clone(),
+                optional: false,
+                absent: absent.clone(),
+            }],
+        };
+
+        let mut occurrences = Vec::new();
+        while let Some(Value::Object {
+            mut content,
+            absent,
+            ..
+        }) = folder.pending.pop()
+        {
+            folder.fold(&mut content);
+            occurrences.push(Value::Object {
+                content,
+                optional: false,
+                absent,
+            });
+        }
+        let Ok(Value::Object {
+            content, absent, ..
+        }) = merge(&occurrences)
+        else {
+            return Value::Recursive {
+                name,
+                shape: Box::new(Value::Object {
+                    content,
+                    optional,
+                    absent,
+                }),
+            };
+        };
+
+        let content = content
+            .into_iter()
+            .map(|(key, value)| {
+                let value = self.detect(value, &key);
+                (key, value)
+            })
+            .collect();
+        Value::Recursive {
+            name,
+            shape: Box::new(Value::Object {
+                content,
+                optional,
+                absent,
+            }),
+        }
+    }
+
+    /// `hint` as a `PascalCase` name not used by any other definition, eg `file_tree` => `FileTree`.
+    fn unique_name(&mut self, hint: &str) -> String {
+        let mut base = hint
+            .split(|char: char| !char.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect::<String>();
+        if !base.starts_with(|char: char| char.is_ascii_alphabetic()) {
+            base.insert_str(0, "Node");
+        }
+        let mut name = base.clone();
+        let mut index = 1;
+        while self.names.contains(&name) {
+            index += 1;
+            name = format!("{base}{index}");
+        }
+        self.names.insert(name.clone());
+        name
+    }
+}
+
+impl ShapeVisitorMut for Detector {
+    fn visit_shape_mut(&mut self, shape: &mut Value, path: &str) {
+        match shape {
+            Value::Object { .. } if has_edges(shape) => {
+                let name = self.unique_name(&self.hint.clone());
+                let object = std::mem::replace(shape, Value::Unknown);
+                *shape = self.define(name, object);
+            }
+            Value::Object { content, .. } => self.detect_content(content),
+            Value::Recursive {
+                name,
+                shape: definition,
+            } => {
+                let name = std::mem::take(name);
+                let definition = std::mem::replace(&mut **definition, Value::Unknown);
+                *shape = self.define(name, definition);
+            }
+            // The variants of tagged unions stay objects, with the tag values naming the other variants
+            Value::Tagged { variants, .. } => {
+                for (value, variant) in variants {
+                    match variant {
+                        Value::Object { content, .. } => self.detect_content(content),
+                        variant => {
+                            *variant =
+                                self.detect(std::mem::replace(variant, Value::Unknown), value);
+                        }
+                    }
+                }
+            }
+            _ => walk_shape_mut(self, shape, path),
+        }
+    }
+}
+
+/// `object` contains itself through one of its keys.
+fn has_edges(object: &Value) -> bool {
+    let Value::Object { content, .. } = object else {
+        return false;
+    };
+    let keys = content.keys().cloned().collect::<BTreeSet<_>>();
+    content
+        .iter()
+        .any(|(key, value)| is_edge(None, key, value, &keys))
+}
+
+/// Replaces the objects reached through recursive `edges` by a [`Value::Ref`] to `name`,
+/// keeping them `pending` to be folded in turn.
+struct Folder<'a> {
+    name: &'a str,
+    keys: &'a BTreeSet<String>,
+    edges: &'a BTreeSet<String>,
+    pending: Vec<Value>,
+}
+
+impl Folder<'_> {
+    fn fold(&mut self, content: &mut BTreeMap<String, Value>) {
+        for (key, value) in content {
+            if self.edges.contains(key) {
+                self.visit_shape_mut(value, &key_path("$", key));
+            }
+        }
+    }
+}
+
+impl ShapeVisitorMut for Folder<'_> {
+    fn visit_shape_mut(&mut self, shape: &mut Value, path: &str) {
+        match shape {
+            Value::Object {
+                content, optional, ..
+            } if is_occurrence(content, self.keys) => {
+                let reference = Value::Ref {
+                    name: self.name.to_string(),
+                    optional: *optional,
+                };
+                let occurrence = std::mem::replace(shape, reference);
+                self.pending.push(occurrence.as_non_optional());
+            }
+            Value::Recursive {
+                name,
+                shape: definition,
+            } if name == self.name
+                && matches!(&**definition, Value::Object { content, .. } if is_occurrence(content, self.keys)) =>
+            {
+                let reference = Value::Ref {
+                    name: name.clone(),
+                    optional: definition.is_optional(),
+                };
+                let occurrence = std::mem::replace(&mut **definition, Value::Unknown);
+                *shape = reference;
+                self.pending.push(occurrence.as_non_optional());
+            }
+            Value::Array { .. } | Value::Tuple { .. } | Value::Map { .. } => {
+                walk_shape_mut(self, shape, path);
+            }
+            Value::OneOf { .. } => {
+                walk_shape_mut(self, shape, path);
+                if let Value::OneOf { variants, optional } = shape
+                    && variants.iter().any(Value::is_ref)
+                    && let Ok(merged) = merge(&variants.iter().cloned().collect::<Vec<_>>())
+                {
+                    *shape = if *optional {
+                        merged.as_optional()
+                    } else {
+                        merged
+                    };
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Checks if `value`, the value of `key` in an object with `keys`, leads to the same object again,
+/// or to a [`Value::Ref`] to the definition `name`.
+fn is_edge(name: Option<&str>, key: &str, value: &Value, keys: &BTreeSet<String>) -> bool {
+    match value {
+        Value::Object { content, .. } => content.contains_key(key) && is_occurrence(content, keys),
+        Value::Ref {
+            name: reference, ..
+        } => name == Some(reference.as_str()),
+        Value::Recursive {
+            name: reference,
+            shape,
+        } => name == Some(reference.as_str()) || is_edge(name, key, shape, keys),
+        Value::Array { r#type, .. } | Value::Map { r#type, .. } => is_edge(name, key, r#type, keys),
+        Value::OneOf { variants, .. } => variants
+            .iter()
+            .any(|variant| is_edge(name, key, variant, keys)),
+        Value::Tuple { elements, .. } => elements
+            .iter()
+            .any(|element| is_edge(name, key, element, keys)),
+        _ => false,
+    }
+}
+
+/// Objects with no other keys than the definition are occurrences of it.
+fn is_occurrence(content: &BTreeMap<String, Value>, keys: &BTreeSet<String>) -> bool {
+    !content.is_empty() && content.keys().all(|key| keys.contains(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn detect(source: &str) -> String {
+        detect_recursion(Value::from_str(source).unwrap()).to_string()
+    }
+
+    #[test]
+    fn detect_recursion_folds_nested_objects_into_definition() {
+        assert_eq!(
+            detect(
+                r#"{"body": "a", "replies": [{"body": "b", "replies": [{"body": "c", "replies": [1]}]}]}"#
+            ),
+            "Recursive<Root: Object{body: String, replies: Array<OneOf[Integer<unsigned> | Ref<Root>]>}>"
+        );
+        assert_eq!(
+            detect(r#"{"tree": {"value": 1, "left": {"value": 2, "left": null}}}"#),
+            "Object{tree: Recursive<Tree: Object{left: Option<Ref<Tree>>, value: Integer<unsigned>}>}"
+        );
+    }
+
+    #[test]
+    fn detect_recursion_ignores_nested_objects_without_the_edge() {
+        assert_eq!(
+            detect(r#"{"name": "a", "address": {"name": "b"}}"#),
+            "Object{address: Object{name: String}, name: String}"
+        );
+    }
+
+    #[test]
+    fn detect_recursion_gives_unique_names() {
+        assert_eq!(
+            detect(
+                r#"{"a": {"node": {"id": 1, "node": {"id": 2, "node": null}}}, "b": {"node": {"name": "x", "node": {"name": "y", "node": null}}}}"#
+            ),
+            "Object{a: Object{node: Recursive<Node: Object{id: Integer<unsigned>, node: Option<Ref<Node>>}>}, b: Object{node: Recursive<Node2: Object{name: String, node: Option<Ref<Node2>>}>}}"
+        );
+    }
+
+    #[test]
+    fn detect_recursion_refolds_merged_definitions() {
+        let recursive = detect_recursion(
+            Value::from_str(r#"{"id": 1, "next": {"id": 2, "next": null}}"#).unwrap(),
+        );
+        let shallow = Value::from_str(r#"{"id": 1, "next": {"id": 2}}"#).unwrap();
+
+        assert_eq!(
+            detect_recursion(merge(&[recursive, shallow]).unwrap()).to_string(),
+            "Recursive<Root: Object{id: Integer<unsigned>, next?: Option<Ref<Root>>}>"
+        );
+    }
+
+    #[test]
+    fn unfold_replaces_references_with_definition() {
+        let Value::Recursive { name, shape } =
+            detect_recursion(Value::from_str(r#"{"next": {"next": null}}"#).unwrap())
+        else {
+            panic!("expected a recursive shape");
+        };
+
+        assert_eq!(
+            unfold(&name, &shape).to_string(),
+            "Object{next: Recursive<Root: Option<Object{next: Option<Ref<Root>>}>>}"
+        );
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    InferenceOptions,
+    error::Error,
+    shape::{apply_limits, merger::merger},
+    value::Value,
+};
+
+/// [`JsonShape`](crate::JsonShape) inferred from multiple samples, with how often each of its paths was seen.
+///
+/// Paths are written as `JSONPath` queries, with `[*]` for array items and `*` for the keys of a [`JsonShape::Map`](crate::JsonShape::Map),
+/// eg `$.users[*].age` or `$.scores.*`.
+///
+/// ```rust
+/// use json_shape::{InferenceOptions, ShapeStatistics};
+///
+/// let sources = [
+///     r#"{"id": 1, "name": "lorem"}"#.to_string(),
+///     r#"{"id": 2, "name": null}"#.to_string(),
+///     r#"{"id": 3}"#.to_string(),
+/// ];
+/// let statistics = ShapeStatistics::from_sources(&sources, &InferenceOptions::default()).unwrap();
+///
+/// assert_eq!(statistics.shape.to_string(), "Object{id: Integer<unsigned>, name?: Option<String>}");
+/// assert_eq!(statistics.samples, 3);
+/// assert!(statistics.paths["$.id"].is_required());
+/// assert_eq!(statistics.paths["$.name"].present, 2);
+/// assert_eq!(statistics.paths["$.name"].null, 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShapeStatistics {
+    /// Shape inferred from all samples
+    pub shape: Value,
+    /// Number of samples the shape was inferred from
+    pub samples: usize,
+    /// Statistics of every path reached by the samples, eg `$.users[*].age`
+    pub paths: BTreeMap<String, PathStatistics>,
+}
+
+/// How often a path of a [`ShapeStatistics`] was seen, and with which types.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathStatistics {
+    /// Number of values that could contain the path: samples for `$`, objects for their keys and items for `[*]`
+    pub samples: usize,
+    /// Number of times the path was present
+    pub present: usize,
+    /// Number of times the path was present with a `null` value
+    pub null: usize,
+    /// Number of times the path was present with each non-null type, eg `Boolean` or `Object`
+    pub types: BTreeMap<String, usize>,
+}
+
+impl PathStatistics {
+    /// Ratio of the samples in which the path was present, between `0.0` and `1.0`
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn presence(&self) -> f64 {
+        if self.samples == 0 {
+            0.0
+        } else {
+            self.present as f64 / self.samples as f64
+        }
+    }
+
+    /// The path was present in every sample
+    #[must_use]
+    pub const fn is_required(&self) -> bool {
+        self.present == self.samples
+    }
+
+    /// The path was present with a `null` value at least once
+    #[must_use]
+    pub const fn is_nullable(&self) -> bool {
+        self.null > 0
+    }
+}
+
+impl ShapeStatistics {
+    /// Infers the [`JsonShape`](crate::JsonShape) of multiple Json sources, collecting the statistics of its paths
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if failed to parse Json or if shapes don't align.
+    pub fn from_sources(sources: &[String], options: &InferenceOptions) -> Result<Self, Error> {
+        let shape = Value::from_sources_with_options(sources, options)?;
+        let values = sources
+            .iter()
+            .map(|source| {
+                serde_json::from_str(source).map_err(|error| invalid_json(source, &error))
+            })
+            .collect::<Result<Vec<serde_json::Value>, _>>()?;
+
+        Ok(Self::collect(shape, &values))
+    }
+
+    /// Infers the [`JsonShape`](crate::JsonShape) of multiple [`serde_json::Value`]s, collecting the statistics of its paths
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there are no values or if shapes don't align.
+    pub fn from_values(
+        values: &[serde_json::Value],
+        options: &InferenceOptions,
+    ) -> Result<Self, Error> {
+        let mut shape: Option<Value> = None;
+        for value in values {
+            let value = Value::from_value_with_options(value, options);
+            shape = Some(match shape {
+                Some(shape) => apply_limits(merger(shape, value)?, options),
+                None => value,
+            });
+        }
+        let shape = shape.ok_or(Error::EmptyFile)?;
+
+        Ok(Self::collect(shape, values))
+    }
+
+    fn collect(shape: Value, values: &[serde_json::Value]) -> Self {
+        let mut collector = Collector::default();
+        for value in values {
+            collector.visit(value, Some(&shape), "$");
+        }
+        let Collector {
+            mut paths, keys, ..
+        } = collector;
+        for (path, parent) in keys {
+            let objects = paths
+                .get(&parent)
+                .and_then(|parent| parent.types.get("Object"))
+                .copied()
+                .unwrap_or_default();
+            paths.entry(path).or_default().samples = objects;
+        }
+
+        Self {
+            shape,
+            samples: values.len(),
+            paths,
+        }
+    }
+}
+
+/// Walks the samples alongside their shape, so map keys and tuple elements get the same paths as the shape.
+#[derive(Default)]
+struct Collector<'a> {
+    paths: BTreeMap<String, PathStatistics>,
+    /// Paths of object keys and the path of their objects
+    keys: BTreeMap<String, String>,
+    definitions: BTreeMap<&'a str, &'a Value>,
+}
+
+impl<'a> Collector<'a> {
+    fn visit(&mut self, value: &serde_json::Value, shape: Option<&'a Value>, path: &str) {
+        let shape = shape.and_then(|shape| self.resolve(shape, value));
+        let statistics = self.paths.entry(path.to_string()).or_default();
+        statistics.samples += 1;
+        statistics.present += 1;
+        match type_name(value) {
+            Some(name) => *statistics.types.entry(name.to_string()).or_default() += 1,
+            None => statistics.null += 1,
+        }
+        match value {
+            serde_json::Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let (child, item_shape) = match shape {
+                        Some(Value::Tuple { elements, .. }) => {
+                            (format!("{path}[{index}]"), elements.get(index))
+                        }
+                        Some(Value::Array { r#type, .. }) => {
+                            (format!("{path}[*]"), Some(&**r#type))
+                        }
+                        _ => (format!("{path}[*]"), None),
+                    };
+                    self.visit(item, item_shape, &child);
+                }
+            }
+            serde_json::Value::Object(members) => {
+                for (key, member) in members {
+                    let (child, member_shape) = if let Some(Value::Map { r#type, .. }) = shape {
+                        (format!("{path}.*"), Some(&**r#type))
+                    } else {
+                        let child = key_path(path, key);
+                        self.keys.insert(child.clone(), path.to_string());
+                        (child, member_shape(shape, members, key))
+                    };
+                    self.visit(member, member_shape, &child);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Shape describing `value`, looking through recursive definitions and `OneOf` variants.
+    fn resolve(&mut self, shape: &'a Value, value: &serde_json::Value) -> Option<&'a Value> {
+        match shape {
+            Value::Recursive { name, shape } => {
+                self.definitions.insert(name, shape);
+                self.resolve(shape, value)
+            }
+            Value::Ref { name, .. } => {
+                let definition = self.definitions.get(name.as_str()).copied()?;
+                self.resolve(definition, value)
+            }
+            Value::OneOf { variants, .. } => variants
+                .iter()
+                .find(|variant| is_kind_of(variant, value))
+                .and_then(|variant| self.resolve(variant, value)),
+            shape => Some(shape),
+        }
+    }
+}
+
+/// Shape of the `key` member of an object described by `shape`, picking the variant of a [`Value::Tagged`] by its tag.
+fn member_shape<'a>(
+    shape: Option<&'a Value>,
+    members: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Option<&'a Value> {
+    match shape? {
+        Value::Object { content, .. } => content.get(key),
+        Value::Tagged { tag, variants, .. } => {
+            let variant = variants.get(members.get(tag)?.as_str()?)?;
+            match variant {
+                Value::Object { content, .. } => content.get(key),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Name of the Json type of `value`, `None` for `null`.
+const fn type_name(value: &serde_json::Value) -> Option<&'static str> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::Bool(_) => Some("Boolean"),
+        serde_json::Value::Number(_) => Some("Number"),
+        serde_json::Value::String(_) => Some("String"),
+        serde_json::Value::Array(_) => Some("Array"),
+        serde_json::Value::Object(_) => Some("Object"),
+    }
+}
+
+/// `shape` describes values of the same Json type as `value`.
+const fn is_kind_of(shape: &Value, value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => shape.is_null(),
+        serde_json::Value::Bool(_) => shape.is_boolean(),
+        serde_json::Value::Number(_) => shape.is_number(),
+        serde_json::Value::String(_) => {
+            matches!(shape, Value::String { .. } | Value::Literal { .. })
+        }
+        serde_json::Value::Array(_) => shape.is_array() || shape.is_tuple(),
+        serde_json::Value::Object(_) => {
+            shape.is_object()
+                || shape.is_map()
+                || shape.is_tagged()
+                || shape.is_recursive()
+                || shape.is_ref()
+        }
+    }
+}
+
+/// [`Error::InvalidJson`] with the token of `source` where `serde_json` failed,
+/// eg numbers out of the `f64` range which the CST accepts.
+fn invalid_json(source: &str, error: &serde_json::Error) -> Error {
+    let line_start = source
+        .split_inclusive('\n')
+        .take(error.line().saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>();
+    let mut position = (line_start + error.column()).min(source.len());
+    while !source.is_char_boundary(position) {
+        position -= 1;
+    }
+    let is_delimiter = |char: char| char.is_whitespace() || "[]{},:".contains(char);
+    let start = source[..position]
+        .rfind(is_delimiter)
+        .map_or(0, |index| index + 1);
+    let end = source[position..]
+        .find(is_delimiter)
+        .map_or(source.len(), |index| position + index);
+    Error::InvalidJson {
+        value: source[start..end].to_string(),
+        span: start..end,
+    }
+}
+
+/// `path.key` for keys that are valid `JSONPath` member names, `path['key']` otherwise.
+pub(crate) fn key_path(path: &str, key: &str) -> String {
+    let mut chars = key.chars();
+    let is_shorthand = chars
+        .next()
+        .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_');
+    if is_shorthand {
+        format!("{path}.{key}")
+    } else {
+        let key = key.replace('\\', "\\\\").replace('\'', "\\'");
+        format!("{path}['{key}']")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statistics(sources: &[&str], options: &InferenceOptions) -> ShapeStatistics {
+        let sources = sources.iter().map(ToString::to_string).collect::<Vec<_>>();
+        ShapeStatistics::from_sources(&sources, options).unwrap()
+    }
+
+    #[test]
+    fn counts_present_null_and_absent_keys() {
+        let statistics = statistics(
+            &[
+                r#"{"id": 1, "tags": ["a", "b"], "parent": null}"#,
+                r#"{"id": 2, "tags": [], "parent": 1}"#,
+                r#"{"id": 3, "tags": ["c"]}"#,
+                r#"{"id": 4, "tags": ["d"]}"#,
+            ],
+            &InferenceOptions::default(),
+        );
+
+        assert_eq!(statistics.samples, 4);
+        assert_eq!(
+            statistics.paths["$.parent"],
+            PathStatistics {
+                samples: 4,
+                present: 2,
+                null: 1,
+                types: [("Number".to_string(), 1)].into(),
+            }
+        );
+        assert!((statistics.paths["$.parent"].presence() - 0.5).abs() < f64::EPSILON);
+        assert!(statistics.paths["$.id"].is_required());
+        assert!(!statistics.paths["$.id"].is_nullable());
+        assert_eq!(statistics.paths["$.tags"].types["Array"], 4);
+        assert_eq!(statistics.paths["$.tags[*]"].present, 4);
+    }
+
+    #[test]
+    fn counts_one_of_variants_by_type() {
+        let statistics = statistics(
+            &[r#"{"value": 1}"#, r#"{"value": "1"}"#, r#"{"value": "2"}"#],
+            &InferenceOptions::default(),
+        );
+
+        assert_eq!(
+            statistics.shape.to_string(),
+            "Object{value: OneOf[Integer<unsigned> | String]}"
+        );
+        assert_eq!(
+            statistics.paths["$.value"].types,
+            [("Number".to_string(), 1), ("String".to_string(), 2)].into()
+        );
+    }
+
+    #[test]
+    fn counts_keys_of_objects_in_arrays_per_item() {
+        let statistics = statistics(
+            &[r#"[{"a": 1, "b": 2}, {"a": 3}]"#, r#"[{"a": 4}]"#],
+            &InferenceOptions::default(),
+        );
+
+        assert_eq!(statistics.paths["$[*]"].samples, 3);
+        assert_eq!(statistics.paths["$[*].a"].present, 3);
+        assert_eq!(statistics.paths["$[*].b"].samples, 3);
+        assert_eq!(statistics.paths["$[*].b"].present, 1);
+    }
+
+    #[test]
+    fn follows_the_shape_for_maps_and_tuples() {
+        let statistics = statistics(
+            &[r#"{"scores": {"1": [1, "a"], "2": [2, "b"]}, "key space": true}"#],
+            &InferenceOptions::default().with_map_threshold(2),
+        );
+
+        assert_eq!(
+            statistics.paths.keys().collect::<Vec<_>>(),
+            [
+                "$",
+                "$.scores",
+                "$.scores.*",
+                "$.scores.*[0]",
+                "$.scores.*[1]",
+                "$['key space']"
+            ]
+        );
+        assert_eq!(statistics.paths["$.scores.*"].samples, 2);
+        assert_eq!(statistics.paths["$.scores.*[1]"].types["String"], 2);
+    }
+
+    #[test]
+    fn from_sources_reports_the_json_serde_json_rejects() {
+        let sources = [
+            r#"{"a": 1}"#.to_string(),
+            "{\"a\":\n [1, -1e400]}".to_string(),
+        ];
+
+        assert_eq!(
+            ShapeStatistics::from_sources(&sources, &InferenceOptions::default()),
+            Err(Error::InvalidJson {
+                value: "-1e400".to_string(),
+                span: 11..17,
+            })
+        );
+    }
+
+    #[test]
+    fn from_values_without_values_is_an_error() {
+        assert_eq!(
+            ShapeStatistics::from_values(&[], &InferenceOptions::default()),
+            Err(Error::EmptyFile)
+        );
+    }
+}
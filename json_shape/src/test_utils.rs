@@ -0,0 +1,7 @@
+use crate::{InferenceOptions, value::Value};
+
+/// [`Value`] inferred from `sources` with `options`
+pub(crate) fn shape(sources: &[&str], options: &InferenceOptions) -> Value {
+    let sources = sources.iter().map(ToString::to_string).collect::<Vec<_>>();
+    Value::from_sources_with_options(&sources, options).unwrap()
+}
@@ -0,0 +1,547 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    ops::Range,
+};
+
+use codespan_reporting::{
+    diagnostic::{Diagnostic, Label},
+    files::SimpleFile,
+    term::{self, Config},
+};
+
+use crate::{
+    IsSubset,
+    lexer::Token,
+    options::InferenceOptions,
+    parser::{Cst, Node, NodeRef, Parser, Rule},
+    pointer::escape,
+    shape::{recursion::unfold, tag_shape},
+    value::{LiteralValue, Value, absent_keys},
+};
+
+/// Reason why a Json value doesn't match a [`JsonShape`](crate::JsonShape), located by its JSON Pointer and its range in the Json source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer of the invalid value, or of the missing or unexpected key, eg `/orders/0/price`
+    pub pointer: String,
+    /// Shape expected at `pointer`, `None` for unexpected keys
+    pub expected: Option<Value>,
+    /// Shape of the Json value found at `pointer`, `None` for missing keys and invalid Json
+    pub found: Option<Value>,
+    /// Range of the Json source where the error is located. Missing keys are located at their object, unexpected keys and unknown tags at their member.
+    pub span: Range<usize>,
+    /// Why the value doesn't match: `unexpected value`, `missing key`, `unexpected key`, `unknown tag` or why the Json is invalid
+    pub reason: String,
+}
+
+impl Value {
+    /// Validates a Json source against this [`JsonShape`](crate::JsonShape), as [`JsonShape::is_superset`](Self::is_superset),
+    /// explaining why it doesn't match with a [`ValidationError`] for each of the deepest mismatching values.
+    ///
+    /// - Objects report their missing and unexpected keys, and check their values against the shape of each key.
+    /// - Arrays, tuples and maps check each element, `Tagged` unions check the variant of their tag and report unknown tags,
+    ///   expecting a `Literal` of the known tags.
+    /// - `OneOf` values are checked against the only variant with the same structure, if any.
+    /// - Values without mismatching nested values are reported as a whole, eg arrays outside their `LengthRange`.
+    /// - Found shapes are refined with the ranges and lengths of the values, eg `Integer<unsigned>[2..=2]`.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use json_shape::JsonShape;
+    ///
+    /// let shape = JsonShape::from_str(r#"{"id": 1, "tags": ["a"]}"#).unwrap();
+    /// let errors = shape.validate(r#"{"tags": ["a", 2], "name": "x"}"#).unwrap_err();
+    /// let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     errors,
+    ///     [
+    ///         "unexpected value at `/tags/1`, expected `String`, found `Integer<unsigned>[2..=2]`: 15..16",
+    ///         "unexpected key at `/name`, found `String[1..=1]`: 19..30",
+    ///         "missing key at `/id`, expected `Integer<unsigned>`: 0..31",
+    ///     ]
+    /// );
+    /// assert!(shape.validate(r#"{"id": 2, "tags": []}"#).is_ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with every mismatch when the Json doesn't match this [`JsonShape`](crate::JsonShape),
+    /// or with a single error located at the invalid portion when failed to parse the Json.
+    pub fn validate(&self, json: &str) -> Result<(), Vec<ValidationError>> {
+        let options = InferenceOptions::validating(self);
+        let found = match Self::from_str_with_options(json, &options) {
+            Ok(found) => found,
+            Err(error) => {
+                return Err(vec![ValidationError {
+                    pointer: String::new(),
+                    expected: Some(self.clone()),
+                    found: None,
+                    span: error.span().unwrap_or(0..json.len()),
+                    reason: error.to_diagnostic().message,
+                }]);
+            }
+        };
+        if found.is_subset(self) {
+            return Ok(());
+        }
+
+        let mut diags = Vec::new();
+        let cst = Parser::parse(json, &mut diags);
+        let root = cst
+            .children(NodeRef::ROOT)
+            .find(|node_ref| matches!(cst.get(*node_ref), Node::Rule(..)))
+            .map_or(
+                Located {
+                    span: 0..json.len(),
+                    node: LocatedNode::Scalar,
+                },
+                |node_ref| Located::new(&cst, node_ref, json),
+            );
+        let mut validator = Validator {
+            source: json,
+            options,
+            errors: Vec::new(),
+        };
+        validator.check_found(self, &root, found, "");
+        Err(validator.errors)
+    }
+}
+
+impl ValidationError {
+    fn mismatch(pointer: &str, expected: &Value, found: Value, span: Range<usize>) -> Self {
+        Self {
+            pointer: pointer.to_string(),
+            expected: Some(expected.clone()),
+            found: Some(found),
+            span,
+            reason: "unexpected value".to_string(),
+        }
+    }
+
+    /// [`Diagnostic`] of this error, labelling its span with its pointer and noting the expected and found shapes,
+    /// to be emitted with [`codespan_reporting::term`].
+    #[must_use]
+    pub fn to_diagnostic(&self) -> Diagnostic<()> {
+        let notes = [("expected", &self.expected), ("found", &self.found)]
+            .into_iter()
+            .filter_map(|(name, shape)| Some(format!("{name}: {}", shape.as_ref()?)))
+            .collect();
+        Diagnostic::error()
+            .with_message(&self.reason)
+            .with_labels(vec![
+                Label::primary((), self.span.clone()).with_message(self.location()),
+            ])
+            .with_notes(notes)
+    }
+
+    /// Renders this error against the Json `source` it was raised for, named `name`, eg
+    /// ```text
+    /// error: unexpected value
+    ///   ┌─ payload.json:1:16
+    ///   │
+    /// 1 │ {"tags": ["a", 2]}
+    ///   │                ^ at `/tags/1`
+    ///   │
+    ///   = expected: String
+    ///   = found: Integer<unsigned>
+    /// ```
+    /// Falls back to the error message when the span is outside of `source`.
+    #[must_use]
+    pub fn render(&self, name: &str, source: &str) -> String {
+        let file = SimpleFile::new(name, source);
+        term::emit_into_string(&Config::default(), &file, &self.to_diagnostic())
+            .unwrap_or_else(|_| self.to_string())
+    }
+
+    fn location(&self) -> String {
+        if self.pointer.is_empty() {
+            "at the root".to_string()
+        } else {
+            format!("at `{}`", self.pointer)
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.reason, self.location())?;
+        if let Some(expected) = &self.expected {
+            write!(f, ", expected `{expected}`")?;
+        }
+        if let Some(found) = &self.found {
+            write!(f, ", found `{found}`")?;
+        }
+        write!(f, ": {}..{}", self.span.start, self.span.end)
+    }
+}
+
+/// Json value with the range of the source where it is located.
+struct Located {
+    span: Range<usize>,
+    node: LocatedNode,
+}
+
+enum LocatedNode {
+    Scalar,
+    Array(Vec<Located>),
+    Object(Vec<Member>),
+}
+
+/// Object member, located from its key to its value.
+struct Member {
+    key: String,
+    span: Range<usize>,
+    value: Located,
+}
+
+impl Located {
+    fn new(cst: &Cst<'_>, node_ref: NodeRef, source: &str) -> Self {
+        let values = |node_ref| {
+            cst.children(node_ref)
+                .filter(|node_ref| matches!(cst.get(*node_ref), Node::Rule(..)))
+        };
+        let node = match cst.get(node_ref) {
+            Node::Rule(Rule::Array, _) => LocatedNode::Array(
+                values(node_ref)
+                    .map(|element| Self::new(cst, element, source))
+                    .collect(),
+            ),
+            Node::Rule(Rule::Object, _) => LocatedNode::Object(
+                values(node_ref)
+                    .filter_map(|member| {
+                        let key = cst.children(member).find(|node_ref| {
+                            matches!(cst.get(*node_ref), Node::Token(Token::String, _))
+                        })?;
+                        let value = values(member).next()?;
+                        Some(Member {
+                            key: serde_json::from_str(&source[cst.span(key)]).ok()?,
+                            span: cst.span(key).start..cst.span(value).end,
+                            value: Self::new(cst, value, source),
+                        })
+                    })
+                    .collect(),
+            ),
+            _ => LocatedNode::Scalar,
+        };
+        Self {
+            span: cst.span(node_ref),
+            node,
+        }
+    }
+}
+
+struct Validator<'a> {
+    source: &'a str,
+    options: InferenceOptions,
+    errors: Vec<ValidationError>,
+}
+
+impl Validator<'_> {
+    /// Shape of a located value, inferred as [`JsonShape::is_superset`](crate::JsonShape::is_superset) does
+    fn shape(&self, located: &Located) -> Value {
+        Value::from_str_with_options(&self.source[located.span.clone()], &self.options)
+            .unwrap_or(Value::Unknown)
+    }
+
+    fn check(&mut self, expected: &Value, located: &Located, pointer: &str) {
+        let found = self.shape(located);
+        if !found.is_subset(expected) {
+            self.check_found(expected, located, found, pointer);
+        }
+    }
+
+    /// Reports the nested values that don't match, or the value itself when they all match
+    fn check_found(&mut self, expected: &Value, located: &Located, found: Value, pointer: &str) {
+        let errors = self.errors.len();
+        self.descend(expected, located, pointer);
+        if self.errors.len() == errors {
+            self.errors.push(ValidationError::mismatch(
+                pointer,
+                expected,
+                found,
+                located.span.clone(),
+            ));
+        }
+    }
+
+    fn descend(&mut self, expected: &Value, located: &Located, pointer: &str) {
+        match (expected, &located.node) {
+            (Value::Recursive { name, shape }, _) => {
+                self.descend(&unfold(name, shape), located, pointer);
+            }
+            (Value::OneOf { variants, .. }, node) => {
+                let mut candidates = variants
+                    .iter()
+                    .filter(|variant| same_structure(variant, node));
+                if let (Some(variant), None) = (candidates.next(), candidates.next()) {
+                    self.descend(variant, located, pointer);
+                }
+            }
+            (
+                Value::Object {
+                    content, absent, ..
+                },
+                LocatedNode::Object(members),
+            ) => {
+                let members = members.iter().collect::<Vec<_>>();
+                self.descend_object(content, absent.as_ref(), located, &members, pointer);
+            }
+            (Value::Tagged { tag, variants, .. }, LocatedNode::Object(members)) => {
+                let tags = Value::Literal {
+                    values: variants
+                        .keys()
+                        .map(|value| LiteralValue::String(value.clone()))
+                        .collect(),
+                    optional: false,
+                };
+                let Some(tag_member) = members.iter().find(|member| &member.key == tag) else {
+                    self.errors.push(ValidationError {
+                        pointer: child(pointer, tag),
+                        expected: Some(tags),
+                        found: None,
+                        span: located.span.clone(),
+                        reason: "missing key".to_string(),
+                    });
+                    return;
+                };
+                let value =
+                    serde_json::from_str::<String>(&self.source[tag_member.value.span.clone()])
+                        .ok();
+                let Some(variant) = value.as_ref().and_then(|value| variants.get(value)) else {
+                    match value {
+                        Some(value) => self.errors.push(ValidationError {
+                            pointer: child(pointer, tag),
+                            expected: Some(tags),
+                            found: Some(tag_shape(value)),
+                            span: tag_member.span.clone(),
+                            reason: "unknown tag".to_string(),
+                        }),
+                        None => self.check(&tags, &tag_member.value, &child(pointer, tag)),
+                    }
+                    return;
+                };
+                // Variants describe the object without its tag
+                if let Value::Object {
+                    content, absent, ..
+                } = variant
+                {
+                    let members = members
+                        .iter()
+                        .filter(|member| &member.key != tag)
+                        .collect::<Vec<_>>();
+                    self.descend_object(content, absent.as_ref(), located, &members, pointer);
+                }
+            }
+            (Value::Map { r#type, key, .. }, LocatedNode::Object(members)) => {
+                for member in members {
+                    let child = child(pointer, &member.key);
+                    if key.is_none_or(|key| key.matches(&member.key)) {
+                        self.check(r#type, &member.value, &child);
+                    } else {
+                        self.unexpected_key(member, child);
+                    }
+                }
+            }
+            (Value::Array { r#type, .. }, LocatedNode::Array(elements)) => {
+                for (index, element) in elements.iter().enumerate() {
+                    self.check(r#type, element, &format!("{pointer}/{index}"));
+                }
+            }
+            (Value::Tuple { elements, .. }, LocatedNode::Array(values))
+                if elements.len() == values.len() =>
+            {
+                for (index, (element, value)) in elements.iter().zip(values).enumerate() {
+                    self.check(element, value, &format!("{pointer}/{index}"));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn descend_object(
+        &mut self,
+        content: &BTreeMap<String, Value>,
+        absent: Option<&BTreeSet<String>>,
+        located: &Located,
+        members: &[&Member],
+        pointer: &str,
+    ) {
+        let absent = absent_keys(content, absent);
+        for member in members {
+            let child = child(pointer, &member.key);
+            match content.get(&member.key) {
+                Some(value) => self.check(value, &member.value, &child),
+                None => self.unexpected_key(member, child),
+            }
+        }
+        for (key, value) in content {
+            if !absent.contains(key) && members.iter().all(|member| &member.key != key) {
+                self.errors.push(ValidationError {
+                    pointer: child(pointer, key),
+                    expected: Some(value.clone()),
+                    found: None,
+                    span: located.span.clone(),
+                    reason: "missing key".to_string(),
+                });
+            }
+        }
+    }
+
+    fn unexpected_key(&mut self, member: &Member, pointer: String) {
+        let found = self.shape(&member.value);
+        self.errors.push(ValidationError {
+            pointer,
+            expected: None,
+            found: Some(found),
+            span: member.span.clone(),
+            reason: "unexpected key".to_string(),
+        });
+    }
+}
+
+/// If `shape` describes values with the same structure as `node`, objects or arrays, so that its nested values can be checked
+fn same_structure(shape: &Value, node: &LocatedNode) -> bool {
+    match (shape, node) {
+        (Value::Recursive { shape, .. }, node) => same_structure(shape, node),
+        (
+            Value::Object { .. } | Value::Map { .. } | Value::Tagged { .. },
+            LocatedNode::Object(_),
+        )
+        | (Value::Array { .. } | Value::Tuple { .. }, LocatedNode::Array(_)) => true,
+        _ => false,
+    }
+}
+
+fn child(pointer: &str, key: &str) -> String {
+    format!("{pointer}/{}", escape(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::InferenceOptions;
+
+    use super::*;
+
+    fn errors(shape: &Value, json: &str) -> Vec<String> {
+        shape
+            .validate(json)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn reports_the_deepest_mismatching_values() {
+        let shape = Value::from_sources(&[
+            r#"{"a/b": [{"x": 1}], "t": [1, "a"], "o": {"m~n": true}}"#.to_string(),
+            r#"{"a/b": [], "t": [2, "b"], "o": null}"#.to_string(),
+        ])
+        .unwrap();
+
+        assert!(
+            shape
+                .validate(r#"{"a/b": [{"x": 3}], "t": [3, "c"], "o": null}"#)
+                .is_ok()
+        );
+        assert_eq!(
+            errors(
+                &shape,
+                r#"{"a/b": [{"x": 1}, {"x": -1}], "t": [1, 2], "o": {"m~n": 1}}"#
+            ),
+            [
+                "unexpected value at `/a~1b/1/x`, expected `Integer<unsigned>`, found `Integer<signed>[-1..=-1]`: 25..27",
+                "unexpected value at `/t/1`, expected `String`, found `Integer<unsigned>[2..=2]`: 40..41",
+                "unexpected value at `/o/m~0n`, expected `Boolean`, found `Integer<unsigned>[1..=1]`: 57..58",
+            ]
+        );
+        assert_eq!(
+            errors(&shape, r#"{"a/b": [], "t": [1], "o": null}"#),
+            [
+                "unexpected value at `/t`, expected `Tuple(Integer<unsigned>, String)`, found `Array<Integer<unsigned>[1..=1]>[1..=1]`: 17..20"
+            ]
+        );
+    }
+
+    #[test]
+    fn one_of_tagged_maps_and_recursive_shapes_check_their_nested_values() {
+        let options = InferenceOptions::default()
+            .with_map_threshold(3)
+            .with_discriminators()
+            .with_recursion();
+        let shape = Value::from_str_with_options(
+            r#"{
+                "events": [{"type": "a", "x": 1}, {"type": "b"}],
+                "scores": {"1": 1.5, "2": 2.5, "3": 3.5},
+                "tree": {"v": 1, "next": {"v": 2, "next": null}},
+                "either": [[1], "a"]
+            }"#,
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(
+            errors(
+                &shape,
+                r#"{"events": [{"type": "a", "x": "1"}, {"type": "c", "y": 1}, {"x": 1}], "scores": {"x": 1}, "tree": {"v": 1, "next": {"v": true, "next": null}}, "either": [["1"], "a"]}"#
+            ),
+            [
+                "unexpected value at `/events/0/x`, expected `Integer<unsigned>`, found `String[1..=1]`: 31..34",
+                r#"unknown tag at `/events/1/type`, expected `Literal["a" | "b"]`, found `Literal["c"]`: 38..49"#,
+                r#"missing key at `/events/2/type`, expected `Literal["a" | "b"]`: 60..68"#,
+                "unexpected key at `/scores/x`, found `Integer<unsigned>[1..=1]`: 82..88",
+                "unexpected value at `/tree/next/v`, expected `Integer<unsigned>`, found `Boolean`: 122..126",
+                "unexpected value at `/either/0/0`, expected `Integer<unsigned>`, found `String[1..=1]`: 156..159",
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_tags_are_reported_at_their_key() {
+        let options = InferenceOptions::default().with_discriminators();
+        let shape = Value::from_str_with_options(
+            r#"[{"type": "click", "x": 1}, {"type": "close"}]"#,
+            &options,
+        )
+        .unwrap();
+        let json = r#"[{"type": "open", "x": 1}]"#;
+        let errors = shape.validate(json).unwrap_err();
+
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                r#"unknown tag at `/0/type`, expected `Literal["click" | "close"]`, found `Literal["open"]`: 2..16"#
+            ]
+        );
+        assert_eq!(&json[errors[0].span.clone()], r#""type": "open""#);
+        assert!(shape.validate(r#"[{"type": "close"}]"#).is_ok());
+    }
+
+    #[test]
+    fn invalid_json_is_a_single_error() {
+        let shape = Value::from_str("[1]").unwrap();
+        let errors = shape.validate("[1, }").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pointer, "");
+        assert_eq!(errors[0].reason, "invalid JSON");
+        assert_eq!(errors[0].found, None);
+    }
+
+    #[test]
+    fn renders_errors_with_codespan() {
+        let shape = Value::from_str(r#"{"id": 1, "tags": ["a"]}"#).unwrap();
+        let json = "{\"tags\": [\"a\", 2]}";
+        let errors = shape.validate(json).unwrap_err();
+
+        assert_eq!(
+            errors[0].render("payload.json", json),
+            "error: unexpected value\n  ┌─ payload.json:1:16\n  │\n1 │ {\"tags\": [\"a\", 2]}\n  │                ^ at `/tags/1`\n  │\n  = expected: String\n  = found: Integer<unsigned>[2..=2]\n\n"
+        );
+    }
+}
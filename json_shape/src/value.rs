@@ -1,11 +1,22 @@
 #![allow(clippy::match_same_arms)]
 use std::{
+    borrow::Cow,
     collections::{BTreeMap, BTreeSet, btree_map::Keys},
     fmt::Display,
 };
 
 use serde::{Deserialize, Serialize};
 
+use crate::shape::{
+    meet::meet,
+    merger::{limit_examples, literal_shape, merge_examples, merger, normalize},
+};
+
+pub use refinement::{
+    Examples, KeyPattern, LengthRange, LiteralValue, NumberKind, NumberRange, StringFormat,
+};
+
+pub mod refinement;
 pub mod subset;
 pub mod subtypes;
 
@@ -24,6 +35,10 @@ pub enum Value {
     /// Represents a JSON null value.
     Null,
 
+    /// Represents the unknown element of an empty JSON array, eg `[]` => `Array<Unknown>`.
+    /// Merging it with any other shape results in that shape.
+    Unknown,
+
     /// Represents a JSON boolean.
     Bool {
         /// If type is optional
@@ -34,11 +49,29 @@ pub enum Value {
     Number {
         /// If type is optional
         optional: bool,
+        /// Integer or floating point refinement of the number
+        #[serde(default)]
+        kind: NumberKind,
+        /// Range of the numbers seen, eg `0..=120`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        range: Option<NumberRange>,
+        /// Example numbers seen, eg `[42, 7]`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        examples: Option<Box<Examples>>,
     },
     /// Represents a JSON string.
     String {
         /// If type is optional
         optional: bool,
+        /// Well known format shared by all strings, eg `uuid` or `date-time`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        format: Option<StringFormat>,
+        /// Bounds of the string lengths seen, in characters
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        length: Option<LengthRange>,
+        /// Example strings seen, eg `["alice", "bob"]`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        examples: Option<Box<Examples>>,
     },
     /// Represents a JSON array.
     Array {
@@ -46,6 +79,9 @@ pub enum Value {
         r#type: Box<Self>,
         /// If type is optional
         optional: bool,
+        /// Bounds of the array lengths seen, in items
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        length: Option<LengthRange>,
     },
 
     /// Represents a JSON object.
@@ -54,6 +90,12 @@ pub enum Value {
         content: BTreeMap<String, Self>,
         /// If type is optional
         optional: bool,
+        /// Keys that may be absent from the object, as opposed to present with a `null` value.
+        /// `None` when not tracked, in which case the keys with optional values may be absent,
+        /// as in shapes built or persisted before absent keys were tracked. An empty set isn't
+        /// serialized either, so such shapes read back with the untracked meaning.
+        #[serde(default, skip_serializing_if = "is_untracked")]
+        absent: Option<BTreeSet<String>>,
     },
 
     /// Represents a JSON Value that can assume one of the Values described.
@@ -73,21 +115,88 @@ pub enum Value {
         /// If type is optional
         optional: bool,
     },
+
+    /// Represents a JSON value that can only assume one of the literal values described.
+    /// Similar to a fieldless enum in Rust, only inferred when [`InferenceOptions::literal_limit`](crate::InferenceOptions::literal_limit) is set.
+    Literal {
+        /// All possible [`LiteralValue`]s
+        values: BTreeSet<LiteralValue>,
+        /// If type is optional
+        optional: bool,
+    },
+
+    /// Represents a JSON object used as a dictionary, with dynamic keys and values of the same type.
+    /// Similar to a `BTreeMap<String, T>` in Rust, only inferred when [`InferenceOptions::map_threshold`](crate::InferenceOptions::map_threshold) is set.
+    Map {
+        /// Type of the values contained in the Map
+        r#type: Box<Self>,
+        /// Pattern shared by all keys, eg `Integer` or `String<uuid>`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        key: Option<KeyPattern>,
+        /// If type is optional
+        optional: bool,
+    },
+
+    /// Represents a named recursive JSON value, whose shape references itself with [`JsonShape::Ref`](Self::Ref).
+    /// Similar to a Rust struct containing `Box<Self>`, only inferred when [`InferenceOptions::recursive`](crate::InferenceOptions::recursive) is set.
+    Recursive {
+        /// Name of the definition, referenced by [`JsonShape::Ref`](Self::Ref)
+        name: String,
+        /// Shape of the definition, optional when the recursive value is optional
+        shape: Box<Self>,
+    },
+
+    /// Represents a reference to the enclosing [`JsonShape::Recursive`](Self::Recursive) definition with the same name.
+    Ref {
+        /// Name of the referenced definition
+        name: String,
+        /// If type is optional
+        optional: bool,
+    },
+
+    /// Represents a JSON object that can assume one of the object variants described, discriminated by the string value of its `tag` key.
+    /// Similar to a `#[serde(tag = "...")]` enum in Rust, only inferred when [`InferenceOptions::discriminators`](crate::InferenceOptions::discriminators) is set.
+    Tagged {
+        /// Key whose value discriminates the variants, eg `type`
+        tag: String,
+        /// Object variants, without the `tag` key, by the value of the `tag` key
+        variants: BTreeMap<String, Self>,
+        /// If type is optional
+        optional: bool,
+    },
 }
 
 impl Value {
+    /// Keys of a [`JsonShape::Object`] that may be absent: its `absent` keys when tracked, otherwise its keys with optional values.
+    /// Empty for other shapes.
+    #[must_use]
+    pub fn absent_keys(&self) -> BTreeSet<String> {
+        match self {
+            Self::Object {
+                content, absent, ..
+            } => absent_keys(content, absent.as_ref()).into_owned(),
+            _ => BTreeSet::new(),
+        }
+    }
+
     /// Is this [`JsonShape`] optional? eg, `Option<String>`
     #[must_use]
     pub const fn is_optional(&self) -> bool {
         match self {
             Self::Null => true,
+            Self::Unknown => false,
             Self::Bool { optional } => *optional,
-            Self::Number { optional } => *optional,
-            Self::String { optional } => *optional,
+            Self::Number { optional, .. } => *optional,
+            Self::String { optional, .. } => *optional,
             Self::Array { optional, .. } => *optional,
             Self::Object { optional, .. } => *optional,
             Self::OneOf { optional, .. } => *optional,
             Self::Tuple { optional, .. } => *optional,
+            Self::Literal { optional, .. } => *optional,
+            Self::Map { optional, .. } => *optional,
+            Self::Recursive { shape, .. } => shape.is_optional(),
+            Self::Ref { optional, .. } => *optional,
+            Self::Tagged { optional, .. } => *optional,
         }
     }
 
@@ -95,16 +204,41 @@ impl Value {
     pub(crate) fn as_optional(self) -> Self {
         match self {
             Self::Null => Self::Null,
+            Self::Unknown => Self::Unknown,
             Self::Bool { .. } => Self::Bool { optional: true },
-            Self::Number { .. } => Self::Number { optional: true },
-            Self::String { .. } => Self::String { optional: true },
-            Self::Array { r#type, .. } => Self::Array {
+            Self::Number {
+                kind,
+                range,
+                examples,
+                ..
+            } => Self::Number {
+                optional: true,
+                kind,
+                range,
+                examples,
+            },
+            Self::String {
+                format,
+                length,
+                examples,
+                ..
+            } => Self::String {
+                optional: true,
+                format,
+                length,
+                examples,
+            },
+            Self::Array { r#type, length, .. } => Self::Array {
                 optional: true,
                 r#type,
+                length,
             },
-            Self::Object { content, .. } => Self::Object {
+            Self::Object {
+                content, absent, ..
+            } => Self::Object {
                 optional: true,
                 content,
+                absent,
             },
             Self::OneOf { variants, .. } => Self::OneOf {
                 optional: true,
@@ -114,6 +248,28 @@ impl Value {
                 optional: true,
                 elements,
             },
+            Self::Literal { values, .. } => Self::Literal {
+                optional: true,
+                values,
+            },
+            Self::Map { r#type, key, .. } => Self::Map {
+                optional: true,
+                r#type,
+                key,
+            },
+            Self::Recursive { name, shape } => Self::Recursive {
+                name,
+                shape: Box::new(shape.as_optional()),
+            },
+            Self::Ref { name, .. } => Self::Ref {
+                optional: true,
+                name,
+            },
+            Self::Tagged { tag, variants, .. } => Self::Tagged {
+                optional: true,
+                tag,
+                variants,
+            },
         }
     }
 
@@ -121,16 +277,41 @@ impl Value {
     pub(crate) fn as_non_optional(self) -> Self {
         match self {
             Self::Null => Self::Null,
+            Self::Unknown => Self::Unknown,
             Self::Bool { .. } => Self::Bool { optional: false },
-            Self::Number { .. } => Self::Number { optional: false },
-            Self::String { .. } => Self::String { optional: false },
-            Self::Array { r#type, .. } => Self::Array {
+            Self::Number {
+                kind,
+                range,
+                examples,
+                ..
+            } => Self::Number {
+                optional: false,
+                kind,
+                range,
+                examples,
+            },
+            Self::String {
+                format,
+                length,
+                examples,
+                ..
+            } => Self::String {
+                optional: false,
+                format,
+                length,
+                examples,
+            },
+            Self::Array { r#type, length, .. } => Self::Array {
                 optional: false,
                 r#type,
+                length,
             },
-            Self::Object { content, .. } => Self::Object {
+            Self::Object {
+                content, absent, ..
+            } => Self::Object {
                 optional: false,
                 content,
+                absent,
             },
             Self::OneOf { variants, .. } => Self::OneOf {
                 optional: false,
@@ -140,36 +321,227 @@ impl Value {
                 optional: false,
                 elements,
             },
+            Self::Literal { values, .. } => Self::Literal {
+                optional: false,
+                values,
+            },
+            Self::Map { r#type, key, .. } => Self::Map {
+                optional: false,
+                r#type,
+                key,
+            },
+            Self::Recursive { name, shape } => Self::Recursive {
+                name,
+                shape: Box::new(shape.as_non_optional()),
+            },
+            Self::Ref { name, .. } => Self::Ref {
+                optional: false,
+                name,
+            },
+            Self::Tagged { tag, variants, .. } => Self::Tagged {
+                optional: false,
+                tag,
+                variants,
+            },
         }
     }
 
-    pub(crate) const fn to_optional_mut(&mut self) {
-        match self {
-            Self::Null => (),
-            Self::Bool { optional } => {
-                *optional = true;
-            }
-            Self::Number { optional } => {
-                *optional = true;
-            }
-            Self::String { optional } => {
-                *optional = true;
-            }
-            Self::Array { optional, .. } => {
-                *optional = true;
+    /// Checks if both shapes are equal when ignoring refinements, such as [`NumberKind`] and [`StringFormat`].
+    /// [`JsonShape::Unknown`] elements of empty arrays match any shape.
+    pub(crate) fn same_structure(&self, other: &Self) -> bool {
+        if self.is_literal() || other.is_literal() {
+            return self
+                .clone()
+                .widen_literal()
+                .same_structure(&other.clone().widen_literal());
+        }
+        match (self, other) {
+            (Self::Null, Self::Null) | (Self::Unknown, _) | (_, Self::Unknown) => true,
+            (Self::Bool { optional }, Self::Bool { optional: opt })
+            | (Self::Number { optional, .. }, Self::Number { optional: opt, .. })
+            | (Self::String { optional, .. }, Self::String { optional: opt, .. }) => {
+                optional == opt
             }
-            Self::Object { optional, .. } => {
-                *optional = true;
+            (
+                Self::Array {
+                    r#type, optional, ..
+                },
+                Self::Array {
+                    r#type: ty,
+                    optional: opt,
+                    ..
+                },
+            )
+            | (
+                Self::Map {
+                    r#type, optional, ..
+                },
+                Self::Map {
+                    r#type: ty,
+                    optional: opt,
+                    ..
+                },
+            ) => optional == opt && r#type.same_structure(ty),
+            (Self::Recursive { name, shape }, Self::Recursive { name: n, shape: s }) => {
+                name == n && shape.same_structure(s)
             }
-            Self::OneOf { optional, .. } => {
-                *optional = true;
+            (
+                Self::Object {
+                    content,
+                    optional,
+                    absent,
+                },
+                Self::Object {
+                    content: cont,
+                    optional: opt,
+                    absent: abs,
+                },
+            ) => {
+                optional == opt
+                    && absent_keys(content, absent.as_ref()) == absent_keys(cont, abs.as_ref())
+                    && content.len() == cont.len()
+                    && content
+                        .iter()
+                        .zip(cont)
+                        .all(|((key, value), (k, v))| key == k && value.same_structure(v))
             }
-            Self::Tuple { optional, .. } => {
-                *optional = true;
+            (
+                Self::Tuple { elements, optional },
+                Self::Tuple {
+                    elements: elems,
+                    optional: opt,
+                },
+            ) => {
+                optional == opt
+                    && elements.len() == elems.len()
+                    && elements.iter().zip(elems).all(|(a, b)| a.same_structure(b))
             }
+            (
+                Self::Tagged {
+                    tag,
+                    variants,
+                    optional,
+                },
+                Self::Tagged {
+                    tag: other_tag,
+                    variants: other,
+                    optional: opt,
+                },
+            ) => {
+                tag == other_tag
+                    && optional == opt
+                    && variants.len() == other.len()
+                    && variants
+                        .iter()
+                        .zip(other)
+                        .all(|((key, value), (k, v))| key == k && value.same_structure(v))
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Widens a [`JsonShape::Literal`] into the shape of its values, other shapes are returned unchanged.
+    /// - `Literal["active" | "disabled"]` => `String`
+    /// - `Literal[-1 | 2]` => `Integer<signed>`
+    /// - `Literal["a" | 1]` => `OneOf[Integer<unsigned> | String]`
+    #[must_use]
+    pub fn widen_literal(self) -> Self {
+        match self {
+            Self::Literal { values, optional } => literal_shape(&values, optional),
+            value => value,
         }
     }
 
+    /// Rewrites this [`JsonShape`] into its canonical form, so that semantically equal shapes compare equal.
+    /// Shapes inferred or merged by this crate are always normalized.
+    /// - `Option<OneOf[Number | String]>` => `OneOf[Null | Number | String]`
+    /// - `OneOf[Boolean | OneOf[Number | String]]` => `OneOf[Boolean | Number | String]`
+    /// - `OneOf[Integer<unsigned> | Option<Number>]` => `Option<Number>`, as `Number` covers `Integer<unsigned>`
+    /// - `OneOf[String]` => `String`
+    ///
+    /// The normalized shape describes the same values: variants are only dropped when another variant covers them,
+    /// never widened, eg `OneOf[Object{a: Boolean} | Object{b: Boolean}]` stays as is, unlike [`JsonShape::join`].
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        normalize(self)
+    }
+
+    /// Joins this [`JsonShape`] with `other` into the most specific shape describing the values of both,
+    /// as when inferring a shape from sources of both shapes.
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use json_shape::{IsSubset, JsonShape};
+    ///
+    /// let a = JsonShape::from_str(r#"{"id": 1, "name": "a"}"#).unwrap();
+    /// let b = JsonShape::from_str(r#"{"id": -1}"#).unwrap();
+    /// let joined = a.join(&b);
+    ///
+    /// assert_eq!(joined.to_string(), "Object{id: Integer<signed>, name?: String}");
+    /// assert!(a.is_subset(&joined) && b.is_subset(&joined));
+    /// ```
+    ///
+    /// [`Examples`] are bounded by the most examples either shape keeps for a single value,
+    /// as the example limit they were inferred with is unknown.
+    #[must_use]
+    pub fn join(&self, other: &Self) -> Self {
+        let union = || {
+            normalize(Self::OneOf {
+                variants: [self.clone(), other.clone()].into(),
+                optional: false,
+            })
+        };
+        // Only a `Tagged` without variants fails to merge, in which case both shapes are kept side by side
+        let joined = merger(self.clone(), other.clone()).unwrap_or_else(|_| union());
+        match self.example_count().max(other.example_count()) {
+            0 => joined,
+            limit => limit_examples(joined, limit),
+        }
+    }
+
+    /// Most [`Examples`] kept by a single value of this [`JsonShape`].
+    fn example_count(&self) -> usize {
+        self.fold(0, |count, shape, _| match shape {
+            Self::Number {
+                examples: Some(examples),
+                ..
+            }
+            | Self::String {
+                examples: Some(examples),
+                ..
+            } => count.max(examples.len()),
+            _ => count,
+        })
+    }
+
+    /// Meets this [`JsonShape`] with `other` into the most general shape that is a subset of both,
+    /// eg what every version of a client is able to accept.
+    /// Returns `None` when no Json value fits both shapes.
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use json_shape::{IsSubset, JsonShape};
+    ///
+    /// let v1 = JsonShape::from_str(r#"{"id": 1, "name": "a", "tags": null}"#).unwrap();
+    /// let v2 = JsonShape::from_sources(&[
+    ///     r#"{"id": 1.5, "tags": ["a"]}"#.to_string(),
+    ///     r#"{"id": 2, "name": "b", "tags": null}"#.to_string(),
+    /// ])
+    /// .unwrap();
+    /// let accepted = v1.meet(&v2).unwrap();
+    ///
+    /// assert_eq!(accepted.to_string(), "Object{id: Integer<unsigned>, name: String, tags: Null}");
+    /// assert!(accepted.is_subset(&v1) && accepted.is_subset(&v2));
+    ///
+    /// let number = JsonShape::from_str("1").unwrap();
+    /// let string = JsonShape::from_str(r#""a""#).unwrap();
+    /// assert_eq!(number.meet(&string), None);
+    /// ```
+    #[must_use]
+    pub fn meet(&self, other: &Self) -> Option<Self> {
+        meet(self, other)
+    }
+
     /// Return the keys contained in a [`JsonShape::Object`]
     #[must_use]
     pub fn keys(&self) -> Option<Keys<'_, String, Self>> {
@@ -186,6 +558,12 @@ impl Value {
         matches!(self, Self::Null)
     }
 
+    /// Checks if Json Node is the unknown element of an empty array
+    #[must_use]
+    pub const fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown)
+    }
+
     /// Checks if Json Node is boolean
     #[must_use]
     pub const fn is_boolean(&self) -> bool {
@@ -198,6 +576,12 @@ impl Value {
         matches!(self, Self::Number { .. })
     }
 
+    /// Checks if Json Node is an integer number
+    #[must_use]
+    pub const fn is_integer(&self) -> bool {
+        matches!(self, Self::Number { kind, .. } if kind.is_integer())
+    }
+
     /// Checks if Json Node is string
     #[must_use]
     pub const fn is_string(&self) -> bool {
@@ -227,12 +611,44 @@ impl Value {
     pub const fn is_oneof(&self) -> bool {
         matches!(self, Self::OneOf { .. })
     }
+
+    /// Checks if Json Node is a literal union
+    #[must_use]
+    pub const fn is_literal(&self) -> bool {
+        matches!(self, Self::Literal { .. })
+    }
+
+    /// Checks if Json Node is a map
+    #[must_use]
+    pub const fn is_map(&self) -> bool {
+        matches!(self, Self::Map { .. })
+    }
+
+    /// Checks if Json Node is a recursive definition
+    #[must_use]
+    pub const fn is_recursive(&self) -> bool {
+        matches!(self, Self::Recursive { .. })
+    }
+
+    /// Checks if Json Node is a reference to a recursive definition
+    #[must_use]
+    pub const fn is_ref(&self) -> bool {
+        matches!(self, Self::Ref { .. })
+    }
+
+    /// Checks if Json Node is a tagged union
+    #[must_use]
+    pub const fn is_tagged(&self) -> bool {
+        matches!(self, Self::Tagged { .. })
+    }
 }
 
 impl Display for Value {
+    #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Null => write!(f, "Null"),
+            Self::Unknown => write!(f, "Unknown"),
             Self::Bool { optional } => write!(
                 f,
                 "{}",
@@ -242,36 +658,56 @@ impl Display for Value {
                     "Boolean"
                 }
             ),
-            Self::Number { optional } => write!(
-                f,
-                "{}",
-                if *optional {
-                    "Option<Number>"
-                } else {
-                    "Number"
-                }
-            ),
-            Self::String { optional } => write!(
-                f,
-                "{}",
+            Self::Number {
+                optional,
+                kind,
+                range,
+                ..
+            } => match (optional, range) {
+                (true, Some(range)) => write!(f, "Option<{kind}[{range}]>"),
+                (true, None) => write!(f, "Option<{kind}>"),
+                (false, Some(range)) => write!(f, "{kind}[{range}]"),
+                (false, None) => write!(f, "{kind}"),
+            },
+            Self::String {
+                optional,
+                format,
+                length,
+                ..
+            } => {
+                let format = format
+                    .map(|format| format!("<{format}>"))
+                    .unwrap_or_default();
+                let length = display_length(length.as_ref());
                 if *optional {
-                    "Option<String>"
+                    write!(f, "Option<String{format}{length}>")
                 } else {
-                    "String"
+                    write!(f, "String{format}{length}")
                 }
-            ),
-            Self::Array { r#type, optional } => {
+            }
+            Self::Array {
+                r#type,
+                optional,
+                length,
+            } => {
+                let length = display_length(length.as_ref());
                 if *optional {
-                    write!(f, "Option<Array<{type}>>")
+                    write!(f, "Option<Array<{type}>{length}>")
                 } else {
-                    write!(f, "Array<{type}>")
+                    write!(f, "Array<{type}>{length}")
                 }
             }
-            Self::Object { content, optional } => {
+            Self::Object {
+                content,
+                optional,
+                absent,
+            } => {
+                let content =
+                    display_object_content(content, &absent_keys(content, absent.as_ref()));
                 if *optional {
-                    write!(f, "Option<Object{{{}}}>", display_object_content(content))
+                    write!(f, "Option<Object{{{content}}}>")
                 } else {
-                    write!(f, "Object{{{}}}", display_object_content(content))
+                    write!(f, "Object{{{content}}}")
                 }
             }
             Self::OneOf { variants, optional } => {
@@ -298,42 +734,144 @@ impl Display for Value {
                     write!(f, "Tuple({elements})")
                 }
             }
+            Self::Literal { values, optional } => {
+                let values = values
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                if *optional {
+                    write!(f, "Option<Literal[{values}]>")
+                } else {
+                    write!(f, "Literal[{values}]")
+                }
+            }
+            Self::Map {
+                r#type,
+                key,
+                optional,
+            } => {
+                let key = key.map_or_else(|| "String".to_string(), |key| key.to_string());
+                if *optional {
+                    write!(f, "Option<Map<{key}, {type}>>")
+                } else {
+                    write!(f, "Map<{key}, {type}>")
+                }
+            }
+            Self::Recursive { name, shape } => write!(f, "Recursive<{name}: {shape}>"),
+            Self::Ref { name, optional } => {
+                if *optional {
+                    write!(f, "Option<Ref<{name}>>")
+                } else {
+                    write!(f, "Ref<{name}>")
+                }
+            }
+            Self::Tagged {
+                tag,
+                variants,
+                optional,
+            } => {
+                let variants = variants
+                    .iter()
+                    .map(|(value, variant)| {
+                        format!("{}: {variant}", serde_json::Value::from(value.as_str()))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                let tag = display_key(tag);
+                if *optional {
+                    write!(f, "Option<Tagged<{tag}>[{variants}]>")
+                } else {
+                    write!(f, "Tagged<{tag}>[{variants}]")
+                }
+            }
         }
     }
 }
 
 impl Similar for Value {
+    #[allow(clippy::too_many_lines)]
     fn similar(&self, other: &Self) -> Option<Value> {
         match (self, other) {
             (Self::Null, Self::Null) => Some(Self::Null),
+            (Self::Unknown, Self::Unknown) => Some(Self::Unknown),
             (Self::Bool { optional }, Self::Bool { optional: opt }) => Some(Self::Bool {
                 optional: *optional || *opt,
             }),
-            (Self::Number { optional }, Self::Number { optional: opt }) => Some(Self::Number {
+            (
+                Self::Number {
+                    optional,
+                    kind,
+                    range,
+                    examples,
+                },
+                Self::Number {
+                    optional: opt,
+                    kind: other_kind,
+                    range: other_range,
+                    examples: other_examples,
+                },
+            ) if kind == other_kind && range == other_range => Some(Self::Number {
                 optional: *optional || *opt,
+                kind: *kind,
+                range: range.clone(),
+                examples: merge_examples(examples.as_deref(), other_examples.as_deref()),
             }),
-            (Self::String { optional }, Self::String { optional: opt }) => Some(Self::String {
+            (
+                Self::String {
+                    optional,
+                    format,
+                    length,
+                    examples,
+                },
+                Self::String {
+                    optional: opt,
+                    format: other_format,
+                    length: other_length,
+                    examples: other_examples,
+                },
+            ) if format == other_format && length == other_length => Some(Self::String {
                 optional: *optional || *opt,
+                format: *format,
+                length: *length,
+                examples: merge_examples(examples.as_deref(), other_examples.as_deref()),
             }),
             (
-                Self::Array { r#type, optional },
+                Self::Array {
+                    r#type,
+                    optional,
+                    length,
+                },
                 Self::Array {
                     r#type: ty,
                     optional: opt,
+                    length: other_length,
                 },
-            ) if ty == r#type => Some(Self::Array {
+            ) if ty == r#type && length == other_length => Some(Self::Array {
                 r#type: ty.clone(),
                 optional: *optional || *opt,
+                length: *length,
             }),
             (
-                Self::Object { content, optional },
+                Self::Object {
+                    content,
+                    optional,
+                    absent,
+                },
                 Self::Object {
                     content: cont,
                     optional: opt,
+                    absent: abs,
                 },
             ) if cont == content => Some(Self::Object {
                 content: content.clone(),
                 optional: *optional || *opt,
+                absent: Some(
+                    absent_keys(content, absent.as_ref())
+                        .union(&absent_keys(cont, abs.as_ref()))
+                        .cloned()
+                        .collect(),
+                ),
             }),
             (
                 Self::OneOf { variants, optional },
@@ -355,42 +893,161 @@ impl Similar for Value {
                 elements: ty.clone(),
                 optional: *optional || *opt,
             }),
+            (
+                Self::Literal { values, optional },
+                Self::Literal {
+                    values: other,
+                    optional: opt,
+                },
+            ) if values == other => Some(Self::Literal {
+                values: values.clone(),
+                optional: *optional || *opt,
+            }),
+            (
+                Self::Map {
+                    r#type,
+                    key,
+                    optional,
+                },
+                Self::Map {
+                    r#type: ty,
+                    key: other_key,
+                    optional: opt,
+                },
+            ) if ty == r#type && key == other_key => Some(Self::Map {
+                r#type: ty.clone(),
+                key: *key,
+                optional: *optional || *opt,
+            }),
+            (Self::Recursive { name, shape }, Self::Recursive { name: n, shape: s })
+                if name == n =>
+            {
+                shape.similar(s).map(|shape| Self::Recursive {
+                    name: name.clone(),
+                    shape: Box::new(shape),
+                })
+            }
+            (
+                Self::Ref { name, optional },
+                Self::Ref {
+                    name: n,
+                    optional: opt,
+                },
+            ) if name == n => Some(Self::Ref {
+                name: name.clone(),
+                optional: *optional || *opt,
+            }),
+            (
+                Self::Tagged {
+                    tag,
+                    variants,
+                    optional,
+                },
+                Self::Tagged {
+                    tag: other_tag,
+                    variants: other,
+                    optional: opt,
+                },
+            ) if tag == other_tag && variants == other => Some(Self::Tagged {
+                tag: tag.clone(),
+                variants: variants.clone(),
+                optional: *optional || *opt,
+            }),
             _ => None,
         }
     }
 }
 
-fn display_object_content(content: &BTreeMap<String, Value>) -> String {
+/// Keys of an object that may be absent: its `absent` keys when tracked, otherwise the keys with optional values,
+/// as in shapes built before absent keys were tracked separately from `null` values.
+/// - `Object{a: Option<T>, b: U}` with `absent: Some(BTreeSet::new())` => `{a}`
+/// - `Object{a: Option<T>, b: U}` with `absent: Some({b})` => `{b}`
+pub(crate) fn absent_keys<'a>(
+    content: &BTreeMap<String, Value>,
+    absent: Option<&'a BTreeSet<String>>,
+) -> Cow<'a, BTreeSet<String>> {
+    absent.map_or_else(
+        || {
+            Cow::Owned(
+                content
+                    .iter()
+                    .filter(|(_, value)| value.is_optional())
+                    .map(|(key, _)| key.clone())
+                    .collect(),
+            )
+        },
+        Cow::Borrowed,
+    )
+}
+
+// `skip_serializing_if` passes the field by reference.
+#[allow(clippy::ref_option)]
+fn is_untracked(absent: &Option<BTreeSet<String>>) -> bool {
+    absent.as_ref().is_none_or(BTreeSet::is_empty)
+}
+
+fn display_object_content(content: &BTreeMap<String, Value>, absent: &BTreeSet<String>) -> String {
     content
         .iter()
         .map(|(key, value)| {
-            if key
-                .chars()
-                .all(|char| char.is_alphanumeric() || char == '_' || char == '-')
-            {
-                format!("{key}: {value}")
-            } else {
-                format!("\"{key}\": {value}")
-            }
+            let presence = if absent.contains(key) { "?" } else { "" };
+            format!("{}{presence}: {value}", display_key(key))
         })
         .collect::<Vec<_>>()
         .join(", ")
 }
 
+/// Keys made of letters, digits, `_` and `-` are displayed as is, others as JSON strings, eg `"first name"`.
+pub(crate) fn display_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|char| char.is_alphanumeric() || char == '_' || char == '-')
+    {
+        key.to_string()
+    } else {
+        serde_json::Value::from(key).to_string()
+    }
+}
+
+pub(crate) fn display_length(length: Option<&LengthRange>) -> String {
+    length
+        .map(|length| format!("[{length}]"))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{InferenceOptions, value::NumberKind};
 
     #[test]
     fn is_optional_returns_true_when_values_are_optional() {
         assert!(Value::Null.is_optional());
         assert!(Value::Bool { optional: true }.is_optional());
-        assert!(Value::Number { optional: true }.is_optional());
-        assert!(Value::String { optional: true }.is_optional());
+        assert!(
+            Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_optional()
+        );
+        assert!(
+            Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_optional()
+        );
         assert!(
             Value::Array {
                 optional: true,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_optional()
         );
@@ -404,7 +1061,8 @@ mod tests {
         assert!(
             Value::Object {
                 optional: true,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_optional()
         );
@@ -420,12 +1078,29 @@ mod tests {
     #[test]
     fn is_optional_returns_false_when_values_are_not_optional() {
         assert!(!Value::Bool { optional: false }.is_optional());
-        assert!(!Value::Number { optional: false }.is_optional());
-        assert!(!Value::String { optional: false }.is_optional());
+        assert!(
+            !Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_optional()
+        );
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_optional()
+        );
         assert!(
             !Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_optional()
         );
@@ -439,7 +1114,8 @@ mod tests {
         assert!(
             !Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_optional()
         );
@@ -456,19 +1132,30 @@ mod tests {
     fn as_optional_returns_optional_version_of_values() {
         assert!(Value::Bool { optional: false }.as_optional().is_optional());
         assert!(
-            Value::Number { optional: false }
-                .as_optional()
-                .is_optional()
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .as_optional()
+            .is_optional()
         );
         assert!(
-            Value::String { optional: false }
-                .as_optional()
-                .is_optional()
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .as_optional()
+            .is_optional()
         );
         assert!(
             Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .as_optional()
             .is_optional()
@@ -476,7 +1163,8 @@ mod tests {
         assert!(
             Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .as_optional()
             .is_optional()
@@ -503,12 +1191,31 @@ mod tests {
     fn keys_returns_keys_only_for_object() {
         assert!(Value::Null.keys().is_none());
         assert!(Value::Bool { optional: true }.keys().is_none());
-        assert!(Value::Number { optional: true }.keys().is_none());
-        assert!(Value::String { optional: true }.keys().is_none());
+        assert!(
+            Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .keys()
+            .is_none()
+        );
+        assert!(
+            Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .keys()
+            .is_none()
+        );
         assert!(
             Value::Array {
                 optional: true,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .keys()
             .is_none()
@@ -536,7 +1243,8 @@ mod tests {
                     ("key_1".to_string(), Value::Null),
                     ("key_2".to_string(), Value::Null),
                 ]
-                .into()
+                .into(),
+                absent: Some(BTreeSet::new())
             }
             .keys()
             .unwrap()
@@ -546,6 +1254,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::too_many_lines)]
     fn to_string_for_optional_values() {
         assert_eq!(Value::Null.to_string(), "Null");
         assert_eq!(
@@ -553,146 +1262,517 @@ mod tests {
             "Option<Boolean>"
         );
         assert_eq!(
-            Value::Number { optional: true }.to_string(),
+            Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .to_string(),
             "Option<Number>"
         );
         assert_eq!(
-            Value::String { optional: true }.to_string(),
+            Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .to_string(),
             "Option<String>"
         );
         assert_eq!(
-            Value::Array {
+            Value::Array {
+                optional: true,
+                r#type: Box::new(Value::Null),
+                length: None
+            }
+            .to_string(),
+            "Option<Array<Null>>"
+        );
+        assert_eq!(
+            Value::Object {
+                optional: true,
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
+            }
+            .to_string(),
+            "Option<Object{}>"
+        );
+        assert_eq!(
+            Value::Object {
+                optional: true,
+                content: [
+                    ("key_1".to_string(), Value::Null),
+                    (
+                        "key_2".to_string(),
+                        Value::Number {
+                            optional: true,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    ),
+                    (
+                        "key_3".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )
+                ]
+                .into(),
+                absent: Some(BTreeSet::new())
+            }
+            .to_string(),
+            "Option<Object{key_1: Null, key_2: Option<Number>, key_3: Number}>"
+        );
+        assert_eq!(
+            Value::OneOf {
+                optional: true,
+                variants: [
+                    Value::Null,
+                    Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }
+                ]
+                .into()
+            }
+            .to_string(),
+            "Option<OneOf[Null | Number | Option<Number>]>"
+        );
+        assert_eq!(
+            Value::Tuple {
+                optional: true,
+                elements: [
+                    Value::Null,
+                    Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }
+                ]
+                .into()
+            }
+            .to_string(),
+            "Option<Tuple(Null, Option<Number>, Number)>"
+        );
+    }
+
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn to_string_for_non_optional_values() {
+        assert_eq!(Value::Bool { optional: false }.to_string(), "Boolean");
+        assert_eq!(
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .to_string(),
+            "Number"
+        );
+        assert_eq!(
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .to_string(),
+            "String"
+        );
+        assert_eq!(
+            Value::Array {
+                optional: false,
+                r#type: Box::new(Value::Null),
+                length: None
+            }
+            .to_string(),
+            "Array<Null>"
+        );
+        assert_eq!(
+            Value::Object {
+                optional: false,
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
+            }
+            .to_string(),
+            "Object{}"
+        );
+        assert_eq!(
+            Value::Object {
+                optional: false,
+                content: [
+                    ("key_1".to_string(), Value::Null),
+                    (
+                        "key_2".to_string(),
+                        Value::Number {
+                            optional: true,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    ),
+                    (
+                        "key_3".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )
+                ]
+                .into(),
+                absent: Some(BTreeSet::new())
+            }
+            .to_string(),
+            "Object{key_1: Null, key_2: Option<Number>, key_3: Number}"
+        );
+        assert_eq!(
+            Value::OneOf {
+                optional: false,
+                variants: [
+                    Value::Null,
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }
+                ]
+                .into()
+            }
+            .to_string(),
+            "OneOf[Null | Number | Option<Number>]"
+        );
+        assert_eq!(
+            Value::Tuple {
+                optional: false,
+                elements: [
+                    Value::Null,
+                    Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }
+                ]
+                .into()
+            }
+            .to_string(),
+            "Tuple(Null, Option<Number>, Number)"
+        );
+    }
+
+    #[test]
+    fn to_string_for_integer_values() {
+        assert_eq!(
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Unsigned,
+                range: None,
+                examples: None,
+            }
+            .to_string(),
+            "Integer<unsigned>"
+        );
+        assert_eq!(
+            Value::Number {
+                optional: true,
+                kind: NumberKind::Signed,
+                range: None,
+                examples: None,
+            }
+            .to_string(),
+            "Option<Integer<signed>>"
+        );
+        assert_eq!(
+            Value::Array {
+                optional: false,
+                r#type: Box::new(Value::Number {
+                    optional: false,
+                    kind: NumberKind::LargeUnsigned,
+                    range: None,
+                    examples: None,
+                }),
+                length: None
+            }
+            .to_string(),
+            "Array<Integer<u64>>"
+        );
+    }
+
+    #[test]
+    fn to_string_for_formatted_string_values() {
+        assert_eq!(
+            Value::String {
+                optional: false,
+                format: Some(StringFormat::Uuid),
+                length: None,
+                examples: None
+            }
+            .to_string(),
+            "String<uuid>"
+        );
+        assert_eq!(
+            Value::String {
                 optional: true,
-                r#type: Box::new(Value::Null)
+                format: Some(StringFormat::DateTime),
+                length: None,
+                examples: None
             }
             .to_string(),
-            "Option<Array<Null>>"
+            "Option<String<date-time>>"
         );
+    }
+
+    #[test]
+    fn to_string_for_literal_values() {
         assert_eq!(
-            Value::Object {
-                optional: true,
-                content: BTreeMap::default()
+            Value::Literal {
+                values: [
+                    LiteralValue::String("active".to_string()),
+                    LiteralValue::String("disabled".to_string())
+                ]
+                .into(),
+                optional: false
             }
             .to_string(),
-            "Option<Object{}>"
+            r#"Literal["active" | "disabled"]"#
         );
         assert_eq!(
-            Value::Object {
-                optional: true,
-                content: [
-                    ("key_1".to_string(), Value::Null),
-                    ("key_2".to_string(), Value::Number { optional: true }),
-                    ("key_3".to_string(), Value::Number { optional: false })
-                ]
-                .into()
+            Value::Literal {
+                values: [LiteralValue::Integer(1), LiteralValue::Integer(-2)].into(),
+                optional: true
             }
             .to_string(),
-            "Option<Object{key_1: Null, key_2: Option<Number>, key_3: Number}>"
+            "Option<Literal[-2 | 1]>"
         );
+    }
+
+    #[test]
+    fn to_string_for_map_values() {
         assert_eq!(
-            Value::OneOf {
-                optional: true,
-                variants: [
-                    Value::Null,
-                    Value::Number { optional: true },
-                    Value::Number { optional: false }
-                ]
-                .into()
+            Value::Map {
+                r#type: Box::new(Value::Bool { optional: false }),
+                key: None,
+                optional: false
             }
             .to_string(),
-            "Option<OneOf[Null | Number | Option<Number>]>"
+            "Map<String, Boolean>"
         );
         assert_eq!(
-            Value::Tuple {
-                optional: true,
-                elements: [
-                    Value::Null,
-                    Value::Number { optional: true },
-                    Value::Number { optional: false }
-                ]
-                .into()
+            Value::Map {
+                r#type: Box::new(Value::Null),
+                key: Some(KeyPattern::Format(StringFormat::Date)),
+                optional: true
             }
             .to_string(),
-            "Option<Tuple(Null, Option<Number>, Number)>"
+            "Option<Map<String<date>, Null>>"
         );
     }
 
     #[test]
-    fn to_string_for_non_optional_values() {
-        assert_eq!(Value::Bool { optional: false }.to_string(), "Boolean");
-        assert_eq!(Value::Number { optional: false }.to_string(), "Number");
-        assert_eq!(Value::String { optional: false }.to_string(), "String");
+    fn to_string_for_recursive_values() {
         assert_eq!(
-            Value::Array {
-                optional: false,
-                r#type: Box::new(Value::Null)
+            Value::Recursive {
+                name: "Node".to_string(),
+                shape: Box::new(Value::Object {
+                    content: [(
+                        "children".to_string(),
+                        Value::Array {
+                            r#type: Box::new(Value::Ref {
+                                name: "Node".to_string(),
+                                optional: false
+                            }),
+                            optional: false,
+                            length: None
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
+                })
             }
             .to_string(),
-            "Array<Null>"
+            "Recursive<Node: Object{children: Array<Ref<Node>>}>"
         );
         assert_eq!(
-            Value::Object {
-                optional: false,
-                content: BTreeMap::default()
+            Value::Ref {
+                name: "Node".to_string(),
+                optional: true
             }
             .to_string(),
-            "Object{}"
+            "Option<Ref<Node>>"
         );
+    }
+
+    #[test]
+    fn to_string_for_absent_keys() {
         assert_eq!(
             Value::Object {
-                optional: false,
                 content: [
-                    ("key_1".to_string(), Value::Null),
-                    ("key_2".to_string(), Value::Number { optional: true }),
-                    ("key_3".to_string(), Value::Number { optional: false })
+                    ("a".to_string(), Value::Bool { optional: false }),
+                    ("b".to_string(), Value::Bool { optional: true }),
+                    ("c d".to_string(), Value::Bool { optional: true }),
                 ]
-                .into()
+                .into(),
+                optional: false,
+                absent: Some(["a".to_string(), "c d".to_string()].into()),
             }
             .to_string(),
-            "Object{key_1: Null, key_2: Option<Number>, key_3: Number}"
+            r#"Object{a?: Boolean, b: Option<Boolean>, "c d"?: Option<Boolean>}"#
         );
+    }
+
+    #[test]
+    fn to_string_for_tagged_values() {
         assert_eq!(
-            Value::OneOf {
-                optional: false,
+            Value::Tagged {
+                tag: "type".to_string(),
                 variants: [
-                    Value::Null,
-                    Value::Number { optional: false },
-                    Value::Number { optional: true }
+                    (
+                        "click".to_string(),
+                        Value::Object {
+                            content: [("x".to_string(), Value::Bool { optional: false })].into(),
+                            optional: false,
+                            absent: Some(BTreeSet::new())
+                        }
+                    ),
+                    (
+                        "close".to_string(),
+                        Value::Object {
+                            content: BTreeMap::default(),
+                            optional: false,
+                            absent: Some(BTreeSet::new())
+                        }
+                    )
                 ]
-                .into()
+                .into(),
+                optional: true
             }
             .to_string(),
-            "OneOf[Null | Number | Option<Number>]"
+            r#"Option<Tagged<type>["click": Object{x: Boolean} | "close": Object{}]>"#
+        );
+    }
+
+    #[test]
+    fn join_bounds_examples() {
+        let options = InferenceOptions::default().with_examples(3);
+        let a = Value::from_str_with_options("[1, 2, 3]", &options).unwrap();
+        let b = Value::from_str_with_options("[4, 5, 6]", &options).unwrap();
+
+        let Value::Array { r#type, .. } = a.join(&b) else {
+            panic!("expected Array");
+        };
+        let Value::Number {
+            examples: Some(examples),
+            ..
+        } = *r#type
+        else {
+            panic!("expected Number with examples, got {type}");
+        };
+        assert_eq!(examples.iter().collect::<Vec<_>>(), ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn widen_literal_into_value_shapes() {
+        let literal = |values: Vec<LiteralValue>, optional| Value::Literal {
+            values: values.into_iter().collect(),
+            optional,
+        };
+
+        assert_eq!(
+            literal(
+                vec![
+                    LiteralValue::String("2024-01-31".to_string()),
+                    LiteralValue::String("active".to_string())
+                ],
+                true
+            )
+            .widen_literal(),
+            Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }
         );
         assert_eq!(
-            Value::Tuple {
+            literal(
+                vec![LiteralValue::Integer(1), LiteralValue::Integer(-2)],
+                false
+            )
+            .widen_literal(),
+            Value::Number {
                 optional: false,
-                elements: [
-                    Value::Null,
-                    Value::Number { optional: true },
-                    Value::Number { optional: false }
+                kind: NumberKind::Signed,
+                range: None,
+                examples: None,
+            }
+        );
+        assert_eq!(
+            literal(
+                vec![
+                    LiteralValue::String("a".to_string()),
+                    LiteralValue::Integer(1)
+                ],
+                false
+            )
+            .widen_literal(),
+            Value::OneOf {
+                variants: [
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Unsigned,
+                        range: None,
+                        examples: None,
+                    },
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
                 ]
-                .into()
+                .into(),
+                optional: false
             }
-            .to_string(),
-            "Tuple(Null, Option<Number>, Number)"
         );
-    }
-
-    #[test]
-    fn to_optional_mut_transforms_value_inline_as_ref_mut() {
-        let mut v = Value::Bool { optional: false };
-        assert!(!v.is_optional());
-        v.to_optional_mut();
-        assert!(v.is_optional());
-        let mut v = Value::Number { optional: false };
-        assert!(!v.is_optional());
-        v.to_optional_mut();
-        assert!(v.is_optional());
-        let mut v = Value::String { optional: false };
-        assert!(!v.is_optional());
-        v.to_optional_mut();
-        assert!(v.is_optional());
+        assert_eq!(Value::Null.widen_literal(), Value::Null);
     }
 
     #[test]
@@ -707,7 +1787,7 @@ mod tests {
         ]
         .into();
 
-        let s = display_object_content(&map);
+        let s = display_object_content(&map, &BTreeSet::new());
 
         assert_eq!(
             s,
@@ -719,25 +1799,52 @@ mod tests {
 #[cfg(test)]
 mod tests_is_a {
     use super::*;
+    use crate::value::NumberKind;
 
     #[test]
     fn only_string_is_a_string() {
-        assert!(Value::String { optional: false }.is_string());
-        assert!(Value::String { optional: true }.is_string());
+        assert!(
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_string()
+        );
+        assert!(
+            Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_string()
+        );
         assert!(!Value::Null.is_string());
         assert!(!Value::Bool { optional: false }.is_string());
-        assert!(!Value::Number { optional: false }.is_string());
+        assert!(
+            !Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_string()
+        );
         assert!(
             !Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_string()
         );
         assert!(
             !Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_string()
         );
@@ -759,22 +1866,48 @@ mod tests_is_a {
 
     #[test]
     fn only_number_is_a_number() {
-        assert!(!Value::String { optional: false }.is_number());
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_number()
+        );
         assert!(!Value::Null.is_number());
         assert!(!Value::Bool { optional: false }.is_number());
-        assert!(Value::Number { optional: false }.is_number());
-        assert!(Value::Number { optional: true }.is_number());
+        assert!(
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_number()
+        );
+        assert!(
+            Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_number()
+        );
         assert!(
             !Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_number()
         );
         assert!(
             !Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_number()
         );
@@ -794,23 +1927,82 @@ mod tests_is_a {
         );
     }
 
+    #[test]
+    fn only_integer_number_is_integer() {
+        assert!(
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Unsigned,
+                range: None,
+                examples: None,
+            }
+            .is_integer()
+        );
+        assert!(
+            Value::Number {
+                optional: true,
+                kind: NumberKind::Signed,
+                range: None,
+                examples: None,
+            }
+            .is_integer()
+        );
+        assert!(
+            !Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_integer()
+        );
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_integer()
+        );
+        assert!(!Value::Null.is_integer());
+    }
+
     #[test]
     fn only_null_is_null() {
-        assert!(!Value::String { optional: false }.is_null());
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_null()
+        );
         assert!(Value::Null.is_null());
         assert!(!Value::Bool { optional: false }.is_null());
-        assert!(!Value::Number { optional: true }.is_null());
+        assert!(
+            !Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_null()
+        );
         assert!(
             !Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_null()
         );
         assert!(
             !Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_null()
         );
@@ -832,21 +2024,39 @@ mod tests_is_a {
 
     #[test]
     fn only_bool_is_bool() {
-        assert!(!Value::String { optional: false }.is_boolean());
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_boolean()
+        );
         assert!(!Value::Null.is_boolean());
         assert!(Value::Bool { optional: false }.is_boolean());
-        assert!(!Value::Number { optional: true }.is_boolean());
+        assert!(
+            !Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_boolean()
+        );
         assert!(
             !Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_boolean()
         );
         assert!(
             !Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_boolean()
         );
@@ -868,21 +2078,39 @@ mod tests_is_a {
 
     #[test]
     fn only_array_is_array() {
-        assert!(!Value::String { optional: false }.is_array());
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_array()
+        );
         assert!(!Value::Null.is_array());
         assert!(!Value::Bool { optional: false }.is_array());
-        assert!(!Value::Number { optional: true }.is_array());
+        assert!(
+            !Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_array()
+        );
         assert!(
             Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_array()
         );
         assert!(
             !Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_array()
         );
@@ -904,21 +2132,39 @@ mod tests_is_a {
 
     #[test]
     fn only_object_is_object() {
-        assert!(!Value::String { optional: false }.is_object());
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_object()
+        );
         assert!(!Value::Null.is_object());
         assert!(!Value::Bool { optional: false }.is_object());
-        assert!(!Value::Number { optional: true }.is_object());
+        assert!(
+            !Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_object()
+        );
         assert!(
             !Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_object()
         );
         assert!(
             Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_object()
         );
@@ -940,21 +2186,39 @@ mod tests_is_a {
 
     #[test]
     fn only_one_of_is_one_of() {
-        assert!(!Value::String { optional: false }.is_oneof());
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_oneof()
+        );
         assert!(!Value::Null.is_oneof());
         assert!(!Value::Bool { optional: false }.is_oneof());
-        assert!(!Value::Number { optional: true }.is_oneof());
+        assert!(
+            !Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_oneof()
+        );
         assert!(
             !Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_oneof()
         );
         assert!(
             !Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_oneof()
         );
@@ -976,21 +2240,39 @@ mod tests_is_a {
 
     #[test]
     fn only_tuple_is_tuple() {
-        assert!(!Value::String { optional: false }.is_tuple());
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_tuple()
+        );
         assert!(!Value::Null.is_tuple());
         assert!(!Value::Bool { optional: false }.is_tuple());
-        assert!(!Value::Number { optional: true }.is_tuple());
+        assert!(
+            !Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_tuple()
+        );
         assert!(
             !Value::Array {
                 optional: false,
-                r#type: Box::new(Value::Null)
+                r#type: Box::new(Value::Null),
+                length: None
             }
             .is_tuple()
         );
         assert!(
             !Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .is_tuple()
         );
@@ -1014,13 +2296,19 @@ mod tests_is_a {
 #[cfg(test)]
 mod tests_similar {
     use super::*;
+    use crate::value::NumberKind;
 
     #[test]
     fn null_is_similar_to_null() {
         assert!(Value::Null.similar(&Value::Null).is_some());
         assert!(
             Value::Null
-                .similar(&Value::String { optional: false })
+                .similar(&Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                })
                 .is_none()
         );
     }
@@ -1028,15 +2316,58 @@ mod tests_similar {
     #[test]
     fn str_is_similar_to_str() {
         assert_eq!(
-            Value::String { optional: false }.similar(&Value::String { optional: false }),
-            Some(Value::String { optional: false })
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .similar(&Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }),
+            Some(Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            })
         );
         assert_eq!(
-            Value::String { optional: false }.similar(&Value::String { optional: true }),
-            Some(Value::String { optional: true })
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .similar(&Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }),
+            Some(Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            })
         );
         assert_eq!(
-            Value::String { optional: false }.similar(&Value::Number { optional: true }),
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .similar(&Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
             None
         );
     }
@@ -1052,7 +2383,12 @@ mod tests_similar {
             Some(Value::Bool { optional: true })
         );
         assert_eq!(
-            Value::Bool { optional: false }.similar(&Value::Number { optional: true }),
+            Value::Bool { optional: false }.similar(&Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
             None
         );
     }
@@ -1060,15 +2396,53 @@ mod tests_similar {
     #[test]
     fn number_is_similar_to_number() {
         assert_eq!(
-            Value::Number { optional: false }.similar(&Value::Number { optional: false }),
-            Some(Value::Number { optional: false })
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .similar(&Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            Some(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            })
         );
         assert_eq!(
-            Value::Number { optional: false }.similar(&Value::Number { optional: true }),
-            Some(Value::Number { optional: true })
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .similar(&Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            Some(Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            })
         );
         assert_eq!(
-            Value::Number { optional: false }.similar(&Value::Bool { optional: true }),
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .similar(&Value::Bool { optional: true }),
             None
         );
     }
@@ -1078,35 +2452,42 @@ mod tests_similar {
         assert_eq!(
             Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .similar(&Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }),
             Some(Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             })
         );
         assert_eq!(
             Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .similar(&Value::Object {
                 optional: true,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }),
             Some(Value::Object {
                 optional: true,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             })
         );
         assert_eq!(
             Value::Object {
                 optional: false,
-                content: BTreeMap::default()
+                content: BTreeMap::default(),
+                absent: Some(BTreeSet::new())
             }
             .similar(&Value::Bool { optional: true }),
             None
@@ -1118,35 +2499,42 @@ mod tests_similar {
         assert_eq!(
             Value::Array {
                 r#type: Box::new(Value::Null),
-                optional: false
+                optional: false,
+                length: None
             }
             .similar(&Value::Array {
                 r#type: Box::new(Value::Null),
-                optional: false
+                optional: false,
+                length: None
             }),
             Some(Value::Array {
                 r#type: Box::new(Value::Null),
-                optional: false
+                optional: false,
+                length: None
             })
         );
         assert_eq!(
             Value::Array {
                 r#type: Box::new(Value::Null),
-                optional: false
+                optional: false,
+                length: None
             }
             .similar(&Value::Array {
                 r#type: Box::new(Value::Null),
-                optional: true
+                optional: true,
+                length: None
             }),
             Some(Value::Array {
                 r#type: Box::new(Value::Null),
-                optional: true
+                optional: true,
+                length: None
             })
         );
         assert_eq!(
             Value::Array {
                 r#type: Box::new(Value::Null),
-                optional: false
+                optional: false,
+                length: None
             }
             .similar(&Value::Bool { optional: true }),
             None
@@ -0,0 +1,849 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::value::Value;
+
+/// Refinement of a [`JsonShape::Number`](crate::JsonShape::Number) describing which Rust numeric type is able to hold every value seen.
+///
+/// Kinds form a small lattice, widening towards [`NumberKind::Float`]:
+/// - `Unsigned + Signed = Signed`
+/// - `Unsigned + LargeUnsigned = LargeUnsigned`
+/// - `Signed + LargeUnsigned = Float`, as no 64 bits integer can represent both.
+/// - `T + Float = Float`
+#[derive(
+    Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord,
+)]
+pub enum NumberKind {
+    /// Non negative integers that fit in both `i64` and `u64`, eg `42`.
+    Unsigned,
+    /// Integers that fit in `i64`, with at least one negative value, eg `-42`.
+    Signed,
+    /// Non negative integers that only fit in `u64`, eg `18446744073709551615`.
+    LargeUnsigned,
+    /// Any JSON number, including floating point and integers too large for 64 bits.
+    #[default]
+    Float,
+}
+
+impl NumberKind {
+    /// Is this [`NumberKind`] an integer refinement?
+    #[must_use]
+    pub const fn is_integer(&self) -> bool {
+        !matches!(self, Self::Float)
+    }
+
+    /// Widens two [`NumberKind`]s into the narrowest kind able to represent both.
+    #[must_use]
+    pub const fn widen(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Unsigned, Self::Unsigned) => Self::Unsigned,
+            (Self::Unsigned | Self::Signed, Self::Signed) | (Self::Signed, Self::Unsigned) => {
+                Self::Signed
+            }
+            (Self::Unsigned | Self::LargeUnsigned, Self::LargeUnsigned)
+            | (Self::LargeUnsigned, Self::Unsigned) => Self::LargeUnsigned,
+            _ => Self::Float,
+        }
+    }
+
+    /// Narrows two [`NumberKind`]s into the widest kind whose values are representable by both.
+    /// - `Signed * LargeUnsigned = Unsigned`
+    /// - `T * Float = T`
+    #[must_use]
+    pub const fn narrow(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Float, kind) | (kind, Self::Float) => kind,
+            (Self::Signed, Self::Signed) => Self::Signed,
+            (Self::LargeUnsigned, Self::LargeUnsigned) => Self::LargeUnsigned,
+            _ => Self::Unsigned,
+        }
+    }
+
+    /// Checks if every value of `self` is representable by `other`.
+    #[must_use]
+    pub const fn is_subset(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (_, Self::Float)
+                | (Self::Unsigned, _)
+                | (Self::Signed, Self::Signed)
+                | (Self::LargeUnsigned, Self::LargeUnsigned)
+        )
+    }
+}
+
+impl From<&serde_json::Number> for NumberKind {
+    fn from(number: &serde_json::Number) -> Self {
+        match (number.as_i64(), number.as_u64()) {
+            (Some(value), _) if value < 0 => Self::Signed,
+            (Some(_), _) => Self::Unsigned,
+            (None, Some(_)) => Self::LargeUnsigned,
+            (None, None) => Self::Float,
+        }
+    }
+}
+
+impl Display for NumberKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsigned => write!(f, "Integer<unsigned>"),
+            Self::Signed => write!(f, "Integer<signed>"),
+            Self::LargeUnsigned => write!(f, "Integer<u64>"),
+            Self::Float => write!(f, "Number"),
+        }
+    }
+}
+
+/// Range of the values seen by a [`JsonShape::Number`](crate::JsonShape::Number).
+///
+/// Only inferred when enabled by [`InferenceOptions::number_ranges`](crate::InferenceOptions::number_ranges).
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct NumberRange {
+    /// Smallest value seen
+    pub min: serde_json::Number,
+    /// Largest value seen
+    pub max: serde_json::Number,
+    /// If a zero value was seen
+    pub zero: bool,
+}
+
+impl NumberRange {
+    /// Creates the [`NumberRange`] of a single value.
+    #[must_use]
+    pub fn new(number: &serde_json::Number) -> Self {
+        Self {
+            min: number.clone(),
+            max: number.clone(),
+            zero: number.as_f64().is_some_and(|value| value == 0.0),
+        }
+    }
+
+    /// Narrowest [`NumberRange`] containing both ranges.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: if compare_numbers(&other.min, &self.min).is_lt() {
+                other.min.clone()
+            } else {
+                self.min.clone()
+            },
+            max: if compare_numbers(&other.max, &self.max).is_gt() {
+                other.max.clone()
+            } else {
+                self.max.clone()
+            },
+            zero: self.zero || other.zero,
+        }
+    }
+
+    /// Widest [`NumberRange`] contained in both ranges, if they overlap.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = if compare_numbers(&other.min, &self.min).is_gt() {
+            &other.min
+        } else {
+            &self.min
+        };
+        let max = if compare_numbers(&other.max, &self.max).is_lt() {
+            &other.max
+        } else {
+            &self.max
+        };
+        compare_numbers(min, max).is_le().then(|| Self {
+            min: min.clone(),
+            max: max.clone(),
+            zero: self.zero && other.zero,
+        })
+    }
+
+    /// Checks if every value of `other` is within this range.
+    #[must_use]
+    pub fn contains(&self, other: &Self) -> bool {
+        compare_numbers(&self.min, &other.min).is_le()
+            && compare_numbers(&other.max, &self.max).is_le()
+    }
+
+    /// Checks if a negative value was seen.
+    #[must_use]
+    pub fn has_negative(&self) -> bool {
+        compare_numbers(&self.min, &serde_json::Number::from(0)).is_lt()
+    }
+}
+
+impl PartialOrd for NumberRange {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NumberRange {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_numbers(&self.min, &other.min)
+            .then_with(|| self.min.is_f64().cmp(&other.min.is_f64()))
+            .then_with(|| compare_numbers(&self.max, &other.max))
+            .then_with(|| self.max.is_f64().cmp(&other.max.is_f64()))
+            .then_with(|| self.zero.cmp(&other.zero))
+    }
+}
+
+impl Display for NumberRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+/// Compares JSON numbers by value, exact for integers.
+fn compare_numbers(a: &serde_json::Number, b: &serde_json::Number) -> Ordering {
+    let integer = |number: &serde_json::Number| {
+        number
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| number.as_u64().map(i128::from))
+    };
+    match (integer(a), integer(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+    }
+}
+
+/// Bounds of the lengths seen by a [`JsonShape::String`](crate::JsonShape::String), in characters,
+/// or by a [`JsonShape::Array`](crate::JsonShape::Array), in items.
+///
+/// Only inferred when enabled by [`InferenceOptions::length_bounds`](crate::InferenceOptions::length_bounds).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct LengthRange {
+    /// Shortest length seen
+    pub min: usize,
+    /// Longest length seen
+    pub max: usize,
+}
+
+impl LengthRange {
+    /// Creates the [`LengthRange`] of a single length.
+    #[must_use]
+    pub const fn new(length: usize) -> Self {
+        Self {
+            min: length,
+            max: length,
+        }
+    }
+
+    /// Narrowest [`LengthRange`] containing both ranges.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Widest [`LengthRange`] contained in both ranges, if they overlap.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        (min <= max).then_some(Self { min, max })
+    }
+
+    /// Checks if every length of `other` is within this range.
+    #[must_use]
+    pub const fn contains(&self, other: &Self) -> bool {
+        self.min <= other.min && other.max <= self.max
+    }
+}
+
+impl Display for LengthRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+/// Distinct example values seen by a [`JsonShape::Number`](crate::JsonShape::Number) or [`JsonShape::String`](crate::JsonShape::String).
+///
+/// Values are encoded as JSON in the order they were first seen, eg `[42, 7]` or `["alice", "bob"]`.
+/// Only inferred when enabled by [`InferenceOptions::example_limit`](crate::InferenceOptions::example_limit), which bounds how many are kept:
+/// the first distinct values are kept rather than a random sample, so that inferring the same sources always keeps the same examples.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct Examples(Vec<String>);
+
+impl Examples {
+    /// Creates the [`Examples`] of a single JSON value.
+    #[must_use]
+    pub fn new(value: &serde_json::Value) -> Self {
+        Self(vec![value.to_string()])
+    }
+
+    /// Examples of both sets, keeping the ones of `self` first.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut examples = self.0.clone();
+        for example in &other.0 {
+            if !examples.contains(example) {
+                examples.push(example.clone());
+            }
+        }
+        Self(examples)
+    }
+
+    /// Keeps at most the first `limit` examples.
+    #[must_use]
+    pub fn truncate(mut self, limit: usize) -> Self {
+        self.0.truncate(limit);
+        self
+    }
+
+    /// Iterates over the JSON encoded examples.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// Number of examples kept.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if no example was kept.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for Examples {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(", "))
+    }
+}
+
+/// Refinement of a [`JsonShape::String`](crate::JsonShape::String) describing a well known format shared by every value seen.
+///
+/// Detection is conservative, a string only gets a format when it is unambiguous:
+/// - `DateTime`: RFC 3339 date-time, eg `2024-01-31T10:00:00Z`.
+/// - `Date`: RFC 3339 full-date, eg `2024-01-31`.
+/// - `Uuid`: hyphenated UUID, eg `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+/// - `Email`: `local@domain.tld` address, eg `naomi@example.com`.
+/// - `Uri`: URI with an authority, eg `https://example.com/path`.
+/// - `Ipv4`/`Ipv6`: IP addresses, eg `127.0.0.1` and `::1`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum StringFormat {
+    /// RFC 3339 date-time, eg `2024-01-31T10:00:00.123+01:00`.
+    DateTime,
+    /// RFC 3339 full-date, eg `2024-01-31`.
+    Date,
+    /// Hyphenated UUID, eg `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    Uuid,
+    /// Email address, eg `naomi@example.com`.
+    Email,
+    /// URI containing a scheme and an authority, eg `https://example.com`.
+    Uri,
+    /// IPv4 address, eg `127.0.0.1`.
+    Ipv4,
+    /// IPv6 address, eg `::1`.
+    Ipv6,
+}
+
+impl StringFormat {
+    /// Detects the [`StringFormat`] of a JSON string content, if any.
+    #[must_use]
+    pub fn detect(value: &str) -> Option<Self> {
+        if is_uuid(value) {
+            Some(Self::Uuid)
+        } else if is_date_time(value) {
+            Some(Self::DateTime)
+        } else if is_date(value) {
+            Some(Self::Date)
+        } else if value.parse::<std::net::Ipv4Addr>().is_ok() {
+            Some(Self::Ipv4)
+        } else if value.parse::<std::net::Ipv6Addr>().is_ok() {
+            Some(Self::Ipv6)
+        } else if is_email(value) {
+            Some(Self::Email)
+        } else if is_uri(value) {
+            Some(Self::Uri)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for StringFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DateTime => write!(f, "date-time"),
+            Self::Date => write!(f, "date"),
+            Self::Uuid => write!(f, "uuid"),
+            Self::Email => write!(f, "email"),
+            Self::Uri => write!(f, "uri"),
+            Self::Ipv4 => write!(f, "ipv4"),
+            Self::Ipv6 => write!(f, "ipv6"),
+        }
+    }
+}
+
+/// Pattern shared by every key of a [`JsonShape::Map`](crate::JsonShape::Map).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum KeyPattern {
+    /// Integer keys, eg `"123"` or `"-1"`.
+    Integer,
+    /// Keys sharing a [`StringFormat`], eg `"2024-01-31"`.
+    Format(StringFormat),
+}
+
+impl KeyPattern {
+    /// Detects the [`KeyPattern`] shared by all `keys`, if any.
+    #[must_use]
+    pub fn detect<'a>(mut keys: impl Iterator<Item = &'a str>) -> Option<Self> {
+        let first = keys.next()?;
+        let pattern = if is_integer_key(first) {
+            Self::Integer
+        } else {
+            Self::Format(StringFormat::detect(first)?)
+        };
+        keys.all(|key| pattern.matches(key)).then_some(pattern)
+    }
+
+    /// Checks if `key` matches this pattern.
+    #[must_use]
+    pub fn matches(&self, key: &str) -> bool {
+        match self {
+            Self::Integer => is_integer_key(key),
+            Self::Format(format) => StringFormat::detect(key) == Some(*format),
+        }
+    }
+}
+
+impl Display for KeyPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer => write!(f, "Integer"),
+            Self::Format(format) => write!(f, "String<{format}>"),
+        }
+    }
+}
+
+fn is_integer_key(key: &str) -> bool {
+    let digits = key.strip_prefix('-').unwrap_or(key);
+    !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// A single JSON value tracked by a [`JsonShape::Literal`](crate::JsonShape::Literal) union.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+#[serde(untagged)]
+pub enum LiteralValue {
+    /// Integer literal, eg `404`.
+    Integer(i64),
+    /// String literal, eg `"active"`.
+    String(String),
+}
+
+impl LiteralValue {
+    /// Is this [`LiteralValue`] a string?
+    #[must_use]
+    pub const fn is_string(&self) -> bool {
+        matches!(self, Self::String(_))
+    }
+
+    /// Is this [`LiteralValue`] an integer?
+    #[must_use]
+    pub const fn is_integer(&self) -> bool {
+        matches!(self, Self::Integer(_))
+    }
+
+    /// Shape of this value when not tracked as a literal, eg `String<uuid>` or `Integer<signed>`.
+    #[must_use]
+    pub fn shape(&self) -> Value {
+        match self {
+            Self::Integer(value) => Value::Number {
+                optional: false,
+                kind: if *value < 0 {
+                    NumberKind::Signed
+                } else {
+                    NumberKind::Unsigned
+                },
+                range: None,
+                examples: None,
+            },
+            Self::String(value) => Value::String {
+                optional: false,
+                format: StringFormat::detect(value),
+                length: None,
+                examples: None,
+            },
+        }
+    }
+}
+
+impl Display for LiteralValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{}", serde_json::Value::from(value.as_str())),
+        }
+    }
+}
+
+fn digits(value: &str) -> Option<u32> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+fn is_uuid(value: &str) -> bool {
+    let groups = value.split('-').collect::<Vec<_>>();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+fn is_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if !value.is_ascii() || bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    let (Some(year), Some(month), Some(day)) = (
+        digits(&value[..4]),
+        digits(&value[5..7]),
+        digits(&value[8..]),
+    ) else {
+        return false;
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+fn is_time(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if !value.is_ascii() || bytes.len() < 8 || bytes[2] != b':' || bytes[5] != b':' {
+        return false;
+    }
+    let (Some(hour), Some(minute), Some(second)) = (
+        digits(&value[..2]),
+        digits(&value[3..5]),
+        digits(&value[6..8]),
+    ) else {
+        return false;
+    };
+    if hour > 23 || minute > 59 || second > 60 {
+        return false;
+    }
+    let rest = &value[8..];
+    let offset = match rest.strip_prefix('.') {
+        Some(fraction) => {
+            let end = fraction
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(fraction.len());
+            if end == 0 {
+                return false;
+            }
+            &fraction[end..]
+        }
+        None => rest,
+    };
+    match offset {
+        "Z" | "z" => true,
+        _ => {
+            let bytes = offset.as_bytes();
+            bytes.len() == 6
+                && matches!(bytes[0], b'+' | b'-')
+                && bytes[3] == b':'
+                && digits(&offset[1..3]).is_some_and(|hour| hour <= 23)
+                && digits(&offset[4..]).is_some_and(|minute| minute <= 59)
+        }
+    }
+}
+
+fn is_date_time(value: &str) -> bool {
+    value
+        .split_once(['T', 't'])
+        .is_some_and(|(date, time)| is_date(date) && is_time(time))
+}
+
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && local
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~.-".contains(c))
+        && domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+fn is_uri(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once("://") else {
+        return false;
+    };
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !rest.is_empty()
+        && !rest.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn kind(number: &str) -> NumberKind {
+        NumberKind::from(&serde_json::Number::from_str(number).unwrap())
+    }
+
+    #[test]
+    fn classifies_json_numbers() {
+        assert_eq!(kind("0"), NumberKind::Unsigned);
+        assert_eq!(kind("42"), NumberKind::Unsigned);
+        assert_eq!(kind("-42"), NumberKind::Signed);
+        assert_eq!(kind("18446744073709551615"), NumberKind::LargeUnsigned);
+        assert_eq!(kind("18446744073709551616"), NumberKind::Float);
+        assert_eq!(kind("-0"), NumberKind::Float);
+        assert_eq!(kind("1.0"), NumberKind::Float);
+        assert_eq!(kind("1e2"), NumberKind::Float);
+    }
+
+    #[test]
+    fn widen_is_commutative() {
+        let kinds = [
+            NumberKind::Unsigned,
+            NumberKind::Signed,
+            NumberKind::LargeUnsigned,
+            NumberKind::Float,
+        ];
+        for a in kinds {
+            for b in kinds {
+                assert_eq!(a.widen(b), b.widen(a));
+                assert!(a.is_subset(&a.widen(b)));
+                assert!(b.is_subset(&a.widen(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn widen_signed_and_large_unsigned_is_float() {
+        assert_eq!(
+            NumberKind::Signed.widen(NumberKind::LargeUnsigned),
+            NumberKind::Float
+        );
+        assert!(!NumberKind::Signed.is_subset(&NumberKind::LargeUnsigned));
+        assert!(!NumberKind::Float.is_subset(&NumberKind::Signed));
+    }
+
+    #[test]
+    fn narrow_is_commutative() {
+        let kinds = [
+            NumberKind::Unsigned,
+            NumberKind::Signed,
+            NumberKind::LargeUnsigned,
+            NumberKind::Float,
+        ];
+        for a in kinds {
+            for b in kinds {
+                assert_eq!(a.narrow(b), b.narrow(a));
+                assert!(a.narrow(b).is_subset(&a));
+                assert!(a.narrow(b).is_subset(&b));
+            }
+        }
+        assert_eq!(
+            NumberKind::Signed.narrow(NumberKind::LargeUnsigned),
+            NumberKind::Unsigned
+        );
+    }
+
+    #[test]
+    fn range_intersections() {
+        let range = |min: i64, max: i64| NumberRange {
+            min: min.into(),
+            max: max.into(),
+            zero: false,
+        };
+        assert_eq!(range(0, 10).intersection(&range(5, 20)), Some(range(5, 10)));
+        assert_eq!(range(0, 10).intersection(&range(11, 20)), None);
+        assert_eq!(
+            LengthRange { min: 1, max: 3 }.intersection(&LengthRange { min: 3, max: 8 }),
+            Some(LengthRange::new(3))
+        );
+        assert_eq!(LengthRange::new(1).intersection(&LengthRange::new(2)), None);
+    }
+
+    #[test]
+    fn detects_string_formats() {
+        assert_eq!(
+            StringFormat::detect("2024-01-31T10:00:00Z"),
+            Some(StringFormat::DateTime)
+        );
+        assert_eq!(
+            StringFormat::detect("2024-02-29T23:59:60.123+01:00"),
+            Some(StringFormat::DateTime)
+        );
+        assert_eq!(StringFormat::detect("2024-01-31"), Some(StringFormat::Date));
+        assert_eq!(
+            StringFormat::detect("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            Some(StringFormat::Uuid)
+        );
+        assert_eq!(
+            StringFormat::detect("naomi@example.com"),
+            Some(StringFormat::Email)
+        );
+        assert_eq!(
+            StringFormat::detect("https://example.com/path?query=1"),
+            Some(StringFormat::Uri)
+        );
+        assert_eq!(StringFormat::detect("127.0.0.1"), Some(StringFormat::Ipv4));
+        assert_eq!(
+            StringFormat::detect("2001:db8::1"),
+            Some(StringFormat::Ipv6)
+        );
+    }
+
+    #[test]
+    fn does_not_detect_free_text() {
+        for value in [
+            "",
+            "this is a string",
+            "2023-02-29",
+            "2024-13-01",
+            "2024-01-31T25:00:00Z",
+            "2024-01-31T10:00:00",
+            "67e55044-10b1-426f-9247-bb680e5fe0c",
+            "naomi@localhost",
+            "@example.com",
+            "key:value",
+            "https://",
+            "http://with space",
+            "256.0.0.1",
+            "12:30",
+            "2024-01-31T10:00:0é",
+        ] {
+            assert_eq!(StringFormat::detect(value), None, "{value}");
+        }
+    }
+
+    #[test]
+    fn string_format_display() {
+        assert_eq!(StringFormat::DateTime.to_string(), "date-time");
+        assert_eq!(StringFormat::Uuid.to_string(), "uuid");
+        assert_eq!(StringFormat::Ipv6.to_string(), "ipv6");
+    }
+
+    #[test]
+    fn literal_value_display_and_shape() {
+        assert_eq!(
+            LiteralValue::String("a \"b\"".to_string()).to_string(),
+            r#""a \"b\"""#
+        );
+        assert_eq!(LiteralValue::Integer(-3).to_string(), "-3");
+        assert_eq!(
+            LiteralValue::Integer(-3).shape(),
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Signed,
+                range: None,
+                examples: None,
+            }
+        );
+        assert_eq!(
+            LiteralValue::String("2024-01-31".to_string()).shape(),
+            Value::String {
+                optional: false,
+                format: Some(StringFormat::Date),
+                length: None,
+                examples: None
+            }
+        );
+    }
+
+    #[test]
+    fn number_range_union_and_contains() {
+        let number = |value: &str| serde_json::Number::from_str(value).unwrap();
+        let a = NumberRange::new(&number("3"));
+        let b = NumberRange::new(&number("-1.5"));
+        let c = NumberRange::new(&number("0"));
+
+        let range = a.union(&b).union(&c);
+
+        assert_eq!(range.min, number("-1.5"));
+        assert_eq!(range.max, number("3"));
+        assert!(range.zero);
+        assert!(range.has_negative());
+        assert!(range.contains(&a));
+        assert!(!a.contains(&range));
+        assert_eq!(range.to_string(), "-1.5..=3");
+    }
+
+    #[test]
+    fn number_range_compares_large_integers_exactly() {
+        let number = |value: &str| serde_json::Number::from_str(value).unwrap();
+        let range = NumberRange::new(&number("9223372036854775807"))
+            .union(&NumberRange::new(&number("18446744073709551615")));
+
+        assert_eq!(range.min, number("9223372036854775807"));
+        assert_eq!(range.max, number("18446744073709551615"));
+        assert!(!range.has_negative());
+        assert!(!range.zero);
+    }
+
+    #[test]
+    fn length_range_union_and_contains() {
+        let range = LengthRange::new(3).union(&LengthRange::new(1));
+
+        assert_eq!(range, LengthRange { min: 1, max: 3 });
+        assert!(range.contains(&LengthRange::new(2)));
+        assert!(!range.contains(&LengthRange::new(4)));
+        assert_eq!(range.to_string(), "1..=3");
+    }
+
+    #[test]
+    fn examples_union_keeps_first_seen_values() {
+        let example = |value: &str| Examples::new(&serde_json::Value::from(value));
+        let examples = example("b")
+            .union(&example("a"))
+            .union(&example("b").union(&example("c")));
+
+        assert_eq!(
+            examples.iter().collect::<Vec<_>>(),
+            [r#""b""#, r#""a""#, r#""c""#]
+        );
+        assert_eq!(examples.clone().truncate(2).to_string(), r#""b", "a""#);
+        assert_eq!(examples.len(), 3);
+    }
+
+    #[test]
+    fn key_pattern_detection() {
+        assert_eq!(
+            KeyPattern::detect(["123", "-4"].into_iter()),
+            Some(KeyPattern::Integer)
+        );
+        assert_eq!(
+            KeyPattern::detect(["2024-01-31", "2024-02-01"].into_iter()),
+            Some(KeyPattern::Format(StringFormat::Date))
+        );
+        assert_eq!(KeyPattern::detect(["123", "2024-01-31"].into_iter()), None);
+        assert_eq!(KeyPattern::detect(["user_123"].into_iter()), None);
+        assert_eq!(KeyPattern::detect([].into_iter()), None);
+        assert_eq!(
+            KeyPattern::Format(StringFormat::Uuid).to_string(),
+            "String<uuid>"
+        );
+    }
+}
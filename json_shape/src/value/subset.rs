@@ -1,51 +1,107 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use crate::value::subtypes::IsOneOf;
 use crate::{
     IsSubset,
+    shape::recursion::{rename, unfold},
     value::{
-        Value,
-        subtypes::{Boolean, Number, Optional, String as Str},
+        KeyPattern, LengthRange, LiteralValue, NumberKind, NumberRange, StringFormat, Value,
+        absent_keys,
+        subtypes::{Boolean, Optional},
     },
 };
 
 /// - `JsonShape::Number` is subset of `JsonShape::Option<Number>`
 /// - `JsonShape::Null` is subset of `JsonShape::Option<Number>` and  `JsonShape::Null`
 /// - `JsonShape::Number` is subset of `JsonShape::OneOf[Number | String]`
+/// - `JsonShape::Integer<unsigned>` is subset of `JsonShape::Integer<signed>` and `JsonShape::Number`
+/// - `JsonShape::Integer[1..=2]` is subset of `JsonShape::Integer[0..=10]`, but `JsonShape::Integer[0..=20]` is *NOT*
+/// - `JsonShape::Literal["a"]` is subset of `JsonShape::Literal["a" | "b"]` and `JsonShape::String`
+/// - `JsonShape::String<uuid>` is subset of `JsonShape::String`, but `JsonShape::String` is *NOT* subset of `JsonShape::String<uuid>`
 /// - `JsonShape::Number` is *NOT* subset of `JsonShape::Array<Number>` => `1.23 != [1.23]`
 /// - `JsonShape::Array<Number>` is subset of `JsonShape::Array<OnOf<[Number | Boolean]>>`
+/// - `JsonShape::Array<Unknown>` is subset of any `JsonShape::Array<T>` => `[]` is a valid `Vec<T>`
 /// - `JsonShape::Object{"key_a": JsonShape::Number}` is *NOT* subset of `JsonShape::Object{"key_b": JsonShape::Number}` => `key_a != key_b`
 /// - `JsonShape::Object{"key_a": JsonShape::Number}` is subset of `JsonShape::Object{"key_a": JsonShape::Option<Number>}`
+/// - `JsonShape::Object{}` is subset of `JsonShape::Object{"key_a"?: JsonShape::Number}`, but *NOT* of `JsonShape::Object{"key_a": JsonShape::Option<Number>}` => absent `!=` null
 /// - `JsonShape::Object{"key_a": JsonShape::Number}` is subset of `JsonShape::Object{"key_a": JsonShape::OneOf[Number | Boolean]}`
+/// - `JsonShape::Object{"1": JsonShape::Number}` is subset of `JsonShape::Map<Integer, Number>`
+/// - `JsonShape::Object{"type": JsonShape::Literal["a"], "b": JsonShape::Number}` is subset of `JsonShape::Tagged<type>["a": Object{"b": Number}]`
+/// - `JsonShape::Object{"replies": JsonShape::Array<Object{"replies": Array<Null>}>}` is subset of `JsonShape::Recursive<Root: Object{"replies": Array<Option<Ref<Root>>>}>`
 impl IsSubset for Value {
     #[allow(clippy::too_many_lines)]
     /// Checks if [`JsonShape`] is subset of `other` [`JsonShape`]
     fn is_subset(&self, other: &Self) -> bool {
-        match self {
-            Self::Null => other.is_optional() || other.is_null(),
-            // Optionals
-            Self::Bool { optional: true } => {
-                other.is_boolean() && other.is_optional()
-                    || IsOneOf::<Optional<Boolean>>::is_one_of(other)
+        if let Self::Recursive { name, shape } = other
+            && !self.is_recursive()
+        {
+            return self.is_subset(&unfold(name, shape));
+        }
+        match (self, other) {
+            // `OneOf[T | U]` fits when both `T` and `U` fit
+            (Self::OneOf { variants, optional }, _) => {
+                return (!optional || Self::Null.is_subset(other))
+                    && variants.iter().all(|variant| variant.is_subset(other));
             }
-            Self::Number { optional: true } => {
-                other.is_number() && other.is_optional()
-                    || IsOneOf::<Optional<Number>>::is_one_of(other)
+            // `Option<T>` fits in `OneOf[Null | T | U]`, as normalized shapes keep `Null` as a variant
+            (_, Self::OneOf { .. }) if self.is_optional() && !self.is_null() => {
+                return Self::Null.is_subset(other)
+                    && self.clone().as_non_optional().is_subset(other);
             }
-            Self::String { optional: true } => {
-                other.is_string() && other.is_optional()
-                    || IsOneOf::<Optional<Str>>::is_one_of(other)
+            (_, Self::OneOf { variants, .. })
+                if variants.iter().any(|variant| self.is_subset(variant)) =>
+            {
+                return true;
             }
+            _ => (),
+        }
+        match self {
+            Self::Null => other.is_optional() || other.is_null(),
+            // The element of an empty array could be of any type
+            Self::Unknown => true,
+            // Optionals
+            Self::Bool { optional: true } => other.is_boolean() && other.is_optional(),
+            Self::Number {
+                optional: true,
+                kind,
+                range,
+                ..
+            } => match other {
+                Self::Number {
+                    optional: true,
+                    kind: other_kind,
+                    range: other_range,
+                    ..
+                } => is_number_subset(*kind, range.as_ref(), *other_kind, other_range.as_ref()),
+                _ => false,
+            },
+            Self::String {
+                optional: true,
+                format,
+                length,
+                ..
+            } => match other {
+                Self::String {
+                    optional: true,
+                    format: other_format,
+                    length: other_length,
+                    ..
+                } => {
+                    is_format_subset(*format, *other_format)
+                        && is_length_subset(length.as_ref(), other_length.as_ref())
+                }
+                _ => false,
+            },
             Self::Array {
                 r#type,
                 optional: true,
+                length,
             } => match other {
                 Self::Array {
                     r#type: ty,
                     optional: true,
-                } => r#type.is_subset(ty),
-                Self::OneOf { variants, .. } => variants.contains(&Self::Array {
-                    r#type: r#type.clone(),
-                    optional: true,
-                }),
+                    length: other_length,
+                } => r#type.is_subset(ty) && is_length_subset(length.as_ref(), other_length.as_ref()),
                 _ => false,
             },
             Self::Tuple {
@@ -59,56 +115,36 @@ impl IsSubset for Value {
                     elements.iter().zip(other).all(|(a, b)| a.is_subset(b))
                         && elements.len() == other.len()
                 }
-                Self::OneOf { variants, .. } => variants.contains(&Self::Tuple {
-                    elements: elements.clone(),
+                Self::Array {
+                    r#type,
+                    length,
                     optional: true,
-                }),
-                Self::Array { r#type, .. } => {
-                    let Self::OneOf { variants, .. } = &&**r#type else {
-                        return false;
-                    };
-                    elements.iter().all(|element| variants.contains(element))
+                } => {
+                    elements.iter().all(|element| element.is_subset(r#type))
+                        && is_length_subset(Some(&LengthRange::new(elements.len())), length.as_ref())
                 }
                 _ => false,
             },
             Self::Object {
                 content,
                 optional: true,
+                absent,
             } => match other {
                 Self::Object {
                     content: other,
                     optional: true,
-                } => {
-                    for (k, v) in other {
-                        if !content.contains_key(k) && !v.is_optional() {
-                            return false;
-                        }
-                    }
-                    content.iter().all(|(key, value)| {
-                        other
-                            .get(key)
-                            .is_some_and(|other_val| value.is_subset(other_val))
-                    })
-                }
-                Self::OneOf { variants, .. } => variants
-                    .iter()
-                    .filter(|var| matches!(var, Self::Object { .. }))
-                    .any(|var| self.is_subset(var)),
-                _ => false,
-            },
-            Self::OneOf {
-                variants,
-                optional: true,
-            } => match other {
-                Self::OneOf {
-                    variants: var,
+                    absent: other_absent,
+                } => is_object_content_subset(content, absent.as_ref(), other, other_absent.as_ref()),
+                Self::Map {
+                    r#type,
+                    key,
                     optional: true,
-                } => {
-                    variants.is_subset(var)
-                        || variants
-                            .iter()
-                            .all(|variant| var.iter().any(|v| variant.is_subset(v)))
-                }
+                } => is_map_content_subset(content, r#type, *key),
+                Self::Tagged {
+                    tag,
+                    variants,
+                    optional: true,
+                } => is_tagged_content_subset(content, absent.as_ref(), tag, variants),
                 _ => false,
             },
 
@@ -118,30 +154,54 @@ impl IsSubset for Value {
                     || IsOneOf::<Boolean>::is_one_of(other)
                     || IsOneOf::<Optional<Boolean>>::is_one_of(other)
             }
-            Self::Number { optional: false } => {
-                other.is_number()
-                    || IsOneOf::<Number>::is_one_of(other)
-                    || IsOneOf::<Optional<Number>>::is_one_of(other)
-            }
-            Self::String { optional: false } => {
-                other.is_string()
-                    || IsOneOf::<Str>::is_one_of(other)
-                    || IsOneOf::<Optional<Str>>::is_one_of(other)
-            }
+            Self::Number {
+                optional: false,
+                kind,
+                range,
+                ..
+            } => match other {
+                Self::Number {
+                    kind: other_kind,
+                    range: other_range,
+                    ..
+                } => is_number_subset(*kind, range.as_ref(), *other_kind, other_range.as_ref()),
+                Self::OneOf { variants, .. } => variants.iter().any(|variant| {
+                    matches!(variant, Self::Number { kind: other_kind, range: other_range, .. } if is_number_subset(*kind, range.as_ref(), *other_kind, other_range.as_ref()))
+                }),
+                _ => false,
+            },
+            Self::String {
+                optional: false,
+                format,
+                length,
+                ..
+            } => match other {
+                Self::String {
+                    format: other_format,
+                    length: other_length,
+                    ..
+                } => {
+                    is_format_subset(*format, *other_format)
+                        && is_length_subset(length.as_ref(), other_length.as_ref())
+                }
+                Self::OneOf { variants, .. } => variants.iter().any(|variant| {
+                    matches!(variant, Self::String { format: other_format, length: other_length, .. } if is_format_subset(*format, *other_format) && is_length_subset(length.as_ref(), other_length.as_ref()))
+                }),
+                _ => false,
+            },
             Self::Array {
                 r#type,
                 optional: false,
+                length,
             } => match other {
-                Self::Array { r#type: ty, .. } => r#type.is_subset(ty),
-                Self::OneOf { variants, .. } => {
-                    variants.contains(&Self::Array {
-                        r#type: r#type.clone(),
-                        optional: false,
-                    }) || variants.contains(&Self::Array {
-                        r#type: r#type.clone(),
-                        optional: true,
-                    })
-                }
+                Self::Array {
+                    r#type: ty,
+                    length: other_length,
+                    ..
+                } => r#type.is_subset(ty) && is_length_subset(length.as_ref(), other_length.as_ref()),
+                Self::OneOf { variants, .. } => variants.iter().any(|variant| {
+                    matches!(variant, Self::Array { r#type: ty, length: other_length, .. } if r#type.is_subset(ty) && is_length_subset(length.as_ref(), other_length.as_ref()))
+                }),
                 _ => false,
             },
             Self::Tuple {
@@ -154,61 +214,255 @@ impl IsSubset for Value {
                     elements.iter().zip(other).all(|(a, b)| a.is_subset(b))
                         && elements.len() == other.len()
                 }
-                Self::OneOf { variants, .. } => {
-                    variants.contains(&Self::Tuple {
-                        elements: elements.clone(),
-                        optional: false,
-                    }) || variants.contains(&Self::Tuple {
-                        elements: elements.clone(),
-                        optional: true,
-                    })
-                }
-                Self::Array { r#type, .. } => {
-                    let Self::OneOf { variants, .. } = &&**r#type else {
-                        return false;
-                    };
-                    elements.iter().all(|element| variants.contains(element))
+                Self::OneOf { variants, .. } => variants
+                    .iter()
+                    .any(|variant| variant.is_tuple() && self.is_subset(variant)),
+                Self::Array { r#type, length, .. } => {
+                    elements.iter().all(|element| element.is_subset(r#type))
+                        && is_length_subset(Some(&LengthRange::new(elements.len())), length.as_ref())
                 }
                 _ => false,
             },
             Self::Object {
                 content,
                 optional: false,
+                absent,
             } => match other {
-                Self::Object { content: other, .. } => {
-                    for (k, v) in other {
-                        if !content.contains_key(k) && !v.is_optional() {
-                            return false;
-                        }
-                    }
-                    content.iter().all(|(key, value)| {
-                        other
-                            .get(key)
-                            .is_some_and(|other_val| value.is_subset(other_val))
-                    })
+                Self::Object {
+                    content: other,
+                    absent: other_absent,
+                    ..
+                } => is_object_content_subset(content, absent.as_ref(), other, other_absent.as_ref()),
+                Self::Map { r#type, key, .. } => is_map_content_subset(content, r#type, *key),
+                Self::Tagged { tag, variants, .. } => {
+                    is_tagged_content_subset(content, absent.as_ref(), tag, variants)
                 }
                 Self::OneOf { variants, .. } => variants
                     .iter()
-                    .filter(|var| matches!(var, Self::Object { .. }))
+                    .filter(|var| {
+                        matches!(
+                            var,
+                            Self::Object { .. }
+                                | Self::Map { .. }
+                                | Self::Recursive { .. }
+                                | Self::Tagged { .. }
+                        )
+                    })
                     .any(|var| self.is_subset(var)),
                 _ => false,
             },
-            Self::OneOf {
-                variants,
-                optional: false,
+            Self::OneOf { .. } => unreachable!("unions are checked variant by variant above"),
+
+            // Literals
+            Self::Literal { values, optional } => match other {
+                Self::Literal {
+                    values: other_values,
+                    optional: other_opt,
+                } => (!optional || *other_opt) && values.is_subset(other_values),
+                Self::OneOf {
+                    variants,
+                    optional: other_opt,
+                } if variants.iter().any(|variant| {
+                    matches!(variant, Self::Literal { values: other_values, .. } if values.is_subset(other_values))
+                }) =>
+                {
+                    !optional || *other_opt || variants.contains(&Self::Null)
+                }
+                _ => self.clone().widen_literal().is_subset(other),
+            },
+
+            // Maps
+            Self::Map {
+                r#type,
+                key,
+                optional,
             } => match other {
-                Self::OneOf { variants: var, .. } => {
-                    variants.is_subset(var)
-                        || variants
-                            .iter()
-                            .all(|variant| var.iter().any(|v| variant.is_subset(v)))
+                Self::Map {
+                    r#type: ty,
+                    key: other_key,
+                    optional: other_opt,
+                } => {
+                    (!optional || *other_opt)
+                        && (other_key.is_none() || key == other_key)
+                        && r#type.is_subset(ty)
+                }
+                Self::OneOf {
+                    variants,
+                    optional: other_opt,
+                } => {
+                    (!optional || *other_opt || variants.contains(&Self::Null))
+                        && variants.iter().any(|variant| {
+                            variant.is_map() && self.clone().as_non_optional().is_subset(variant)
+                        })
+                }
+                _ => false,
+            },
+
+            // Recursive
+            Self::Recursive { name, shape } => match other {
+                Self::Recursive {
+                    name: other_name,
+                    shape: other_shape,
+                } => shape.is_subset(&rename((**other_shape).clone(), other_name, name)),
+                _ => unfold(name, shape).is_subset(other),
+            },
+            Self::Ref { name, optional } => match other {
+                Self::Ref {
+                    name: other_name,
+                    optional: other_opt,
+                } => name == other_name && (!optional || *other_opt),
+                Self::OneOf {
+                    variants,
+                    optional: other_opt,
+                } => {
+                    (!optional || *other_opt || variants.contains(&Self::Null))
+                        && variants.iter().any(|variant| {
+                            variant.is_ref() && self.clone().as_non_optional().is_subset(variant)
+                        })
                 }
                 _ => false,
             },
+
+            // Tagged unions
+            Self::Tagged {
+                tag,
+                variants,
+                optional,
+            } => match other {
+                Self::Tagged {
+                    tag: other_tag,
+                    variants: other,
+                    optional: other_opt,
+                } if tag == other_tag => {
+                    (!optional || *other_opt)
+                        && variants.iter().all(|(value, variant)| {
+                            other
+                                .get(value)
+                                .is_some_and(|other| variant.is_subset(other))
+                        })
+                }
+                Self::OneOf {
+                    variants: other,
+                    optional: other_opt,
+                } => {
+                    (!optional || *other_opt || other.contains(&Self::Null))
+                        && other.iter().any(|variant| {
+                            (variant.is_tagged() || variant.is_object())
+                                && self.clone().as_non_optional().is_subset(variant)
+                        })
+                }
+                _ => variants.iter().all(|(value, variant)| match variant {
+                    Self::Object {
+                        content, absent, ..
+                    } => {
+                        let mut content = content.clone();
+                        content.insert(
+                            tag.clone(),
+                            Self::Literal {
+                                values: [LiteralValue::String(value.clone())].into(),
+                                optional: false,
+                            },
+                        );
+                        Self::Object {
+                            content,
+                            optional: *optional,
+                            absent: absent.clone(),
+                        }
+                        .is_subset(other)
+                    }
+                    _ => false,
+                }),
+            },
         }
     }
 }
 
+/// `Object{a: T, b?: U}` fits in `Object{a: T, b?: U, c?: V}`: every key of `content` is a key of `other`,
+/// and keys missing from `content` or absent in it must be absent in `other`.
+fn is_object_content_subset(
+    content: &BTreeMap<String, Value>,
+    absent: Option<&BTreeSet<String>>,
+    other: &BTreeMap<String, Value>,
+    other_absent: Option<&BTreeSet<String>>,
+) -> bool {
+    let absent = absent_keys(content, absent);
+    let other_absent = absent_keys(other, other_absent);
+    other
+        .keys()
+        .all(|key| content.contains_key(key) || other_absent.contains(key))
+        && content.iter().all(|(key, value)| {
+            other
+                .get(key)
+                .is_some_and(|other_value| value.is_subset(other_value))
+                && (!absent.contains(key) || other_absent.contains(key))
+        })
+}
+
+/// `Object{"1": T, "2": T}` fits in both `Map<Integer, T>` and `Map<String, T>`.
+fn is_map_content_subset(
+    content: &BTreeMap<String, Value>,
+    r#type: &Value,
+    key: Option<KeyPattern>,
+) -> bool {
+    content
+        .iter()
+        .all(|(name, value)| key.is_none_or(|key| key.matches(name)) && value.is_subset(r#type))
+}
+
+/// `Object{type: Literal["a"], b: T}` fits in `Tagged<type>["a": Object{b: T}]`, each value of the tag fitting in the object of its variant.
+/// An object with a `String` tag doesn't fit, as its tag could be any value.
+fn is_tagged_content_subset(
+    content: &BTreeMap<String, Value>,
+    absent: Option<&BTreeSet<String>>,
+    tag: &str,
+    variants: &BTreeMap<String, Value>,
+) -> bool {
+    let absent = absent_keys(content, absent).into_owned();
+    let mut content = content.clone();
+    let Some(value) = content.remove(tag).filter(|_| !absent.contains(tag)) else {
+        return false;
+    };
+    let object = Value::Object {
+        content,
+        optional: false,
+        absent: Some(absent),
+    };
+    match value {
+        Value::Literal {
+            values,
+            optional: false,
+        } => values.iter().all(|value| {
+            matches!(value, LiteralValue::String(value) if variants.get(value).is_some_and(|variant| object.is_subset(variant)))
+        }),
+        _ => false,
+    }
+}
+
+/// `Integer[1..=2]` fits in both `Integer[0..=10]` and `Integer`. A number without range is assumed to fit any range.
+fn is_number_subset(
+    kind: NumberKind,
+    range: Option<&NumberRange>,
+    other_kind: NumberKind,
+    other_range: Option<&NumberRange>,
+) -> bool {
+    kind.is_subset(&other_kind)
+        && range
+            .zip(other_range)
+            .is_none_or(|(range, other)| other.contains(range))
+}
+
+/// `Array<T>[1..=2]` fits in both `Array<T>[0..=10]` and `Array<T>`. A value without length bounds is assumed to fit any bounds.
+fn is_length_subset(length: Option<&LengthRange>, other: Option<&LengthRange>) -> bool {
+    length
+        .zip(other)
+        .is_none_or(|(length, other)| other.contains(length))
+}
+
+/// `String<uuid>` fits in both `String<uuid>` and `String`, while `String` only fits in `String`.
+fn is_format_subset(format: Option<StringFormat>, other: Option<StringFormat>) -> bool {
+    other.is_none() || format == other
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,12 +477,67 @@ mod tests {
 
         #[test]
         fn when_null_is_subset_of_optional() {
-            assert!(Value::Null.is_subset(&Value::Number { optional: true }));
+            assert!(Value::Null.is_subset(&Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }));
         }
 
         #[test]
         fn when_null_is_not_subset_of_number() {
-            assert!(!Value::Null.is_subset(&Value::Number { optional: false }));
+            assert!(!Value::Null.is_subset(&Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }));
+        }
+    }
+
+    mod unknown {
+        use super::*;
+
+        #[test]
+        fn when_array_unknown_is_subset_of_any_array() {
+            let empty = Value::Array {
+                r#type: Box::new(Value::Unknown),
+                optional: false,
+                length: None,
+            };
+
+            assert!(empty.is_subset(&Value::Array {
+                r#type: Box::new(Value::Bool { optional: false }),
+                optional: false,
+                length: None
+            }));
+            assert!(empty.is_subset(&Value::Array {
+                r#type: Box::new(Value::Unknown),
+                optional: false,
+                length: None
+            }));
+        }
+
+        #[test]
+        fn when_array_number_is_not_subset_of_array_unknown() {
+            assert!(
+                !Value::Array {
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
+                }
+                .is_subset(&Value::Array {
+                    r#type: Box::new(Value::Unknown),
+                    optional: false,
+                    length: None
+                })
+            );
         }
     }
 
@@ -238,115 +547,853 @@ mod tests {
         #[test]
         fn when_number_is_subset_of_number() {
             assert!(
-                Value::Number { optional: false }.is_subset(&Value::Number { optional: false })
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                })
             );
         }
 
         #[test]
         fn when_number_is_subset_of_optional_number() {
-            assert!(Value::Number { optional: false }.is_subset(&Value::Number { optional: true }));
+            assert!(
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                })
+            );
         }
 
         #[test]
         fn when_optional_number_is_subset_of_optional_number() {
-            assert!(Value::Number { optional: true }.is_subset(&Value::Number { optional: true }));
+            assert!(
+                Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                })
+            );
         }
 
         #[test]
         fn when_optional_number_is_not_subset_of_number() {
             assert!(
-                !Value::Number { optional: true }.is_subset(&Value::Number { optional: false })
+                !Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                })
             );
         }
 
         #[test]
         fn when_number_is_not_subset_of_string() {
             assert!(
-                !Value::Number { optional: false }.is_subset(&Value::String { optional: false })
+                !Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                })
             );
         }
 
         #[test]
         fn when_number_is_subset_of_oneof_with_number_variant() {
-            assert!(Value::Number { optional: false }.is_subset(&Value::OneOf {
-                variants: [Value::Number { optional: false }, Value::Null].into(),
-                optional: false
-            }));
+            assert!(
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::OneOf {
+                    variants: [
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        },
+                        Value::Null
+                    ]
+                    .into(),
+                    optional: false
+                })
+            );
         }
 
         #[test]
         fn when_number_is_subset_of_oneof_with_optional_number_variant() {
-            assert!(Value::Number { optional: false }.is_subset(&Value::OneOf {
-                variants: [Value::Number { optional: true }, Value::Null].into(),
-                optional: false
-            }));
+            assert!(
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::OneOf {
+                    variants: [
+                        Value::Number {
+                            optional: true,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        },
+                        Value::Null
+                    ]
+                    .into(),
+                    optional: false
+                })
+            );
         }
     }
 
-    mod string {
+    mod integer {
         use super::*;
 
         #[test]
-        fn when_string_is_subset_of_string() {
+        fn when_integer_is_subset_of_number() {
             assert!(
-                Value::String { optional: false }.is_subset(&Value::String { optional: false })
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                })
             );
         }
 
         #[test]
-        fn when_string_is_subset_of_optional_string() {
-            assert!(Value::String { optional: false }.is_subset(&Value::String { optional: true }));
-        }
-
-        #[test]
-        fn when_optional_string_is_subset_of_optional_string() {
-            assert!(Value::String { optional: true }.is_subset(&Value::String { optional: true }));
-        }
-
-        #[test]
-        fn when_optional_string_is_not_subset_of_string() {
+        fn when_unsigned_integer_is_subset_of_signed_integer() {
             assert!(
-                !Value::String { optional: true }.is_subset(&Value::String { optional: false })
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::Number {
+                    optional: true,
+                    kind: NumberKind::Signed,
+                    range: None,
+                    examples: None,
+                })
             );
         }
 
         #[test]
-        fn when_string_is_not_subset_of_string() {
+        fn when_number_is_not_subset_of_integer() {
             assert!(
-                !Value::String { optional: false }.is_subset(&Value::Number { optional: false })
+                !Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::Number {
+                    optional: false,
+                    kind: NumberKind::Signed,
+                    range: None,
+                    examples: None,
+                })
             );
         }
 
         #[test]
-        fn when_string_is_subset_of_oneof_with_string_variant() {
-            assert!(Value::String { optional: false }.is_subset(&Value::OneOf {
-                variants: [Value::String { optional: false }, Value::Null].into(),
-                optional: false
-            }));
+        fn when_signed_integer_is_not_subset_of_large_unsigned_integer() {
+            assert!(
+                !Value::Number {
+                    optional: false,
+                    kind: NumberKind::Signed,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::Number {
+                    optional: false,
+                    kind: NumberKind::LargeUnsigned,
+                    range: None,
+                    examples: None,
+                })
+            );
         }
 
         #[test]
-        fn when_string_is_subset_of_oneof_with_optional_string_variant() {
-            assert!(Value::String { optional: false }.is_subset(&Value::OneOf {
-                variants: [Value::String { optional: true }, Value::Null].into(),
-                optional: false
-            }));
+        fn when_optional_integer_is_subset_of_oneof_with_number_and_null() {
+            assert!(
+                Value::Number {
+                    optional: true,
+                    kind: NumberKind::Signed,
+                    range: None,
+                    examples: None,
+                }
+                .is_subset(&Value::OneOf {
+                    variants: [
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        },
+                        Value::Null
+                    ]
+                    .into(),
+                    optional: false
+                })
+            );
         }
     }
 
-    mod bool {
+    mod string_format {
         use super::*;
 
         #[test]
-        fn when_bool_is_subset_of_bool() {
-            assert!(Value::Bool { optional: false }.is_subset(&Value::Bool { optional: false }));
+        fn when_formatted_string_is_subset_of_string() {
+            assert!(
+                Value::String {
+                    optional: false,
+                    format: Some(StringFormat::Uuid),
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None
+                })
+            );
         }
 
         #[test]
-        fn when_bool_is_subset_of_optional_bool() {
-            assert!(Value::Bool { optional: false }.is_subset(&Value::Bool { optional: true }));
-        }
-
+        fn when_string_is_not_subset_of_formatted_string() {
+            assert!(
+                !Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::String {
+                    optional: false,
+                    format: Some(StringFormat::Uuid),
+                    length: None,
+                    examples: None
+                })
+            );
+        }
+
+        #[test]
+        fn when_formats_differ_is_not_subset() {
+            assert!(
+                !Value::String {
+                    optional: true,
+                    format: Some(StringFormat::Date),
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::OneOf {
+                    variants: [
+                        Value::String {
+                            optional: false,
+                            format: Some(StringFormat::DateTime),
+                            length: None,
+                            examples: None
+                        },
+                        Value::Null
+                    ]
+                    .into(),
+                    optional: false
+                })
+            );
+        }
+    }
+
+    mod number_range {
+        use super::*;
+
+        fn ranged(min: i64, max: i64) -> Value {
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Signed,
+                range: Some(NumberRange::new(&min.into()).union(&NumberRange::new(&max.into()))),
+                examples: None,
+            }
+        }
+
+        #[test]
+        fn when_range_is_within_other_range_is_subset() {
+            assert!(ranged(0, 10).is_subset(&ranged(-5, 10)));
+        }
+
+        #[test]
+        fn when_range_exceeds_other_range_is_not_subset() {
+            assert!(!ranged(0, 11).is_subset(&ranged(-5, 10)));
+        }
+
+        #[test]
+        fn when_either_range_is_unknown_is_subset() {
+            let unranged = Value::Number {
+                optional: false,
+                kind: NumberKind::Signed,
+                range: None,
+                examples: None,
+            };
+
+            assert!(ranged(0, 11).is_subset(&unranged));
+            assert!(unranged.is_subset(&ranged(0, 11)));
+        }
+    }
+
+    mod length_bounds {
+        use super::*;
+
+        fn bounded_array(min: usize, max: usize) -> Value {
+            Value::Array {
+                r#type: Box::new(Value::Null),
+                optional: false,
+                length: Some(LengthRange { min, max }),
+            }
+        }
+
+        #[test]
+        fn when_length_is_within_other_bounds_is_subset() {
+            assert!(bounded_array(1, 2).is_subset(&bounded_array(0, 2)));
+            assert!(
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: Some(LengthRange::new(3)),
+                    examples: None,
+                }
+                .is_subset(&Value::String {
+                    optional: true,
+                    format: None,
+                    length: Some(LengthRange { min: 1, max: 3 }),
+                    examples: None,
+                })
+            );
+        }
+
+        #[test]
+        fn when_length_exceeds_other_bounds_is_not_subset() {
+            assert!(!bounded_array(1, 3).is_subset(&bounded_array(0, 2)));
+            assert!(
+                !Value::Tuple {
+                    elements: vec![Value::Null, Value::Null, Value::Null],
+                    optional: false,
+                }
+                .is_subset(&Value::Array {
+                    r#type: Box::new(Value::OneOf {
+                        variants: [Value::Null].into(),
+                        optional: false,
+                    }),
+                    optional: false,
+                    length: Some(LengthRange { min: 0, max: 2 }),
+                })
+            );
+        }
+
+        #[test]
+        fn when_either_length_is_unknown_is_subset() {
+            let unbounded = Value::Array {
+                r#type: Box::new(Value::Null),
+                optional: false,
+                length: None,
+            };
+
+            assert!(bounded_array(5, 5).is_subset(&unbounded));
+            assert!(unbounded.is_subset(&bounded_array(0, 0)));
+        }
+    }
+
+    mod map {
+        use super::*;
+
+        fn map(key: Option<KeyPattern>, optional: bool) -> Value {
+            Value::Map {
+                r#type: Box::new(Value::Bool { optional: false }),
+                key,
+                optional,
+            }
+        }
+
+        #[test]
+        fn when_object_values_fit_map_is_subset() {
+            let object = Value::Object {
+                content: [("1".to_string(), Value::Bool { optional: false })].into(),
+                optional: false,
+                absent: Some(BTreeSet::new()),
+            };
+
+            assert!(object.is_subset(&map(Some(KeyPattern::Integer), false)));
+            assert!(object.is_subset(&map(None, true)));
+        }
+
+        #[test]
+        fn when_object_keys_do_not_match_pattern_is_not_subset() {
+            let object = Value::Object {
+                content: [("a".to_string(), Value::Bool { optional: false })].into(),
+                optional: false,
+                absent: Some(BTreeSet::new()),
+            };
+
+            assert!(!object.is_subset(&map(Some(KeyPattern::Integer), false)));
+        }
+
+        #[test]
+        fn when_map_fits_other_map_is_subset() {
+            assert!(map(Some(KeyPattern::Integer), false).is_subset(&map(None, false)));
+            assert!(!map(None, false).is_subset(&map(Some(KeyPattern::Integer), false)));
+            assert!(!map(None, true).is_subset(&map(None, false)));
+            assert!(map(None, true).is_subset(&Value::OneOf {
+                variants: [map(None, false), Value::Null].into(),
+                optional: false,
+            }));
+        }
+    }
+
+    mod recursive {
+        use super::*;
+
+        /// `Recursive<Node: Object{next: Option<Ref<Node>>, value: Boolean}>`
+        fn list(name: &str) -> Value {
+            Value::Recursive {
+                name: name.to_string(),
+                shape: Box::new(Value::Object {
+                    content: [
+                        (
+                            "next".to_string(),
+                            Value::Ref {
+                                name: name.to_string(),
+                                optional: true,
+                            },
+                        ),
+                        ("value".to_string(), Value::Bool { optional: false }),
+                    ]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new()),
+                }),
+            }
+        }
+
+        fn node(next: Value) -> Value {
+            Value::Object {
+                content: [
+                    ("next".to_string(), next),
+                    ("value".to_string(), Value::Bool { optional: false }),
+                ]
+                .into(),
+                optional: false,
+                absent: Some(BTreeSet::new()),
+            }
+        }
+
+        #[test]
+        fn when_object_unfolds_into_definition_is_subset() {
+            assert!(node(Value::Null).is_subset(&list("Node")));
+            assert!(node(node(node(Value::Null))).is_subset(&list("Node")));
+            assert!(!node(Value::Bool { optional: false }).is_subset(&list("Node")));
+        }
+
+        #[test]
+        fn when_definitions_match_recursive_is_subset() {
+            assert!(list("Node").is_subset(&list("Node")));
+            assert!(list("Node").is_subset(&list("List")));
+            assert!(!list("Node").is_subset(&node(Value::Null)));
+        }
+
+        #[test]
+        fn ref_is_subset_of_same_ref() {
+            let reference = |name: &str, optional| Value::Ref {
+                name: name.to_string(),
+                optional,
+            };
+
+            assert!(reference("Node", false).is_subset(&reference("Node", true)));
+            assert!(!reference("Node", true).is_subset(&reference("Node", false)));
+            assert!(!reference("Node", false).is_subset(&reference("List", false)));
+        }
+    }
+
+    mod tagged {
+        use super::*;
+
+        /// `Tagged<type>["a": Object{x: Boolean} | "b": Object{}]`
+        fn tagged(optional: bool) -> Value {
+            Value::Tagged {
+                tag: "type".to_string(),
+                variants: [
+                    ("a".to_string(), object("x")),
+                    (
+                        "b".to_string(),
+                        Value::Object {
+                            content: BTreeMap::default(),
+                            optional: false,
+                            absent: Some(BTreeSet::new()),
+                        },
+                    ),
+                ]
+                .into(),
+                optional,
+            }
+        }
+
+        fn object(key: &str) -> Value {
+            Value::Object {
+                content: [(key.to_string(), Value::Bool { optional: false })].into(),
+                optional: false,
+                absent: Some(BTreeSet::new()),
+            }
+        }
+
+        fn event(tag: Value, key: &str) -> Value {
+            Value::Object {
+                content: [
+                    ("type".to_string(), tag),
+                    (key.to_string(), Value::Bool { optional: false }),
+                ]
+                .into(),
+                optional: false,
+                absent: Some(BTreeSet::new()),
+            }
+        }
+
+        fn literal(value: &str) -> Value {
+            Value::Literal {
+                values: [LiteralValue::String(value.to_string())].into(),
+                optional: false,
+            }
+        }
+
+        #[test]
+        fn when_object_fits_tagged_variant_is_subset() {
+            assert!(event(literal("a"), "x").is_subset(&tagged(false)));
+            assert!(!event(literal("b"), "x").is_subset(&tagged(false)));
+            assert!(!event(literal("c"), "x").is_subset(&tagged(false)));
+            let string = Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None,
+            };
+            assert!(!event(string.clone(), "x").is_subset(&tagged(false)));
+            assert!(!event(string, "y").is_subset(&tagged(false)));
+        }
+
+        #[test]
+        fn when_variants_fit_other_variants_is_subset() {
+            let narrow = Value::Tagged {
+                tag: "type".to_string(),
+                variants: [("a".to_string(), object("x"))].into(),
+                optional: false,
+            };
+
+            assert!(narrow.is_subset(&tagged(false)));
+            assert!(!tagged(false).is_subset(&narrow));
+            assert!(!tagged(true).is_subset(&tagged(false)));
+            assert!(tagged(false).is_subset(&tagged(true)));
+        }
+
+        #[test]
+        fn when_variants_fit_merged_object_is_subset() {
+            let merged = Value::Object {
+                content: [
+                    (
+                        "type".to_string(),
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None,
+                        },
+                    ),
+                    ("x".to_string(), Value::Bool { optional: false }),
+                ]
+                .into(),
+                optional: false,
+                absent: Some(["x".to_string()].into()),
+            };
+
+            assert!(tagged(false).is_subset(&merged));
+            assert!(!tagged(false).is_subset(&object("x")));
+        }
+    }
+
+    mod literal {
+        use super::*;
+        use crate::value::LiteralValue;
+
+        fn literal(values: &[&str], optional: bool) -> Value {
+            Value::Literal {
+                values: values
+                    .iter()
+                    .map(|value| LiteralValue::String((*value).to_string()))
+                    .collect(),
+                optional,
+            }
+        }
+
+        #[test]
+        fn when_literal_is_subset_of_larger_literal() {
+            assert!(literal(&["a"], false).is_subset(&literal(&["a", "b"], true)));
+            assert!(!literal(&["a", "c"], false).is_subset(&literal(&["a", "b"], false)));
+            assert!(!literal(&["a"], true).is_subset(&literal(&["a", "b"], false)));
+        }
+
+        #[test]
+        fn when_literal_is_subset_of_string() {
+            assert!(literal(&["a"], false).is_subset(&Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }));
+            assert!(!literal(&["a"], false).is_subset(&Value::String {
+                optional: false,
+                format: Some(StringFormat::Uuid),
+                length: None,
+                examples: None
+            }));
+        }
+
+        #[test]
+        fn when_literal_is_subset_of_one_of() {
+            assert!(literal(&["a"], true).is_subset(&Value::OneOf {
+                variants: [literal(&["a", "b"], false), Value::Null].into(),
+                optional: false
+            }));
+            assert!(
+                literal(&["a"], false).is_subset(&Value::OneOf {
+                    variants: [
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None
+                        },
+                        Value::Bool { optional: false }
+                    ]
+                    .into(),
+                    optional: false
+                })
+            );
+        }
+
+        #[test]
+        fn when_string_is_not_subset_of_literal() {
+            assert!(
+                !Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&literal(&["a"], false))
+            );
+        }
+    }
+
+    mod string {
+        use super::*;
+
+        #[test]
+        fn when_string_is_subset_of_string() {
+            assert!(
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                })
+            );
+        }
+
+        #[test]
+        fn when_string_is_subset_of_optional_string() {
+            assert!(
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None
+                })
+            );
+        }
+
+        #[test]
+        fn when_optional_string_is_subset_of_optional_string() {
+            assert!(
+                Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None
+                })
+            );
+        }
+
+        #[test]
+        fn when_optional_string_is_not_subset_of_string() {
+            assert!(
+                !Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                })
+            );
+        }
+
+        #[test]
+        fn when_string_is_not_subset_of_string() {
+            assert!(
+                !Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                })
+            );
+        }
+
+        #[test]
+        fn when_string_is_subset_of_oneof_with_string_variant() {
+            assert!(
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::OneOf {
+                    variants: [
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None
+                        },
+                        Value::Null
+                    ]
+                    .into(),
+                    optional: false
+                })
+            );
+        }
+
+        #[test]
+        fn when_string_is_subset_of_oneof_with_optional_string_variant() {
+            assert!(
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
+                .is_subset(&Value::OneOf {
+                    variants: [
+                        Value::String {
+                            optional: true,
+                            format: None,
+                            length: None,
+                            examples: None
+                        },
+                        Value::Null
+                    ]
+                    .into(),
+                    optional: false
+                })
+            );
+        }
+    }
+
+    mod bool {
+        use super::*;
+
+        #[test]
+        fn when_bool_is_subset_of_bool() {
+            assert!(Value::Bool { optional: false }.is_subset(&Value::Bool { optional: false }));
+        }
+
+        #[test]
+        fn when_bool_is_subset_of_optional_bool() {
+            assert!(Value::Bool { optional: false }.is_subset(&Value::Bool { optional: true }));
+        }
+
         #[test]
         fn when_optional_bool_is_subset_of_optional_bool() {
             assert!(Value::Bool { optional: true }.is_subset(&Value::Bool { optional: true }));
@@ -359,7 +1406,12 @@ mod tests {
 
         #[test]
         fn when_bool_is_not_subset_of_string() {
-            assert!(!Value::Bool { optional: false }.is_subset(&Value::String { optional: false }));
+            assert!(!Value::Bool { optional: false }.is_subset(&Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }));
         }
 
         #[test]
@@ -386,12 +1438,24 @@ mod tests {
         fn when_array_number_is_subset_of_array_number() {
             assert!(
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 })
             );
         }
@@ -400,12 +1464,24 @@ mod tests {
         fn when_array_number_is_subset_of_optional_array_number() {
             assert!(
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 })
             );
         }
@@ -414,12 +1490,24 @@ mod tests {
         fn when_array_number_is_subset_of_array_optional_number() {
             assert!(
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 })
             );
         }
@@ -428,12 +1516,24 @@ mod tests {
         fn when_array_number_is_subset_of_optional_array_optional_number() {
             assert!(
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 })
             );
         }
@@ -442,12 +1542,24 @@ mod tests {
         fn when_array_optional_number_is_subset_of_array_number() {
             assert!(
                 !Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 })
             );
         }
@@ -456,12 +1568,24 @@ mod tests {
         fn when_array_optional_number_is_subset_of_optional_array_number() {
             assert!(
                 !Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 })
             );
         }
@@ -470,12 +1594,24 @@ mod tests {
         fn when_array_optional_number_is_subset_of_array_optional_number() {
             assert!(
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 })
             );
         }
@@ -484,12 +1620,24 @@ mod tests {
         fn when_array_optional_number_is_subset_of_optional_array_optional_number() {
             assert!(
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 })
             );
         }
@@ -498,12 +1646,24 @@ mod tests {
         fn when_optional_array_number_is_subset_of_array_number() {
             assert!(
                 !Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 })
             );
         }
@@ -512,12 +1672,24 @@ mod tests {
         fn when_optional_array_number_is_subset_of_optional_array_number() {
             assert!(
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 })
             );
         }
@@ -526,12 +1698,24 @@ mod tests {
         fn when_optional_array_number_is_subset_of_array_optional_number() {
             assert!(
                 !Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 })
             );
         }
@@ -540,12 +1724,24 @@ mod tests {
         fn when_optional_array_number_is_subset_of_optional_array_optional_number() {
             assert!(
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 })
             );
         }
@@ -554,12 +1750,24 @@ mod tests {
         fn when_optional_array_optional_number_is_subset_of_array_number() {
             assert!(
                 !Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 })
             );
         }
@@ -568,12 +1776,24 @@ mod tests {
         fn when_optional_array_optional_number_is_subset_of_optional_array_number() {
             assert!(
                 !Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 })
             );
         }
@@ -582,12 +1802,24 @@ mod tests {
         fn when_optional_array_optional_number_is_subset_of_array_optional_number() {
             assert!(
                 !Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: false
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: false,
+                    length: None
                 })
             );
         }
@@ -596,12 +1828,24 @@ mod tests {
         fn when_optional_array_optional_number_is_subset_of_optional_array_optional_number() {
             assert!(
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 }
                 .is_subset(&Value::Array {
-                    r#type: Box::new(Value::Number { optional: true }),
-                    optional: true
+                    r#type: Box::new(Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
+                    optional: true,
+                    length: None
                 })
             );
         }
@@ -614,11 +1858,23 @@ mod tests {
         fn when_oneof_is_subset_of_equal_oneof() {
             assert!(
                 Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: false
                 }
                 .is_subset(&Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: false
                 })
             );
@@ -628,11 +1884,23 @@ mod tests {
         fn when_oneof_is_subset_of_equal_optional_oneof() {
             assert!(
                 Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: false
                 }
                 .is_subset(&Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: true
                 })
             );
@@ -642,13 +1910,29 @@ mod tests {
         fn when_oneof_is_subset_of_larger_oneof() {
             assert!(
                 Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: false
                 }
                 .is_subset(&Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
-                        Value::String { optional: false }
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        },
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None
+                        }
                     ]
                     .into(),
                     optional: false
@@ -660,13 +1944,29 @@ mod tests {
         fn when_oneof_is_subset_of_larger_oneof_with_optional_superset() {
             assert!(
                 Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: false
                 }
                 .is_subset(&Value::OneOf {
                     variants: [
-                        Value::Number { optional: true },
-                        Value::String { optional: false }
+                        Value::Number {
+                            optional: true,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        },
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None
+                        }
                     ]
                     .into(),
                     optional: false
@@ -678,13 +1978,24 @@ mod tests {
         fn when_oneof_is_subset_of_diff_oneof() {
             assert!(
                 !Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: false
                 }
                 .is_subset(&Value::OneOf {
                     variants: [
                         Value::Bool { optional: false },
-                        Value::String { optional: false }
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None
+                        }
                     ]
                     .into(),
                     optional: false
@@ -696,11 +2007,23 @@ mod tests {
         fn when_optional_oneof_is_subset_of_equal_oneof() {
             assert!(
                 Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: true
                 }
                 .is_subset(&Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: true
                 })
             );
@@ -710,11 +2033,23 @@ mod tests {
         fn when_optional_oneof_is_not_subset_of_non_optional_oneof() {
             assert!(
                 !Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: true
                 }
                 .is_subset(&Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: false
                 })
             );
@@ -724,11 +2059,23 @@ mod tests {
         fn when_optional_oneof_is_subset_of_equal_optional_oneof() {
             assert!(
                 Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: true
                 }
                 .is_subset(&Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: true
                 })
             );
@@ -738,13 +2085,29 @@ mod tests {
         fn when_optional_oneof_is_subset_of_larger_oneof() {
             assert!(
                 Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: true
                 }
                 .is_subset(&Value::OneOf {
                     variants: [
-                        Value::Number { optional: false },
-                        Value::String { optional: false }
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        },
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None
+                        }
                     ]
                     .into(),
                     optional: true
@@ -756,13 +2119,29 @@ mod tests {
         fn when_optional_oneof_is_subset_of_larger_oneof_with_optional_superset() {
             assert!(
                 Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: true
                 }
                 .is_subset(&Value::OneOf {
                     variants: [
-                        Value::Number { optional: true },
-                        Value::String { optional: false }
+                        Value::Number {
+                            optional: true,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        },
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None
+                        }
                     ]
                     .into(),
                     optional: true
@@ -774,19 +2153,91 @@ mod tests {
         fn when_optional_oneof_is_not_subset_of_diff_oneof() {
             assert!(
                 !Value::OneOf {
-                    variants: [Value::Number { optional: false }].into(),
+                    variants: [Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }]
+                    .into(),
                     optional: true
                 }
                 .is_subset(&Value::OneOf {
                     variants: [
                         Value::Bool { optional: false },
-                        Value::String { optional: false }
+                        Value::String {
+                            optional: false,
+                            format: None,
+                            length: None,
+                            examples: None
+                        }
                     ]
                     .into(),
                     optional: false
                 })
             );
         }
+
+        #[test]
+        fn when_optional_is_subset_of_normalized_oneof_with_null() {
+            let one_of = Value::OneOf {
+                variants: [
+                    Value::Null,
+                    Value::Bool { optional: false },
+                    Value::Array {
+                        r#type: Box::new(Value::Null),
+                        optional: false,
+                        length: None,
+                    },
+                ]
+                .into(),
+                optional: false,
+            };
+
+            assert!(Value::Null.is_subset(&one_of));
+            assert!(
+                Value::Array {
+                    r#type: Box::new(Value::Null),
+                    optional: true,
+                    length: None,
+                }
+                .is_subset(&one_of)
+            );
+            assert!(
+                !Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None,
+                }
+                .is_subset(&one_of)
+            );
+        }
+
+        #[test]
+        fn when_oneof_is_subset_of_single_shape() {
+            let one_of = Value::OneOf {
+                variants: [
+                    Value::Tuple {
+                        elements: vec![Value::Bool { optional: false }],
+                        optional: false,
+                    },
+                    Value::Array {
+                        r#type: Box::new(Value::Bool { optional: false }),
+                        optional: false,
+                        length: None,
+                    },
+                ]
+                .into(),
+                optional: false,
+            };
+
+            assert!(one_of.is_subset(&Value::Array {
+                r#type: Box::new(Value::Bool { optional: true }),
+                optional: false,
+                length: None,
+            }));
+        }
     }
 
     mod object {
@@ -797,11 +2248,13 @@ mod tests {
             assert!(
                 Value::Object {
                     content: [].into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
                     content: [].into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 })
             );
         }
@@ -811,11 +2264,13 @@ mod tests {
             assert!(
                 !Value::Object {
                     content: [].into(),
-                    optional: true
+                    optional: true,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
                     content: [].into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 })
             );
         }
@@ -825,25 +2280,67 @@ mod tests {
             assert!(
                 Value::Object {
                     content: [].into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
                     content: [("key".to_string(), Value::Null)].into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(["key".to_string()].into())
+                })
+            );
+        }
+
+        #[test]
+        fn when_empty_obj_is_not_subset_of_obj_with_present_null() {
+            assert!(
+                !Value::Object {
+                    content: [].into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
+                }
+                .is_subset(&Value::Object {
+                    content: [("key".to_string(), Value::Bool { optional: true })].into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 })
             );
         }
 
+        #[test]
+        fn when_absent_key_is_not_subset_of_present_key() {
+            let object = |absent: &[&str]| Value::Object {
+                content: [("key".to_string(), Value::Bool { optional: true })].into(),
+                optional: false,
+                absent: Some(absent.iter().map(ToString::to_string).collect()),
+            };
+
+            assert!(!object(&["key"]).is_subset(&object(&[])));
+            assert!(object(&[]).is_subset(&object(&["key"])));
+            assert!(object(&["key"]).is_subset(&object(&["key"])));
+        }
+
         #[test]
         fn when_empty_obj_is_subset_of_obj_with_optional() {
             assert!(
                 Value::Object {
                     content: [].into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
-                    content: [("key".to_string(), Value::Number { optional: true })].into(),
-                    optional: false
+                    content: [(
+                        "key".to_string(),
+                        Value::Number {
+                            optional: true,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(["key".to_string()].into())
                 })
             );
         }
@@ -853,11 +2350,22 @@ mod tests {
             assert!(
                 !Value::Object {
                     content: [].into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
-                    content: [("key".to_string(), Value::Number { optional: false })].into(),
-                    optional: false
+                    content: [(
+                        "key".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 })
             );
         }
@@ -866,12 +2374,32 @@ mod tests {
         fn when_obj_is_subset_of_obj_with_same_optional() {
             assert!(
                 Value::Object {
-                    content: [("key".to_string(), Value::Number { optional: false })].into(),
-                    optional: false
+                    content: [(
+                        "key".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
-                    content: [("key".to_string(), Value::Number { optional: true })].into(),
-                    optional: false
+                    content: [(
+                        "key".to_string(),
+                        Value::Number {
+                            optional: true,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 })
             );
         }
@@ -880,12 +2408,32 @@ mod tests {
         fn when_obj_of_optionalis_subset_of_obj_with_same() {
             assert!(
                 Value::Object {
-                    content: [("key".to_string(), Value::Number { optional: true })].into(),
-                    optional: false
+                    content: [(
+                        "key".to_string(),
+                        Value::Number {
+                            optional: true,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
-                    content: [("key".to_string(), Value::Number { optional: true })].into(),
-                    optional: false
+                    content: [(
+                        "key".to_string(),
+                        Value::Number {
+                            optional: true,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 })
             );
         }
@@ -894,12 +2442,23 @@ mod tests {
         fn when_obj_is_not_subset_of_obj_with_different_same_key() {
             assert!(
                 !Value::Object {
-                    content: [("key".to_string(), Value::Number { optional: false })].into(),
-                    optional: false
+                    content: [(
+                        "key".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
                     content: [("key".to_string(), Value::Bool { optional: true })].into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 })
             );
         }
@@ -908,15 +2467,30 @@ mod tests {
         fn when_obj_is_subset_of_obj_with_same_key_superset() {
             assert!(
                 Value::Object {
-                    content: [("key".to_string(), Value::Number { optional: false })].into(),
-                    optional: false
+                    content: [(
+                        "key".to_string(),
+                        Value::Number {
+                            optional: false,
+                            kind: NumberKind::Float,
+                            range: None,
+                            examples: None,
+                        }
+                    )]
+                    .into(),
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
                     content: [(
                         "key".to_string(),
                         Value::OneOf {
                             variants: [
-                                Value::Number { optional: false },
+                                Value::Number {
+                                    optional: false,
+                                    kind: NumberKind::Float,
+                                    range: None,
+                                    examples: None,
+                                },
                                 Value::Bool { optional: false },
                                 Value::Null,
                             ]
@@ -925,7 +2499,8 @@ mod tests {
                         }
                     )]
                     .into(),
-                    optional: false
+                    optional: false,
+                    absent: Some(BTreeSet::new())
                 })
             );
         }
@@ -935,23 +2510,73 @@ mod tests {
             assert!(
                 Value::Object {
                     content: [
-                        ("key".to_string(), Value::Number { optional: false }),
+                        (
+                            "key".to_string(),
+                            Value::Number {
+                                optional: false,
+                                kind: NumberKind::Float,
+                                range: None,
+                                examples: None,
+                            }
+                        ),
                         ("b".to_string(), Value::Bool { optional: false }),
-                        ("s".to_string(), Value::String { optional: true }),
+                        (
+                            "s".to_string(),
+                            Value::String {
+                                optional: true,
+                                format: None,
+                                length: None,
+                                examples: None
+                            }
+                        ),
                     ]
                     .into(),
-                    optional: true
+                    optional: true,
+                    absent: Some(BTreeSet::new())
                 }
                 .is_subset(&Value::Object {
                     content: [
-                        ("key".to_string(), Value::Number { optional: true }),
+                        (
+                            "key".to_string(),
+                            Value::Number {
+                                optional: true,
+                                kind: NumberKind::Float,
+                                range: None,
+                                examples: None,
+                            }
+                        ),
                         ("b".to_string(), Value::Bool { optional: false }),
-                        ("s".to_string(), Value::String { optional: true }),
-                        ("z".to_string(), Value::String { optional: true }),
-                        ("n".to_string(), Value::Number { optional: true }),
+                        (
+                            "s".to_string(),
+                            Value::String {
+                                optional: true,
+                                format: None,
+                                length: None,
+                                examples: None
+                            }
+                        ),
+                        (
+                            "z".to_string(),
+                            Value::String {
+                                optional: true,
+                                format: None,
+                                length: None,
+                                examples: None
+                            }
+                        ),
+                        (
+                            "n".to_string(),
+                            Value::Number {
+                                optional: true,
+                                kind: NumberKind::Float,
+                                range: None,
+                                examples: None,
+                            }
+                        ),
                     ]
                     .into(),
-                    optional: true
+                    optional: true,
+                    absent: Some(["n".to_string(), "z".to_string()].into())
                 })
             );
         }
@@ -963,15 +2588,38 @@ mod tests {
             variants: [
                 Value::Object {
                     content: [
-                        ("number".to_string(), Value::Number { optional: false }),
-                        ("state".to_string(), Value::String { optional: false }),
+                        (
+                            "number".to_string(),
+                            Value::Number {
+                                optional: false,
+                                kind: NumberKind::Float,
+                                range: None,
+                                examples: None,
+                            },
+                        ),
+                        (
+                            "state".to_string(),
+                            Value::String {
+                                optional: false,
+                                format: None,
+                                length: None,
+                                examples: None,
+                            },
+                        ),
                     ]
                     .into(),
                     optional: false,
+                    absent: Some(BTreeSet::new()),
                 },
                 Value::Array {
-                    r#type: Box::new(Value::Number { optional: false }),
+                    r#type: Box::new(Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }),
                     optional: false,
+                    length: None,
                 },
             ]
             .into(),
@@ -980,11 +2628,28 @@ mod tests {
 
         let value = Value::Object {
             content: [
-                ("number".to_string(), Value::Number { optional: false }),
-                ("state".to_string(), Value::String { optional: false }),
+                (
+                    "number".to_string(),
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                ),
+                (
+                    "state".to_string(),
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ),
             ]
             .into(),
             optional: false,
+            absent: Some(BTreeSet::new()),
         };
 
         assert!(value.is_subset(&shape));
@@ -1001,42 +2666,120 @@ mod ai_tests {
 
     #[test]
     fn null_is_not_subset_of_number() {
-        assert!(!Value::Null.is_subset(&Value::Number { optional: false }));
+        assert!(!Value::Null.is_subset(&Value::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        }));
     }
 
     #[test]
     fn null_is_not_subset_of_string() {
-        assert!(!Value::Null.is_subset(&Value::String { optional: false }));
+        assert!(!Value::Null.is_subset(&Value::String {
+            optional: false,
+            format: None,
+            length: None,
+            examples: None
+        }));
     }
 
     #[test]
     fn number_is_subset_of_number() {
-        assert!(Value::Number { optional: false }.is_subset(&Value::Number { optional: false }));
+        assert!(
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_subset(&Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            })
+        );
     }
 
     #[test]
     fn number_is_not_subset_of_string() {
-        assert!(!Value::Number { optional: false }.is_subset(&Value::String { optional: false }));
+        assert!(
+            !Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_subset(&Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            })
+        );
     }
 
     #[test]
     fn number_is_not_subset_of_null() {
-        assert!(!Value::Number { optional: false }.is_subset(&Value::Null));
+        assert!(
+            !Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }
+            .is_subset(&Value::Null)
+        );
     }
 
     #[test]
     fn string_is_subset_of_string() {
-        assert!(Value::String { optional: false }.is_subset(&Value::String { optional: false }));
+        assert!(
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_subset(&Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            })
+        );
     }
 
     #[test]
     fn string_is_not_subset_of_number() {
-        assert!(!Value::String { optional: false }.is_subset(&Value::Number { optional: false }));
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_subset(&Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            })
+        );
     }
 
     #[test]
     fn string_is_not_subset_of_null() {
-        assert!(!Value::String { optional: false }.is_subset(&Value::Null));
+        assert!(
+            !Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }
+            .is_subset(&Value::Null)
+        );
     }
 
     #[test]
@@ -1046,11 +2789,21 @@ mod ai_tests {
 
     #[test]
     fn boolean_is_not_subset_of_number() {
-        assert!(!Value::Bool { optional: false }.is_subset(&Value::Number { optional: false }));
+        assert!(!Value::Bool { optional: false }.is_subset(&Value::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        }));
     }
 
     #[test]
     fn boolean_is_not_subset_of_string() {
-        assert!(!Value::Bool { optional: false }.is_subset(&Value::String { optional: false }));
+        assert!(!Value::Bool { optional: false }.is_subset(&Value::String {
+            optional: false,
+            format: None,
+            length: None,
+            examples: None
+        }));
     }
 }
@@ -84,7 +84,13 @@ impl IsArrayOf<Null> for Value {
 impl IsArrayOf<Number> for Value {
     fn is_array_of(&self) -> bool {
         if let Self::Array { r#type, .. } = self {
-            matches!(**r#type, Self::Number { optional: false })
+            matches!(
+                **r#type,
+                Self::Number {
+                    optional: false,
+                    ..
+                }
+            )
         } else {
             false
         }
@@ -94,7 +100,7 @@ impl IsArrayOf<Number> for Value {
 impl IsArrayOf<Optional<Number>> for Value {
     fn is_array_of(&self) -> bool {
         if let Self::Array { r#type, .. } = self {
-            matches!(**r#type, Self::Number { optional: true })
+            matches!(**r#type, Self::Number { optional: true, .. })
         } else {
             false
         }
@@ -130,7 +136,13 @@ impl IsArrayOf<Optional<Tuple>> for Value {
 impl IsArrayOf<String> for Value {
     fn is_array_of(&self) -> bool {
         if let Self::Array { r#type, .. } = self {
-            matches!(**r#type, Self::String { optional: false })
+            matches!(
+                **r#type,
+                Self::String {
+                    optional: false,
+                    ..
+                }
+            )
         } else {
             false
         }
@@ -140,7 +152,7 @@ impl IsArrayOf<String> for Value {
 impl IsArrayOf<Optional<String>> for Value {
     fn is_array_of(&self) -> bool {
         if let Self::Array { r#type, .. } = self {
-            matches!(**r#type, Self::String { optional: true })
+            matches!(**r#type, Self::String { optional: true, .. })
         } else {
             false
         }
@@ -259,9 +271,15 @@ impl IsOneOf<Null> for Value {
 impl IsOneOf<Number> for Value {
     fn is_one_of(&self) -> bool {
         if let Self::OneOf { variants, .. } = self {
-            variants
-                .iter()
-                .any(|variant| matches!(&variant, &Self::Number { optional: false }))
+            variants.iter().any(|variant| {
+                matches!(
+                    &variant,
+                    &Self::Number {
+                        optional: false,
+                        ..
+                    }
+                )
+            })
         } else {
             false
         }
@@ -271,9 +289,15 @@ impl IsOneOf<Number> for Value {
 impl IsOneOf<String> for Value {
     fn is_one_of(&self) -> bool {
         if let Self::OneOf { variants, .. } = self {
-            variants
-                .iter()
-                .any(|variant| matches!(&variant, &Self::String { optional: false }))
+            variants.iter().any(|variant| {
+                matches!(
+                    &variant,
+                    &Self::String {
+                        optional: false,
+                        ..
+                    }
+                )
+            })
         } else {
             false
         }
@@ -470,9 +494,16 @@ impl IsObjectOf<Null> for Value {
 impl IsObjectOf<Number> for Value {
     fn is_object_of(&self, key: &str) -> bool {
         if let Self::Object { content, .. } = self {
-            content
-                .iter()
-                .any(|(k, value)| k == key && matches!(&value, &Self::Number { optional: false }))
+            content.iter().any(|(k, value)| {
+                k == key
+                    && matches!(
+                        &value,
+                        &Self::Number {
+                            optional: false,
+                            ..
+                        }
+                    )
+            })
         } else {
             false
         }
@@ -482,9 +513,16 @@ impl IsObjectOf<Number> for Value {
 impl IsObjectOf<String> for Value {
     fn is_object_of(&self, key: &str) -> bool {
         if let Self::Object { content, .. } = self {
-            content
-                .iter()
-                .any(|(k, value)| k == key && matches!(&value, &Self::String { optional: false }))
+            content.iter().any(|(k, value)| {
+                k == key
+                    && matches!(
+                        &value,
+                        &Self::String {
+                            optional: false,
+                            ..
+                        }
+                    )
+            })
         } else {
             false
         }
@@ -582,9 +620,9 @@ impl IsObjectOf<OneOf> for Value {
 impl IsObjectOf<Optional<Number>> for Value {
     fn is_object_of(&self, key: &str) -> bool {
         if let Self::Object { content, .. } = self {
-            content
-                .iter()
-                .any(|(k, value)| k == key && matches!(&value, &Self::Number { optional: true }))
+            content.iter().any(|(k, value)| {
+                k == key && matches!(&value, &Self::Number { optional: true, .. })
+            })
         } else {
             false
         }
@@ -594,9 +632,9 @@ impl IsObjectOf<Optional<Number>> for Value {
 impl IsObjectOf<Optional<String>> for Value {
     fn is_object_of(&self, key: &str) -> bool {
         if let Self::Object { content, .. } = self {
-            content
-                .iter()
-                .any(|(k, value)| k == key && matches!(&value, &Self::String { optional: true }))
+            content.iter().any(|(k, value)| {
+                k == key && matches!(&value, &Self::String { optional: true, .. })
+            })
         } else {
             false
         }
@@ -677,7 +715,13 @@ impl IsTupleOf<Null> for Value {
 impl IsTupleOf<Number> for Value {
     fn is_tuple_of(&self, i: usize) -> bool {
         if let Self::Tuple { elements, .. } = self {
-            matches!(elements.get(i), Some(Self::Number { optional: false }))
+            matches!(
+                elements.get(i),
+                Some(Self::Number {
+                    optional: false,
+                    ..
+                })
+            )
         } else {
             false
         }
@@ -687,7 +731,7 @@ impl IsTupleOf<Number> for Value {
 impl IsTupleOf<Optional<Number>> for Value {
     fn is_tuple_of(&self, i: usize) -> bool {
         if let Self::Tuple { elements, .. } = self {
-            matches!(elements.get(i), Some(Self::Number { optional: true }))
+            matches!(elements.get(i), Some(Self::Number { optional: true, .. }))
         } else {
             false
         }
@@ -697,7 +741,13 @@ impl IsTupleOf<Optional<Number>> for Value {
 impl IsTupleOf<String> for Value {
     fn is_tuple_of(&self, i: usize) -> bool {
         if let Self::Tuple { elements, .. } = self {
-            matches!(elements.get(i), Some(Self::String { optional: false }))
+            matches!(
+                elements.get(i),
+                Some(Self::String {
+                    optional: false,
+                    ..
+                })
+            )
         } else {
             false
         }
@@ -707,7 +757,7 @@ impl IsTupleOf<String> for Value {
 impl IsTupleOf<Optional<String>> for Value {
     fn is_tuple_of(&self, i: usize) -> bool {
         if let Self::Tuple { elements, .. } = self {
-            matches!(elements.get(i), Some(Self::String { optional: true }))
+            matches!(elements.get(i), Some(Self::String { optional: true, .. }))
         } else {
             false
         }
@@ -814,89 +864,165 @@ impl IsTupleOf<Optional<OneOf>> for Value {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::*;
+    use crate::value::NumberKind;
 
     #[test]
     fn is_array_of_null() {
         assert!(IsArrayOf::<Null>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Null),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<Null>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::Number { optional: true }),
-            optional: false
+            r#type: Box::new(Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None
         }));
     }
 
     #[test]
     fn is_array_of_number() {
         assert!(IsArrayOf::<Number>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: false
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None
         }));
         assert!(IsArrayOf::<Number>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: true
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: true,
+            length: None
         }));
         assert!(IsArrayOf::<Optional<Number>>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::Number { optional: true }),
-            optional: false
+            r#type: Box::new(Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<Optional<Number>>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(IsArrayOf::<Number>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
-            optional: true
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: true,
+            length: None
         }));
         assert!(IsArrayOf::<Optional<Number>>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::Number { optional: true }),
-            optional: false
+            r#type: Box::new(Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<Optional<Number>>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<Number>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
     }
 
     #[test]
     fn is_array_of_string() {
         assert!(IsArrayOf::<String>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::String { optional: false }),
-            optional: false
+            r#type: Box::new(Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None
+            }),
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<String>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::String { optional: true }),
-            optional: false
+            r#type: Box::new(Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }),
+            optional: false,
+            length: None
         }));
         assert!(IsArrayOf::<Optional<String>>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::String { optional: true }),
-            optional: false
+            r#type: Box::new(Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }),
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<Optional<String>>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<String>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::String { optional: true }),
-            optional: false
+            r#type: Box::new(Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }),
+            optional: false,
+            length: None
         }));
         assert!(IsArrayOf::<Optional<String>>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::String { optional: true }),
-            optional: false
+            r#type: Box::new(Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }),
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<Optional<String>>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<String>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
     }
 
@@ -904,39 +1030,62 @@ mod tests {
     fn is_array_of_bool() {
         assert!(IsArrayOf::<Boolean>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: false }),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<Boolean>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(IsArrayOf::<Optional<Boolean>>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<Optional<Boolean>>::is_array_of(
             &Value::Array {
-                r#type: Box::new(Value::Number { optional: true }),
-                optional: false
+                r#type: Box::new(Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }),
+                optional: false,
+                length: None
             }
         ));
         assert!(!IsArrayOf::<Boolean>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(IsArrayOf::<Optional<Boolean>>::is_array_of(&Value::Array {
             r#type: Box::new(Value::Bool { optional: true }),
-            optional: false
+            optional: false,
+            length: None
         }));
         assert!(!IsArrayOf::<Optional<Boolean>>::is_array_of(
             &Value::Array {
-                r#type: Box::new(Value::Number { optional: true }),
-                optional: false
+                r#type: Box::new(Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                }),
+                optional: false,
+                length: None
             }
         ));
         assert!(!IsArrayOf::<Boolean>::is_array_of(&Value::Array {
-            r#type: Box::new(Value::String { optional: true }),
-            optional: false
+            r#type: Box::new(Value::String {
+                optional: true,
+                format: None,
+                length: None,
+                examples: None
+            }),
+            optional: false,
+            length: None
         }));
     }
 
@@ -944,9 +1093,19 @@ mod tests {
     fn is_oneof_of_number() {
         assert!(IsOneOf::<Optional<Number>>::is_one_of(&Value::OneOf {
             variants: [
-                Value::Number { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Bool { optional: false },
-                Value::String { optional: false },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                },
                 Value::Null
             ]
             .into(),
@@ -954,9 +1113,19 @@ mod tests {
         }));
         assert!(IsOneOf::<Number>::is_one_of(&Value::OneOf {
             variants: [
-                Value::Number { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Bool { optional: false },
-                Value::String { optional: false }
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
             ]
             .into(),
             optional: false
@@ -967,9 +1136,19 @@ mod tests {
     fn is_oneof_of_bool() {
         assert!(IsOneOf::<Optional<Boolean>>::is_one_of(&Value::OneOf {
             variants: [
-                Value::Number { optional: true },
+                Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Bool { optional: true },
-                Value::String { optional: true },
+                Value::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None
+                },
                 Value::Null
             ]
             .into(),
@@ -977,9 +1156,19 @@ mod tests {
         }));
         assert!(IsOneOf::<Boolean>::is_one_of(&Value::OneOf {
             variants: [
-                Value::Number { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Bool { optional: false },
-                Value::String { optional: false }
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
             ]
             .into(),
             optional: false
@@ -990,9 +1179,19 @@ mod tests {
     fn is_oneof_of_string() {
         assert!(IsOneOf::<Optional<String>>::is_one_of(&Value::OneOf {
             variants: [
-                Value::Number { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Bool { optional: false },
-                Value::String { optional: false },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                },
                 Value::Null
             ]
             .into(),
@@ -1000,9 +1199,19 @@ mod tests {
         }));
         assert!(IsOneOf::<String>::is_one_of(&Value::OneOf {
             variants: [
-                Value::Number { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Bool { optional: false },
-                Value::String { optional: false }
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                }
             ]
             .into(),
             optional: false
@@ -1013,16 +1222,36 @@ mod tests {
     fn is_object_of_number() {
         assert!(IsObjectOf::<Number>::is_object_of(
             &Value::Object {
-                content: [("key".to_string(), Value::Number { optional: false })].into(),
-                optional: false
+                content: [(
+                    "key".to_string(),
+                    Value::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }
+                )]
+                .into(),
+                optional: false,
+                absent: Some(BTreeSet::new())
             },
             "key"
         ));
 
         assert!(IsObjectOf::<Optional<Number>>::is_object_of(
             &Value::Object {
-                content: [("key".to_string(), Value::Number { optional: true })].into(),
-                optional: false
+                content: [(
+                    "key".to_string(),
+                    Value::Number {
+                        optional: true,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    }
+                )]
+                .into(),
+                optional: false,
+                absent: Some(BTreeSet::new())
             },
             "key"
         ));
@@ -1032,16 +1261,36 @@ mod tests {
     fn is_object_of_string() {
         assert!(IsObjectOf::<String>::is_object_of(
             &Value::Object {
-                content: [("key".to_string(), Value::String { optional: false })].into(),
-                optional: false
+                content: [(
+                    "key".to_string(),
+                    Value::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
+                )]
+                .into(),
+                optional: false,
+                absent: Some(BTreeSet::new())
             },
             "key"
         ));
 
         assert!(IsObjectOf::<Optional<String>>::is_object_of(
             &Value::Object {
-                content: [("key".to_string(), Value::String { optional: true })].into(),
-                optional: false
+                content: [(
+                    "key".to_string(),
+                    Value::String {
+                        optional: true,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
+                )]
+                .into(),
+                optional: false,
+                absent: Some(BTreeSet::new())
             },
             "key"
         ));
@@ -1052,7 +1301,8 @@ mod tests {
         assert!(IsObjectOf::<Boolean>::is_object_of(
             &Value::Object {
                 content: [("key".to_string(), Value::Bool { optional: false })].into(),
-                optional: false
+                optional: false,
+                absent: Some(BTreeSet::new())
             },
             "key"
         ));
@@ -1060,7 +1310,8 @@ mod tests {
         assert!(IsObjectOf::<Optional<Boolean>>::is_object_of(
             &Value::Object {
                 content: [("key".to_string(), Value::Bool { optional: true })].into(),
-                optional: false
+                optional: false,
+                absent: Some(BTreeSet::new())
             },
             "key"
         ));
@@ -1069,14 +1320,27 @@ mod tests {
 
 #[cfg(test)]
 mod ai_tests {
+    use std::collections::BTreeSet;
+
     use super::*;
+    use crate::value::NumberKind;
 
     #[test]
     fn value_is_oneof_number() {
         let value = Value::OneOf {
             variants: [
-                Value::Number { optional: false },
-                Value::String { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
             ]
             .into(),
             optional: false,
@@ -1088,7 +1352,12 @@ mod ai_tests {
     fn value_is_not_oneof_string() {
         let value = Value::OneOf {
             variants: [
-                Value::Number { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Bool { optional: false },
             ]
             .into(),
@@ -1100,7 +1369,16 @@ mod ai_tests {
     #[test]
     fn value_is_oneof_optional_number() {
         let value = Value::OneOf {
-            variants: [Value::Null, Value::Number { optional: false }].into(),
+            variants: [
+                Value::Null,
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+            ]
+            .into(),
             optional: false,
         };
         assert!(IsOneOf::<Optional<Number>>::is_one_of(&value));
@@ -1111,7 +1389,12 @@ mod ai_tests {
         let value = Value::OneOf {
             variants: [
                 Value::Bool { optional: false },
-                Value::Number { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Null,
             ]
             .into(),
@@ -1125,7 +1408,12 @@ mod ai_tests {
         let value = Value::OneOf {
             variants: [
                 Value::Bool { optional: false },
-                Value::Number { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Null,
             ]
             .into(),
@@ -1139,7 +1427,12 @@ mod ai_tests {
         let value = Value::OneOf {
             variants: [
                 Value::Bool { optional: false },
-                Value::Number { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
                 Value::Null,
             ]
             .into(),
@@ -1151,8 +1444,14 @@ mod ai_tests {
     #[test]
     fn value_is_arrayof_number() {
         let value = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
             optional: false,
+            length: None,
         };
         assert!(IsArrayOf::<Number>::is_array_of(&value));
     }
@@ -1160,8 +1459,14 @@ mod ai_tests {
     #[test]
     fn value_is_not_arrayof_string() {
         let value = Value::Array {
-            r#type: Box::new(Value::Number { optional: false }),
+            r#type: Box::new(Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
             optional: false,
+            length: None,
         };
         assert!(!IsArrayOf::<String>::is_array_of(&value));
     }
@@ -1169,8 +1474,14 @@ mod ai_tests {
     #[test]
     fn value_is_arrayof_optional_number() {
         let value = Value::Array {
-            r#type: Box::new(Value::Number { optional: true }),
+            r#type: Box::new(Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
             optional: false,
+            length: None,
         };
         assert!(IsArrayOf::<Optional<Number>>::is_array_of(&value));
     }
@@ -1178,8 +1489,14 @@ mod ai_tests {
     #[test]
     fn value_is_not_arrayof_optional_string() {
         let value = Value::Array {
-            r#type: Box::new(Value::Number { optional: true }),
+            r#type: Box::new(Value::Number {
+                optional: true,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            }),
             optional: false,
+            length: None,
         };
         assert!(!IsArrayOf::<Optional<String>>::is_array_of(&value));
     }
@@ -1187,8 +1504,18 @@ mod ai_tests {
     #[test]
     fn value_is_objectof_number() {
         let value = Value::Object {
-            content: [("key".to_string(), Value::Number { optional: false })].into(),
+            content: [(
+                "key".to_string(),
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+            )]
+            .into(),
             optional: false,
+            absent: Some(BTreeSet::new()),
         };
         assert!(IsObjectOf::<Number>::is_object_of(&value, "key"));
     }
@@ -1196,8 +1523,18 @@ mod ai_tests {
     #[test]
     fn value_is_not_objectof_string() {
         let value = Value::Object {
-            content: [("key".to_string(), Value::Number { optional: false })].into(),
+            content: [(
+                "key".to_string(),
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+            )]
+            .into(),
             optional: false,
+            absent: Some(BTreeSet::new()),
         };
         assert!(!IsObjectOf::<String>::is_object_of(&value, "key"));
     }
@@ -1206,14 +1543,34 @@ mod ai_tests {
     fn test_is_tuple_of_match() {
         let value = Value::Tuple {
             elements: vec![
-                Value::Number { optional: false },
-                Value::String { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
             ],
             optional: false,
         };
         let types = vec![
-            Value::Number { optional: false },
-            Value::String { optional: false },
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            },
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None,
+            },
         ];
         assert!(value.is_tuple_of(&types));
     }
@@ -1222,14 +1579,34 @@ mod ai_tests {
     fn test_is_tuple_of_match_with_optional() {
         let value = Value::Tuple {
             elements: vec![
-                Value::Number { optional: true },
-                Value::String { optional: false },
+                Value::Number {
+                    optional: true,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
             ],
             optional: false,
         };
         let types = vec![
-            Value::Number { optional: false },
-            Value::String { optional: false },
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            },
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None,
+            },
         ];
         assert!(!value.is_tuple_of(&types));
     }
@@ -1238,13 +1615,28 @@ mod ai_tests {
     fn test_is_tuple_of_mismatch() {
         let value = Value::Tuple {
             elements: vec![
-                Value::Number { optional: false },
-                Value::String { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
             ],
             optional: false,
         };
         let types = vec![
-            Value::Number { optional: false },
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            },
             Value::Bool { optional: false },
         ];
         assert!(!value.is_tuple_of(&types));
@@ -1254,21 +1646,51 @@ mod ai_tests {
     fn test_is_tuple_of_length_mismatch() {
         let value = Value::Tuple {
             elements: vec![
-                Value::Number { optional: false },
-                Value::String { optional: false },
+                Value::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                Value::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
             ],
             optional: false,
         };
-        let types = vec![Value::Number { optional: false }];
+        let types = vec![Value::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        }];
         assert!(!value.is_tuple_of(&types));
     }
 
     #[test]
     fn test_is_tuple_of_not_tuple() {
-        let value = Value::Number { optional: false };
+        let value = Value::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        };
         let types = vec![
-            Value::Number { optional: false },
-            Value::String { optional: false },
+            Value::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            },
+            Value::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None,
+            },
         ];
         assert!(!value.is_tuple_of(&types));
     }
@@ -0,0 +1,355 @@
+use crate::{statistics::key_path, value::Value};
+
+/// Visits a [`JsonShape`](crate::JsonShape) and every shape nested in it, with the `JSONPath` path of the values they describe, eg `$.orders[*].id`.
+///
+/// Override [`ShapeVisitor::visit_shape`] matching only the shapes of interest, and call [`walk_shape`] to visit their nested shapes,
+/// so that visitors keep working when new shapes are added.
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use json_shape::{JsonShape, ShapeVisitor, walk_shape};
+///
+/// #[derive(Default)]
+/// struct Strings(Vec<String>);
+///
+/// impl ShapeVisitor for Strings {
+///     fn visit_shape(&mut self, shape: &JsonShape, path: &str) {
+///         if shape.is_string() {
+///             self.0.push(path.to_string());
+///         }
+///         walk_shape(self, shape, path);
+///     }
+/// }
+///
+/// let shape = JsonShape::from_str(r#"{"id": 1, "tags": ["a"], "user": {"name": "b"}}"#).unwrap();
+/// let mut strings = Strings::default();
+/// strings.visit_shape(&shape, "$");
+///
+/// assert_eq!(strings.0, ["$.tags[*]", "$.user.name"]);
+/// ```
+pub trait ShapeVisitor {
+    /// Visits `shape` at `path`, by default visiting its nested shapes with [`walk_shape`]
+    fn visit_shape(&mut self, shape: &Value, path: &str) {
+        walk_shape(self, shape, path);
+    }
+}
+
+/// Mutably visits a [`JsonShape`](crate::JsonShape) and every shape nested in it, as [`ShapeVisitor`].
+///
+/// Shapes changed by the visitor are not normalized, call [`JsonShape::normalize`](crate::JsonShape::normalize) when needed.
+pub trait ShapeVisitorMut {
+    /// Visits `shape` at `path`, by default visiting its nested shapes with [`walk_shape_mut`]
+    fn visit_shape_mut(&mut self, shape: &mut Value, path: &str) {
+        walk_shape_mut(self, shape, path);
+    }
+}
+
+/// Visits the shapes nested in `shape` with `visitor`:
+/// - `Array` elements at `path[*]`, `Tuple` elements at `path[i]` and `Map` values at `path.*`
+/// - `Object` values at `path.key`, or `path['key']` for keys that aren't valid `JSONPath` member names
+/// - `OneOf` and `Tagged` variants, and `Recursive` definitions, at `path`, as they describe the same values
+pub fn walk_shape<V: ShapeVisitor + ?Sized>(visitor: &mut V, shape: &Value, path: &str) {
+    match shape {
+        Value::Null
+        | Value::Unknown
+        | Value::Bool { .. }
+        | Value::Number { .. }
+        | Value::String { .. }
+        | Value::Literal { .. }
+        | Value::Ref { .. } => (),
+        Value::Array { r#type, .. } => visitor.visit_shape(r#type, &format!("{path}[*]")),
+        Value::Map { r#type, .. } => visitor.visit_shape(r#type, &format!("{path}.*")),
+        Value::Tuple { elements, .. } => {
+            for (index, element) in elements.iter().enumerate() {
+                visitor.visit_shape(element, &format!("{path}[{index}]"));
+            }
+        }
+        Value::Object { content, .. } => {
+            for (key, value) in content {
+                visitor.visit_shape(value, &key_path(path, key));
+            }
+        }
+        Value::OneOf { variants, .. } => {
+            for variant in variants {
+                visitor.visit_shape(variant, path);
+            }
+        }
+        Value::Tagged { variants, .. } => {
+            for variant in variants.values() {
+                visitor.visit_shape(variant, path);
+            }
+        }
+        Value::Recursive { shape, .. } => visitor.visit_shape(shape, path),
+    }
+}
+
+/// Mutably visits the shapes nested in `shape` with `visitor`, at the same paths as [`walk_shape`].
+pub fn walk_shape_mut<V: ShapeVisitorMut + ?Sized>(visitor: &mut V, shape: &mut Value, path: &str) {
+    match shape {
+        Value::Null
+        | Value::Unknown
+        | Value::Bool { .. }
+        | Value::Number { .. }
+        | Value::String { .. }
+        | Value::Literal { .. }
+        | Value::Ref { .. } => (),
+        Value::Array { r#type, .. } => visitor.visit_shape_mut(r#type, &format!("{path}[*]")),
+        Value::Map { r#type, .. } => visitor.visit_shape_mut(r#type, &format!("{path}.*")),
+        Value::Tuple { elements, .. } => {
+            for (index, element) in elements.iter_mut().enumerate() {
+                visitor.visit_shape_mut(element, &format!("{path}[{index}]"));
+            }
+        }
+        Value::Object { content, .. } => {
+            for (key, value) in content {
+                visitor.visit_shape_mut(value, &key_path(path, key));
+            }
+        }
+        // Variants are ordered by their shape, so they are reinserted once changed
+        Value::OneOf { variants, .. } => {
+            *variants = std::mem::take(variants)
+                .into_iter()
+                .map(|mut variant| {
+                    visitor.visit_shape_mut(&mut variant, path);
+                    variant
+                })
+                .collect();
+        }
+        Value::Tagged { variants, .. } => {
+            for variant in variants.values_mut() {
+                visitor.visit_shape_mut(variant, path);
+            }
+        }
+        Value::Recursive { shape, .. } => visitor.visit_shape_mut(shape, path),
+    }
+}
+
+impl Value {
+    /// Folds this [`JsonShape`](crate::JsonShape) and every shape nested in it, parents before their nested shapes,
+    /// with the paths of [`walk_shape`].
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use json_shape::JsonShape;
+    ///
+    /// let shape = JsonShape::from_str(r#"{"a": [1, 2], "b": {"c": null}}"#).unwrap();
+    /// let optional = shape.fold(Vec::new(), |mut paths, shape, path| {
+    ///     if shape.is_optional() {
+    ///         paths.push(path.to_string());
+    ///     }
+    ///     paths
+    /// });
+    ///
+    /// assert_eq!(optional, ["$.b.c"]);
+    /// ```
+    pub fn fold<T>(&self, init: T, f: impl FnMut(T, &Self, &str) -> T) -> T {
+        let mut folder = Folder {
+            accumulator: Some(init),
+            f,
+        };
+        folder.visit_shape(self, "$");
+        let Some(accumulator) = folder.accumulator else {
+            unreachable!("accumulator is always set between visits")
+        };
+        accumulator
+    }
+
+    /// Rewrites this [`JsonShape`](crate::JsonShape) with `f`, nested shapes before their parents,
+    /// with the paths of [`walk_shape`]. The rewritten shape is not normalized.
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use json_shape::JsonShape;
+    ///
+    /// let shape = JsonShape::from_str(r#"{"id": 1, "tags": [2]}"#).unwrap();
+    /// let shape = shape.map(|shape, path| match shape {
+    ///     JsonShape::Number { optional, .. } if path != "$.id" => JsonShape::String {
+    ///         optional,
+    ///         format: None,
+    ///         length: None,
+    ///         examples: None,
+    ///     },
+    ///     shape => shape,
+    /// });
+    ///
+    /// assert_eq!(shape.to_string(), "Object{id: Integer<unsigned>, tags: Array<String>}");
+    /// ```
+    #[must_use]
+    pub fn map(mut self, f: impl FnMut(Self, &str) -> Self) -> Self {
+        Mapper { f }.visit_shape_mut(&mut self, "$");
+        self
+    }
+}
+
+struct Folder<T, F> {
+    accumulator: Option<T>,
+    f: F,
+}
+
+impl<T, F: FnMut(T, &Value, &str) -> T> ShapeVisitor for Folder<T, F> {
+    fn visit_shape(&mut self, shape: &Value, path: &str) {
+        self.accumulator = self
+            .accumulator
+            .take()
+            .map(|accumulator| (self.f)(accumulator, shape, path));
+        walk_shape(self, shape, path);
+    }
+}
+
+struct Mapper<F> {
+    f: F,
+}
+
+impl<F: FnMut(Value, &str) -> Value> ShapeVisitorMut for Mapper<F> {
+    fn visit_shape_mut(&mut self, shape: &mut Value, path: &str) {
+        walk_shape_mut(self, shape, path);
+        let visited = std::mem::replace(shape, Value::Unknown);
+        *shape = (self.f)(visited, path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::InferenceOptions;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Paths(Vec<String>);
+
+    impl ShapeVisitor for Paths {
+        fn visit_shape(&mut self, shape: &Value, path: &str) {
+            self.0.push(format!("{path}: {shape}"));
+            walk_shape(self, shape, path);
+        }
+    }
+
+    struct Optional;
+
+    impl ShapeVisitorMut for Optional {
+        fn visit_shape_mut(&mut self, shape: &mut Value, path: &str) {
+            if path != "$" {
+                *shape = std::mem::replace(shape, Value::Unknown).as_optional();
+            }
+            walk_shape_mut(self, shape, path);
+        }
+    }
+
+    #[test]
+    fn walk_shape_visits_nested_shapes_with_their_paths() {
+        let shape = Value::from_sources(&[
+            r#"{"a b": [1], "t": [1, "x"], "u": 1}"#.to_string(),
+            r#"{"a b": [2], "t": [2, "y"], "u": "x"}"#.to_string(),
+        ])
+        .unwrap();
+        let mut paths = Paths::default();
+        paths.visit_shape(&shape, "$");
+
+        assert_eq!(
+            paths.0,
+            [
+                format!("$: {shape}"),
+                "$['a b']: Array<Integer<unsigned>>".to_string(),
+                "$['a b'][*]: Integer<unsigned>".to_string(),
+                "$.t: Tuple(Integer<unsigned>, String)".to_string(),
+                "$.t[0]: Integer<unsigned>".to_string(),
+                "$.t[1]: String".to_string(),
+                "$.u: OneOf[Integer<unsigned> | String]".to_string(),
+                "$.u: Integer<unsigned>".to_string(),
+                "$.u: String".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_shape_visits_maps_tagged_and_recursive_shapes() {
+        fn paths(shape: &Value) -> Vec<String> {
+            shape.fold(Vec::new(), |mut paths, shape, path| {
+                if !shape.is_object() {
+                    paths.push(format!("{path}: {shape}"));
+                }
+                paths
+            })
+        }
+        let options = InferenceOptions::default()
+            .with_map_threshold(2)
+            .with_discriminators();
+        let shape = Value::from_str_with_options(
+            r#"{"m": {"1": true, "2": false}, "e": [{"type": "a", "x": 1}, {"type": "b"}]}"#,
+            &options,
+        )
+        .unwrap();
+        let recursive = Value::from_str_with_options(
+            r#"{"v": 1, "next": {"v": 2, "next": null}}"#,
+            &InferenceOptions::default().with_recursion(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            paths(&shape),
+            [
+                r#"$.e: Array<Tagged<type>["a": Object{x: Integer<unsigned>} | "b": Object{}]>"#,
+                r#"$.e[*]: Tagged<type>["a": Object{x: Integer<unsigned>} | "b": Object{}]"#,
+                "$.e[*].x: Integer<unsigned>",
+                "$.m: Map<Integer, Boolean>",
+                "$.m.*: Boolean",
+            ]
+        );
+        assert_eq!(
+            paths(&recursive),
+            [
+                format!("$: {recursive}"),
+                "$.next: Option<Ref<Root>>".to_string(),
+                "$.v: Integer<unsigned>".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_shape_mut_rewrites_nested_shapes() {
+        let mut shape = Value::from_sources(&[
+            r#"{"a": [1, "x"], "b": [{"c": true}], "d": [1, [true]]}"#.to_string(),
+            r#"{"a": [2, "y", 3], "b": [], "d": [2, []]}"#.to_string(),
+        ])
+        .unwrap();
+        Optional.visit_shape_mut(&mut shape, "$");
+
+        assert_eq!(
+            shape.to_string(),
+            "Object{a: Option<Array<Option<OneOf[Option<Integer<unsigned>> | Option<String>]>>>, b: Option<Array<Option<Object{c: Option<Boolean>}>>>, d: Option<Tuple(Option<Integer<unsigned>>, Option<Array<Option<Boolean>>>)>}"
+        );
+    }
+
+    #[test]
+    fn map_rewrites_nested_shapes_before_their_parents() {
+        let shape = Value::from_str(r#"{"a": [[1]], "b": {"c": [2]}}"#).unwrap();
+        let mut visited = Vec::new();
+        let shape = shape.map(|shape, path| {
+            visited.push(path.to_string());
+            match shape {
+                Value::Array { r#type, .. } if r#type.is_array() => *r#type,
+                shape => shape,
+            }
+        });
+
+        assert_eq!(
+            visited,
+            [
+                "$.a[*][*]",
+                "$.a[*]",
+                "$.a",
+                "$.b.c[*]",
+                "$.b.c",
+                "$.b",
+                "$"
+            ]
+        );
+        assert_eq!(
+            shape.to_string(),
+            "Object{a: Array<Integer<unsigned>>, b: Object{c: Array<Integer<unsigned>>}}"
+        );
+    }
+}
@@ -74,7 +74,8 @@ fn test_nullable_field_merging() {
     \"content\": {
       \"a\": {
         \"Number\": {
-          \"optional\": true
+          \"optional\": true,
+          \"kind\": \"Unsigned\"
         }
       }
     },
@@ -103,7 +104,8 @@ fn test_array_merging_with_mixed_types() {
           },
           {
             \"Number\": {
-              \"optional\": false
+              \"optional\": false,
+              \"kind\": \"Unsigned\"
             }
           },
           {
@@ -2,7 +2,7 @@
 use std::{fs::read_to_string, str::FromStr};
 
 use insta::assert_snapshot;
-use json_shape::JsonShape;
+use json_shape::{JsonShape, PrettyOptions, ShapeEncoding};
 
 #[test]
 fn test_json_array_variant() {
@@ -13,6 +13,48 @@ fn test_json_array_variant() {
     assert_snapshot!(array);
 }
 
+#[test]
+fn test_json_array_metrics() {
+    let json = huge_json();
+
+    let metrics = JsonShape::from_str(&json).unwrap().metrics();
+
+    assert_snapshot!(metrics, @"depth: 3, nodes: 21, distinct keys: 15, max width: 15, max variants: 2, optional ratio: 0.00");
+}
+
+#[test]
+fn test_json_array_pretty() {
+    let json = huge_json();
+
+    let shape = JsonShape::from_str(&json).unwrap();
+    let options = PrettyOptions::default()
+        .with_max_depth(2)
+        .with_collapsed_repeats();
+
+    assert_snapshot!(shape.pretty(&options), @r#"
+    Array<Object{
+      1st, "20+", "40+", Att, FUM, TD: Integer<unsigned>,
+      "1st%", "Att/G", Avg, "Yds/G": Number,
+      Lng, Yds: OneOf[Integer<signed> | String],
+      Player, Pos, Team: String
+    }>
+    "#);
+}
+
+/// Documents written by earlier releases must stay readable, and the current release must keep writing them
+#[test]
+fn test_json_array_interchange_v1() {
+    let json = huge_json();
+    let document: serde_json::Value =
+        serde_json::from_str(&read_to_string("./tests/fixture/test.shape.v1.json").unwrap())
+            .unwrap();
+
+    let shape = JsonShape::from_str(&json).unwrap();
+
+    assert_eq!(JsonShape::from_interchange(&document).unwrap(), shape);
+    assert_eq!(shape.to_interchange(ShapeEncoding::Tree), document);
+}
+
 fn huge_json() -> String {
     read_to_string("./tests/fixture/test.json").unwrap()
 }
@@ -2,7 +2,7 @@
 
 use std::{collections::BTreeMap, str::FromStr};
 
-use json_shape::JsonShape;
+use json_shape::{ChangeKind, InferenceOptions, IsSubset, JsonShape, ShapeEncoding};
 use proptest::prelude::*;
 use serde_json::{Map, Value};
 
@@ -80,6 +80,66 @@ fn arb_same_kind_sources() -> impl Strategy<Value = Vec<Value>> {
     ]
 }
 
+fn arb_shape() -> impl Strategy<Value = JsonShape> {
+    prop_oneof![
+        1 => arb_json_value().prop_map(JsonShape::from),
+        1 => prop::collection::vec(arb_json_value(), 1..4).prop_map(|values| shape_of(&values)),
+        1 => arb_same_kind_sources().prop_map(|values| shape_of(&values)),
+    ]
+}
+
+/// Shapes of `values` under `key`, any string, with every refinement but examples
+fn refined_shape_of(key: &str, values: &[Value], options: &InferenceOptions) -> JsonShape {
+    let options = options
+        .clone()
+        .with_literal_limit(3)
+        .with_length_bounds()
+        .with_map_threshold(3)
+        .with_recursion()
+        .with_discriminators();
+    let sources = values
+        .iter()
+        .map(|value| serde_json::json!({ key: value }).to_string())
+        .collect::<Vec<_>>();
+    JsonShape::from_sources_with_options(&sources, &options).unwrap()
+}
+
+fn shape_of(values: &[Value]) -> JsonShape {
+    let sources = values
+        .iter()
+        .map(|value| serde_json::to_string(value).unwrap())
+        .collect::<Vec<_>>();
+    JsonShape::from_sources(&sources).unwrap()
+}
+
+/// Shapes describing the same Json values, eg `Tuple(Number)` and `Array<Number>[1..=1]`
+fn equivalent(a: &JsonShape, b: &JsonShape) -> bool {
+    a.is_subset(b) && b.is_subset(a)
+}
+
+/// Every JSON Pointer into `value`, eg `["", "/a", "/a/0"]`
+fn pointers(value: &Value, pointer: &str) -> Vec<String> {
+    let children: Vec<(String, &Value)> = match value {
+        Value::Array(values) => values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (index.to_string(), value))
+            .collect(),
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, value)| (key.replace('~', "~0").replace('/', "~1"), value))
+            .collect(),
+        _ => Vec::new(),
+    };
+    std::iter::once(pointer.to_string())
+        .chain(
+            children
+                .into_iter()
+                .flat_map(|(token, value)| pointers(value, &format!("{pointer}/{token}"))),
+        )
+        .collect()
+}
+
 proptest! {
     #[test]
     fn from_str_matches_from_json_value(value in arb_json_value()) {
@@ -113,4 +173,188 @@ proptest! {
 
         prop_assert_eq!(merged, base);
     }
+
+    #[test]
+    fn inferred_and_merged_shapes_are_normalized(values in prop::collection::vec(arb_json_value(), 1..4)) {
+        let sources = values
+            .iter()
+            .map(|value| serde_json::to_string(value).unwrap())
+            .collect::<Vec<_>>();
+
+        for (source, value) in sources.iter().zip(&values) {
+            let shape = JsonShape::from_str(source).unwrap();
+            prop_assert_eq!(shape.clone().normalize(), shape);
+            let shape = JsonShape::from(value);
+            prop_assert_eq!(shape.clone().normalize(), shape);
+        }
+        if let Ok(merged) = JsonShape::from_sources(&sources) {
+            prop_assert_eq!(merged.clone().normalize(), merged);
+        }
+    }
+
+    #[test]
+    fn normalize_keeps_the_described_values(variants in prop::collection::vec(arb_shape(), 1..4), optional in any::<bool>()) {
+        let shape = JsonShape::OneOf {
+            variants: variants.into_iter().collect(),
+            optional,
+        };
+
+        prop_assert!(equivalent(&shape, &shape.clone().normalize()));
+    }
+
+    #[test]
+    fn join_is_an_upper_bound(a in arb_shape(), b in arb_shape()) {
+        let joined = a.join(&b);
+
+        prop_assert!(a.is_subset(&joined));
+        prop_assert!(b.is_subset(&joined));
+        prop_assert_eq!(a.join(&a), a);
+    }
+
+    #[test]
+    fn join_is_commutative(a in arb_shape(), b in arb_shape()) {
+        prop_assert_eq!(a.join(&b), b.join(&a));
+    }
+
+    #[test]
+    fn join_is_associative(a in arb_shape(), b in arb_shape(), c in arb_shape()) {
+        prop_assert_eq!(a.join(&b).join(&c), a.join(&b.join(&c)));
+    }
+
+    #[test]
+    fn meet_is_a_lower_bound(a in arb_shape(), b in arb_shape()) {
+        if let Some(met) = a.meet(&b) {
+            prop_assert!(met.is_subset(&a));
+            prop_assert!(met.is_subset(&b));
+        }
+        prop_assert_eq!(a.meet(&a), Some(a.clone()));
+        if a.is_subset(&b) {
+            let met = a.meet(&b);
+            prop_assert!(met.as_ref().is_some_and(|met| equivalent(met, &a)));
+        }
+    }
+
+    #[test]
+    fn meet_is_commutative(a in arb_shape(), b in arb_shape()) {
+        prop_assert_eq!(a.meet(&b), b.meet(&a));
+    }
+
+    #[test]
+    fn meet_is_associative(a in arb_shape(), b in arb_shape(), c in arb_shape()) {
+        let left = a.meet(&b).and_then(|ab| ab.meet(&c));
+        let right = b.meet(&c).and_then(|bc| a.meet(&bc));
+
+        match (left, right) {
+            (Some(left), Some(right)) => prop_assert!(equivalent(&left, &right)),
+            (left, right) => prop_assert_eq!(left, right),
+        }
+    }
+
+    #[test]
+    fn meet_is_absorbed_by_join(a in arb_shape(), b in arb_shape()) {
+        prop_assert_eq!(a.meet(&a.join(&b)), Some(a));
+    }
+
+    #[test]
+    fn diff_to_join_only_widens(a in arb_shape(), b in arb_shape()) {
+        prop_assert!(a.diff(&a).is_empty());
+        for change in a.diff(&a.join(&b)) {
+            prop_assert!(
+                matches!(
+                    change.kind,
+                    ChangeKind::FieldAdded { optional: true, .. }
+                        | ChangeKind::BecameOptional
+                        | ChangeKind::BecameNullable
+                        | ChangeKind::TypeWidened { .. }
+                        | ChangeKind::VariantAdded(_)
+                ),
+                "{}",
+                change
+            );
+        }
+    }
+
+    #[test]
+    fn get_contains_every_value_at_the_pointer(values in prop::collection::vec(arb_json_value(), 1..4)) {
+        let shape = shape_of(&values);
+        for value in &values {
+            for pointer in pointers(value, "") {
+                let resolved = shape.get(&pointer);
+                prop_assert!(resolved.is_some(), "{}", pointer);
+                let at_pointer = JsonShape::from(value.pointer(&pointer).unwrap());
+                prop_assert!(at_pointer.is_subset(&resolved.unwrap()), "{}", pointer);
+            }
+        }
+    }
+
+    #[test]
+    fn query_contains_every_value_at_the_normalized_path(values in prop::collection::vec(arb_json_value(), 1..4)) {
+        let shape = shape_of(&values);
+        for value in &values {
+            for pointer in pointers(value, "") {
+                // Keys are letters, so numeric tokens are array indices
+                let path = pointer.split('/').skip(1).fold("$".to_string(), |path, token| {
+                    if token.parse::<usize>().is_ok() {
+                        format!("{path}[{token}]")
+                    } else {
+                        format!("{path}['{token}']")
+                    }
+                });
+                let at_path = JsonShape::from(value.pointer(&pointer).unwrap());
+                let Ok(JsonShape::Array { r#type, .. }) = shape.query(&path) else {
+                    panic!("{path}");
+                };
+                prop_assert!(at_path.is_subset(&*r#type), "{}", path);
+            }
+        }
+    }
+
+    #[test]
+    fn leaf_paths_are_matching_queries(shape in arb_shape()) {
+        for leaf in shape.leaf_paths() {
+            // Elements of empty arrays are never selected
+            if !leaf.shape.is_unknown() {
+                prop_assert!(!shape.never_matches(&leaf.path).unwrap(), "{}", leaf);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_shape_round_trips_display(key in "\\PC{0,6}", values in prop::collection::vec(arb_json_value(), 1..4)) {
+        let shape = refined_shape_of(&key, &values, &InferenceOptions::default());
+        let notation = shape.to_string();
+
+        prop_assert_eq!(JsonShape::parse_shape(&notation).unwrap(), shape, "{}", notation);
+    }
+
+    #[test]
+    fn parse_shape_round_trips_number_ranges(key in "\\PC{0,6}", values in prop::collection::vec(arb_json_value(), 1..4)) {
+        let shape = refined_shape_of(&key, &values, &InferenceOptions::default().with_number_ranges());
+        let notation = shape.to_string();
+
+        prop_assert_eq!(JsonShape::parse_shape(&notation).unwrap().to_string(), notation);
+    }
+
+    #[test]
+    fn interchange_tree_round_trips(key in "\\PC{0,6}", values in prop::collection::vec(arb_json_value(), 1..4)) {
+        let options = InferenceOptions::default().with_number_ranges().with_examples(3);
+        let shape = refined_shape_of(&key, &values, &options);
+        let document = shape.to_interchange(ShapeEncoding::Tree);
+
+        prop_assert_eq!(JsonShape::from_interchange(&document).unwrap(), shape);
+    }
+
+    #[test]
+    fn validate_locates_errors_where_is_superset_fails(shape in arb_shape(), value in arb_json_value()) {
+        let json = value.to_string();
+        let result = shape.validate(&json);
+
+        prop_assert_eq!(result.is_ok(), shape.is_superset(&json));
+        for error in result.err().unwrap_or_default() {
+            prop_assert!(error.span.end <= json.len());
+            if error.found.is_some() {
+                prop_assert!(value.pointer(&error.pointer).is_some(), "{}", error);
+            }
+        }
+    }
 }
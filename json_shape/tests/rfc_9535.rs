@@ -115,3 +115,113 @@ fn example_10() {
     assert_eq!(shape, shape_from_value);
     assert_snapshot!(shape_from_value);
 }
+
+fn example_shape(example: usize) -> JsonShape {
+    let json_str = read_to_string(format!("./testdata/rfc-9535-example-{example}.json")).unwrap();
+    JsonShape::from_str(&json_str).unwrap()
+}
+
+fn query(shape: &JsonShape, path: &str) -> String {
+    shape.query(path).unwrap().to_string()
+}
+
+#[test]
+fn example_1_queries() {
+    let shape = example_shape(1);
+    let book =
+        "Object{author: String, category: String, isbn?: String, price: Number, title: String}";
+
+    assert_eq!(query(&shape, "$.store.book[*].author"), "Array<String>");
+    assert_eq!(query(&shape, "$..author"), "Array<String>");
+    assert_eq!(
+        query(&shape, "$.store.*"),
+        format!("Array<OneOf[Array<{book}> | Object{{color: String, price: Integer<unsigned>}}]>")
+    );
+    assert_eq!(query(&shape, "$.store..price"), "Array<Number>");
+    assert_eq!(query(&shape, "$..book[2]"), format!("Array<{book}>"));
+    assert_eq!(query(&shape, "$..book[-1]"), format!("Array<{book}>"));
+    assert_eq!(query(&shape, "$..book[0,1]"), format!("Array<{book}>"));
+    assert_eq!(query(&shape, "$..book[:2]"), format!("Array<{book}>"));
+    assert_eq!(query(&shape, "$..book[?@.isbn].isbn"), "Array<String>");
+    assert_eq!(query(&shape, "$..book[?@.price<10].title"), "Array<String>");
+    assert!(shape.never_matches("$..book[2].publisher").unwrap());
+    assert!(!shape.never_matches("$..*").unwrap());
+}
+
+#[test]
+fn example_2_queries() {
+    let shape = example_shape(2);
+
+    assert_eq!(
+        query(&shape, "$.o['j j']['k.k']"),
+        "Array<Integer<unsigned>>"
+    );
+    assert_eq!(
+        query(&shape, r#"$.o["j j"]["k.k"]"#),
+        "Array<Integer<unsigned>>"
+    );
+    assert_eq!(query(&shape, r#"$["'"]["@"]"#), "Array<Integer<unsigned>>");
+    assert!(shape.never_matches("$.o.jj").unwrap());
+}
+
+#[test]
+fn example_3_queries() {
+    let shape = example_shape(3);
+
+    assert_eq!(
+        query(&shape, "$[*]"),
+        "Array<OneOf[Array<Integer<unsigned>> | Object{j: Integer<unsigned>, k: Integer<unsigned>}]>"
+    );
+    assert_eq!(query(&shape, "$.o[*, *]"), "Array<Integer<unsigned>>");
+    assert_eq!(query(&shape, "$.a[*]"), "Array<Integer<unsigned>>");
+}
+
+#[test]
+fn example_5_queries() {
+    let shape = example_shape(5);
+
+    for path in ["$[1:3]", "$[5:]", "$[1:5:2]", "$[5:1:-2]", "$[::-1]"] {
+        assert_eq!(query(&shape, path), "Array<String>");
+    }
+    assert!(shape.never_matches("$[::0]").unwrap());
+}
+
+#[test]
+fn example_7_queries() {
+    let shape = example_shape(7);
+
+    assert_eq!(
+        query(&shape, "$.a[?@.b]"),
+        "Array<OneOf[Object{b: String} | Object{b: Object{}}]>"
+    );
+    assert_eq!(
+        query(&shape, "$.a[?@.b == 'kilo']"),
+        "Array<OneOf[Integer<unsigned> | Object{b: String} | Object{b: Object{}}]>"
+    );
+    assert_eq!(query(&shape, "$.a[9].b"), "Array<String>");
+    assert!(shape.never_matches("$.a[10]").unwrap());
+    assert!(shape.never_matches("$.o[?@.v]").unwrap());
+}
+
+#[test]
+fn example_9_queries() {
+    let shape = example_shape(9);
+
+    assert_eq!(query(&shape, "$..j"), "Array<Integer<unsigned>>");
+    assert_eq!(
+        query(&shape, "$..[0]"),
+        "Array<OneOf[Integer<unsigned> | Object{j?: Integer<unsigned>, k?: Integer<unsigned>}]>"
+    );
+    assert_eq!(query(&shape, "$.o..[*, *]"), "Array<Integer<unsigned>>");
+}
+
+#[test]
+fn example_10_queries() {
+    let shape = example_shape(10);
+
+    assert_eq!(query(&shape, "$.a"), "Array<Null>");
+    assert_eq!(query(&shape, "$.b[0]"), "Array<Null>");
+    assert_eq!(query(&shape, "$.null"), "Array<Integer<unsigned>>");
+    assert!(shape.never_matches("$.a.d").unwrap());
+    assert!(shape.never_matches("$.c[*].d").unwrap());
+}
@@ -1,9 +1,9 @@
 #![allow(missing_docs)]
 
-use std::str::FromStr;
+use std::{collections::BTreeSet, str::FromStr};
 
 use insta::assert_snapshot;
-use json_shape::{JsonShape, error::Error};
+use json_shape::{JsonShape, NumberKind, error::Error};
 
 #[test]
 fn parse_nil_shape() {
@@ -34,7 +34,15 @@ fn parse_number_shape() {
     let source = "123.456";
     let value = JsonShape::from_str(source).unwrap();
 
-    assert_eq!(value, JsonShape::Number { optional: false });
+    assert_eq!(
+        value,
+        JsonShape::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        }
+    );
 }
 
 #[test]
@@ -42,7 +50,15 @@ fn parse_string_shape() {
     let source = "\"this is a string\"";
     let value = JsonShape::from_str(source).unwrap();
 
-    assert_eq!(value, JsonShape::String { optional: false });
+    assert_eq!(
+        value,
+        JsonShape::String {
+            optional: false,
+            format: None,
+            length: None,
+            examples: None
+        }
+    );
 }
 
 #[test]
@@ -68,9 +84,24 @@ fn parse_array_shape() {
         value,
         JsonShape::Tuple {
             elements: vec![
-                JsonShape::String { optional: false },
-                JsonShape::Number { optional: false },
-                JsonShape::Number { optional: false },
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                },
+                JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                },
                 JsonShape::Bool { optional: false },
                 JsonShape::Bool { optional: false },
                 JsonShape::Null,
@@ -90,7 +121,12 @@ fn parse_map_shape() {
     let content = [
         (
             String::from_str("a").unwrap(),
-            JsonShape::Number { optional: false },
+            JsonShape::Number {
+                optional: false,
+                kind: NumberKind::Unsigned,
+                range: None,
+                examples: None,
+            },
         ),
         (
             String::from_str("b").unwrap(),
@@ -102,12 +138,14 @@ fn parse_map_shape() {
         value,
         JsonShape::Object {
             content,
-            optional: false
+            optional: false,
+            absent: Some(BTreeSet::new())
         }
     );
 }
 
 #[test]
+#[allow(clippy::too_many_lines)]
 fn complex_json_shape() {
     let source = r#"{
         "str": "this is a string",
@@ -141,16 +179,32 @@ fn complex_json_shape() {
                 (
                     "array".to_string(),
                     JsonShape::Array {
-                        r#type: Box::new(JsonShape::Number { optional: false }),
-                        optional: false
+                        r#type: Box::new(JsonShape::Number {
+                            optional: false,
+                            kind: NumberKind::Unsigned,
+                            range: None,
+                            examples: None,
+                        }),
+                        optional: false,
+                        length: None
                     }
                 ),
                 (
                     "tuple".to_string(),
                     JsonShape::Tuple {
                         elements: vec![
-                            JsonShape::Number { optional: false },
-                            JsonShape::String { optional: false },
+                            JsonShape::Number {
+                                optional: false,
+                                kind: NumberKind::Unsigned,
+                                range: None,
+                                examples: None,
+                            },
+                            JsonShape::String {
+                                optional: false,
+                                format: None,
+                                length: None,
+                                examples: None
+                            },
                             JsonShape::Bool { optional: false }
                         ],
                         optional: false
@@ -161,14 +215,32 @@ fn complex_json_shape() {
                     JsonShape::Array {
                         r#type: Box::new(JsonShape::Object {
                             content: [
-                                ("a".to_string(), JsonShape::String { optional: false }),
-                                ("c".to_string(), JsonShape::Number { optional: true }),
-                                ("b".to_string(), JsonShape::Bool { optional: true })
+                                (
+                                    "a".to_string(),
+                                    JsonShape::String {
+                                        optional: false,
+                                        format: None,
+                                        length: None,
+                                        examples: None
+                                    }
+                                ),
+                                (
+                                    "c".to_string(),
+                                    JsonShape::Number {
+                                        optional: false,
+                                        kind: NumberKind::Unsigned,
+                                        range: None,
+                                        examples: None,
+                                    }
+                                ),
+                                ("b".to_string(), JsonShape::Bool { optional: false })
                             ]
                             .into(),
-                            optional: false
+                            optional: false,
+                            absent: Some(["b".to_string(), "c".to_string()].into())
                         }),
-                        optional: false
+                        optional: false,
+                        length: None
                     }
                 ),
                 (
@@ -180,19 +252,53 @@ fn complex_json_shape() {
                     "map".to_string(),
                     JsonShape::Object {
                         content: [
-                            ("a".to_string(), JsonShape::String { optional: false }),
-                            ("c".to_string(), JsonShape::Number { optional: false })
+                            (
+                                "a".to_string(),
+                                JsonShape::String {
+                                    optional: false,
+                                    format: None,
+                                    length: None,
+                                    examples: None
+                                }
+                            ),
+                            (
+                                "c".to_string(),
+                                JsonShape::Number {
+                                    optional: false,
+                                    kind: NumberKind::Unsigned,
+                                    range: None,
+                                    examples: None,
+                                }
+                            )
                         ]
                         .into(),
-                        optional: false
+                        optional: false,
+                        absent: Some(BTreeSet::new())
                     }
                 ),
                 ("nil".to_string(), JsonShape::Null,),
-                ("number".to_string(), JsonShape::Number { optional: false },),
-                ("str".to_string(), JsonShape::String { optional: false })
+                (
+                    "number".to_string(),
+                    JsonShape::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
+                ),
+                (
+                    "str".to_string(),
+                    JsonShape::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
+                )
             ]
             .into(),
-            optional: false
+            optional: false,
+            absent: Some(BTreeSet::new())
         }
     );
     assert_snapshot!(value);
@@ -205,7 +311,7 @@ fn parse_duplicated_keys_object_shape() {
 
     assert_eq!(
         err.to_string(),
-        "invalid type `Boolean`. Expected `Number`."
+        "invalid type `Boolean`. Expected `Integer<unsigned>`."
     );
 }
 
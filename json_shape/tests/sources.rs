@@ -1,6 +1,8 @@
 #![allow(missing_docs)]
 
-use json_shape::JsonShape;
+use std::collections::BTreeSet;
+
+use json_shape::{InferenceOptions, JsonShape, LiteralValue, NumberKind, ShapeStatistics};
 
 #[test]
 fn from_sources_manages_option_in_tuples() {
@@ -13,9 +15,24 @@ fn from_sources_manages_option_in_tuples() {
         shape,
         JsonShape::Tuple {
             elements: vec![
-                JsonShape::Number { optional: false },
-                JsonShape::Number { optional: false },
-                JsonShape::String { optional: false },
+                JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                },
+                JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                },
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                },
                 JsonShape::Bool { optional: true },
             ],
             optional: false
@@ -34,9 +51,24 @@ fn from_sources_manages_option_in_tuples_2() {
         shape,
         JsonShape::Tuple {
             elements: vec![
-                JsonShape::Number { optional: false },
-                JsonShape::Number { optional: true },
-                JsonShape::String { optional: false },
+                JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                },
+                JsonShape::Number {
+                    optional: true,
+                    kind: NumberKind::Unsigned,
+                    range: None,
+                    examples: None,
+                },
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None
+                },
                 JsonShape::Bool { optional: false },
             ],
             optional: false
@@ -57,13 +89,357 @@ fn from_sources_tuples_become_array_when_differ() {
             r#type: Box::new(JsonShape::OneOf {
                 variants: [
                     JsonShape::Bool { optional: false },
-                    JsonShape::Number { optional: false },
-                    JsonShape::String { optional: false }
+                    JsonShape::Number {
+                        optional: false,
+                        kind: NumberKind::Unsigned,
+                        range: None,
+                        examples: None,
+                    },
+                    JsonShape::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
                 ]
                 .into(),
                 optional: false
             }),
-            optional: false
+            optional: false,
+            length: None
+        }
+    );
+}
+
+#[test]
+fn from_sources_keeps_string_format_only_when_all_match() {
+    let source_1 =
+        r#"{"id": "67e55044-10b1-426f-9247-bb680e5fe0c8", "at": "2024-01-31T10:00:00Z"}"#;
+    let source_2 = r#"{"id": "not an uuid", "at": "2024-02-01T08:30:00.5-03:00"}"#;
+
+    let shape = JsonShape::from_sources(&[source_1.to_string(), source_2.to_string()]).unwrap();
+
+    assert_eq!(
+        shape.to_string(),
+        "Object{at: String<date-time>, id: String}"
+    );
+}
+
+#[test]
+fn from_sources_with_literal_limit() {
+    let sources = [
+        r#"{"status": "active", "tier": 1, "name": "a"}"#.to_string(),
+        r#"{"status": "disabled", "tier": 2, "name": "b"}"#.to_string(),
+        r#"{"status": null, "tier": 1, "name": "c"}"#.to_string(),
+    ];
+    let options = InferenceOptions::default().with_literal_limit(2);
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert_eq!(
+        shape,
+        JsonShape::Object {
+            content: [
+                (
+                    "name".to_string(),
+                    JsonShape::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None
+                    }
+                ),
+                (
+                    "status".to_string(),
+                    JsonShape::Literal {
+                        values: [
+                            LiteralValue::String("active".to_string()),
+                            LiteralValue::String("disabled".to_string())
+                        ]
+                        .into(),
+                        optional: true
+                    }
+                ),
+                (
+                    "tier".to_string(),
+                    JsonShape::Literal {
+                        values: [LiteralValue::Integer(1), LiteralValue::Integer(2)].into(),
+                        optional: false
+                    }
+                ),
+            ]
+            .into(),
+            optional: false,
+            absent: Some(BTreeSet::new())
         }
     );
+    assert_eq!(
+        JsonShape::from_sources(&sources).unwrap().to_string(),
+        "Object{name: String, status: Option<String>, tier: Integer<unsigned>}"
+    );
+}
+
+#[test]
+fn from_sources_with_number_ranges() {
+    let sources = [
+        r#"{"age": 30, "id": 1}"#.to_string(),
+        r#"{"age": 120, "id": -7}"#.to_string(),
+        r#"{"id": 3}"#.to_string(),
+    ];
+    let options = InferenceOptions::default().with_number_ranges();
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert_eq!(
+        shape.to_string(),
+        "Object{age?: Integer<unsigned>[30..=120], id: Integer<signed>[-7..=3]}"
+    );
+    assert_eq!(
+        JsonShape::from_sources(&sources).unwrap().to_string(),
+        "Object{age?: Integer<unsigned>, id: Integer<signed>}"
+    );
+}
+
+#[test]
+fn from_sources_refines_empty_arrays() {
+    let sources = [
+        r#"{"tags": []}"#.to_string(),
+        r#"{"tags": ["a"]}"#.to_string(),
+        r#"{"tags": []}"#.to_string(),
+    ];
+
+    let shape = JsonShape::from_sources(&sources[..1]).unwrap();
+    assert_eq!(shape.to_string(), "Object{tags: Array<Unknown>}");
+
+    let shape = JsonShape::from_sources(&sources).unwrap();
+    assert_eq!(shape.to_string(), "Object{tags: Array<String>}");
+    assert!(shape.is_superset(&sources[0]));
+}
+
+#[test]
+fn from_sources_with_length_bounds() {
+    let sources = [
+        r#"{"name": "Naomi", "tags": ["a", "b"]}"#.to_string(),
+        r#"{"name": "Bo", "tags": []}"#.to_string(),
+    ];
+    let options = InferenceOptions::default().with_length_bounds();
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert_eq!(
+        shape.to_string(),
+        "Object{name: String[2..=5], tags: Array<String[1..=1]>[0..=2]}"
+    );
+}
+
+#[test]
+fn from_sources_with_length_bounds_and_literal_limit() {
+    let sources = [
+        r#"{"name": "Naomi", "code": 7}"#.to_string(),
+        r#"{"name": "Bo", "code": 12}"#.to_string(),
+        r#"{"name": "Alexandra", "code": 300}"#.to_string(),
+    ];
+    let options = InferenceOptions::default()
+        .with_literal_limit(1)
+        .with_length_bounds()
+        .with_number_ranges();
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert_eq!(
+        shape.to_string(),
+        "Object{code: Integer<unsigned>[7..=300], name: String[2..=9]}"
+    );
+}
+
+#[test]
+fn from_sources_with_map_threshold() {
+    let sources = [
+        r#"{"users": {"6f9619ff-8b86-d011-b42d-00cf4fc964ff": {"name": "Naomi"}}}"#.to_string(),
+        r#"{"users": {"67e55044-10b1-426f-9247-bb680e5fe0c8": {"name": "Bo", "age": 3}}}"#
+            .to_string(),
+    ];
+    let options = InferenceOptions::default().with_map_threshold(2);
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert_eq!(
+        shape.to_string(),
+        "Object{users: Map<String<uuid>, Object{age?: Integer<unsigned>, name: String}>}"
+    );
+}
+
+#[test]
+fn from_sources_with_recursion() {
+    let sources = [
+        r#"{"name": "/", "children": [{"name": "a.txt"}, {"name": "src", "children": [{"name": "lib.rs"}]}]}"#
+            .to_string(),
+        r#"{"name": "/", "children": [{"name": "b", "children": [{"name": "c", "children": [{"name": "d.rs"}]}]}]}"#
+            .to_string(),
+    ];
+    let options = InferenceOptions::default().with_recursion();
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert_eq!(
+        shape.to_string(),
+        "Recursive<Root: Object{children?: Array<Ref<Root>>, name: String}>"
+    );
+}
+
+#[test]
+fn from_sources_with_discriminators() {
+    let sources = [
+        r#"[{"type": "click", "x": 1}, {"type": "scroll", "delta": 2.5}]"#.to_string(),
+        r#"[{"type": "click", "x": 2, "y": 3}, {"type": "close"}]"#.to_string(),
+    ];
+    let options = InferenceOptions::default().with_discriminators();
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert_eq!(
+        shape.to_string(),
+        r#"Array<Tagged<type>["click": Object{x: Integer<unsigned>, y?: Integer<unsigned>} | "close": Object{} | "scroll": Object{delta: Number}]>"#
+    );
+}
+
+#[test]
+fn from_sources_with_discriminators_merges_objects_into_their_variant() {
+    let tagged = r#"[{"type": "click", "x": 1}, {"type": "scroll", "dy": 2}]"#.to_string();
+    let single = r#"[{"type": "click", "x": 2.5}]"#.to_string();
+    let options = InferenceOptions::default().with_discriminators();
+
+    for sources in [[tagged.clone(), single.clone()], [single, tagged]] {
+        let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+        assert_eq!(
+            shape.to_string(),
+            r#"Array<Tagged<type>["click": Object{x: Number} | "scroll": Object{dy: Integer<unsigned>}]>"#
+        );
+    }
+}
+
+#[test]
+fn from_sources_with_discriminators_keeps_literal_tags_of_merged_variants() {
+    let sources = [
+        r#"[{"type": "click", "x": 1}, {"type": "scroll", "dy": 2}]"#.to_string(),
+        r#"[{"kind": "close"}]"#.to_string(),
+    ];
+    let options = InferenceOptions::default()
+        .with_discriminators()
+        .with_literal_limit(4);
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert_eq!(
+        shape.to_string(),
+        r#"Array<Object{dy?: Literal[2], kind?: Literal["close"], type?: Literal["click" | "scroll"], x?: Literal[1]}>"#
+    );
+}
+
+#[test]
+fn from_sources_with_examples() {
+    let sources = [
+        r#"{"name": "alice", "age": 30}"#.to_string(),
+        r#"{"name": "bob", "age": 30}"#.to_string(),
+        r#"{"name": "carol", "age": null}"#.to_string(),
+    ];
+    let options = InferenceOptions::default().with_examples(2);
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    let JsonShape::Object { content, .. } = &shape else {
+        panic!("expected Object, got {shape}");
+    };
+    let examples = |key: &str| match &content[key] {
+        JsonShape::Number { examples, .. } | JsonShape::String { examples, .. } => {
+            examples.as_ref().unwrap().iter().collect::<Vec<_>>()
+        }
+        shape => panic!("expected Number or String, got {shape}"),
+    };
+    assert_eq!(
+        shape.to_string(),
+        "Object{age: Option<Integer<unsigned>>, name: String}"
+    );
+    assert_eq!(examples("name"), [r#""alice""#, r#""bob""#]);
+    assert_eq!(examples("age"), ["30"]);
+}
+
+#[test]
+fn from_sources_with_examples_and_literal_limit() {
+    let sources = [
+        r#"{"name": "alice", "age": 30}"#.to_string(),
+        r#"{"name": "bob", "age": 41}"#.to_string(),
+        r#"{"name": "carol", "age": 52}"#.to_string(),
+    ];
+    let options = InferenceOptions::default()
+        .with_literal_limit(1)
+        .with_examples(2);
+
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    let JsonShape::Object { content, .. } = &shape else {
+        panic!("expected Object, got {shape}");
+    };
+    let examples = |key: &str| match &content[key] {
+        JsonShape::Number { examples, .. } | JsonShape::String { examples, .. } => {
+            examples.as_ref().unwrap().iter().collect::<Vec<_>>()
+        }
+        shape => panic!("expected Number or String, got {shape}"),
+    };
+    assert_eq!(
+        shape.to_string(),
+        "Object{age: Integer<unsigned>, name: String}"
+    );
+    assert_eq!(examples("name"), [r#""alice""#, r#""bob""#]);
+    assert_eq!(examples("age"), ["30", "41"]);
+}
+
+#[test]
+fn representative_sources_from_sources() {
+    let sources = [
+        r#"{"name": "alice", "age": 30}"#.to_string(),
+        r#"{"name": "bob", "age": 31}"#.to_string(),
+        r#"{"name": "carol"}"#.to_string(),
+        r#"{"name": "dan", "age": null}"#.to_string(),
+    ];
+    let options = InferenceOptions::default().with_examples(2);
+
+    let representatives = JsonShape::representative_sources(&sources, &options).unwrap();
+
+    assert_eq!(
+        representatives
+            .iter()
+            .map(|(shape, source)| (shape.to_string(), source.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            (
+                "Object{age: Integer<unsigned>, name: String}".to_string(),
+                sources[0].as_str()
+            ),
+            ("Object{name: String}".to_string(), sources[2].as_str()),
+            (
+                "Object{age: Null, name: String}".to_string(),
+                sources[3].as_str()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn statistics_from_sources() {
+    let sources = [
+        r#"{"id": 1, "tags": ["a"], "parent": null}"#.to_string(),
+        r#"{"id": 2, "tags": [], "parent": 1}"#.to_string(),
+        r#"{"id": 3, "tags": ["b", "c"]}"#.to_string(),
+    ];
+
+    let statistics = ShapeStatistics::from_sources(&sources, &InferenceOptions::default()).unwrap();
+
+    assert_eq!(statistics.shape, JsonShape::from_sources(&sources).unwrap());
+    assert!(statistics.paths["$.id"].is_required());
+    assert_eq!(statistics.paths["$.parent"].samples, 3);
+    assert_eq!(statistics.paths["$.parent"].present, 2);
+    assert_eq!(statistics.paths["$.parent"].null, 1);
+    assert_eq!(statistics.paths["$.tags[*]"].types["String"], 3);
 }
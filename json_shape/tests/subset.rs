@@ -2,34 +2,86 @@
 
 use std::str::FromStr;
 
-use json_shape::{IsSubset, JsonShape, error::Error};
+use json_shape::{InferenceOptions, IsSubset, JsonShape, NumberKind, error::Error};
 
 #[test]
 fn json_is_subset_of_json_shape() {
     let shape = JsonShape::Object {
         content: [
-            ("name".to_string(), JsonShape::String { optional: false }),
-            ("surname".to_string(), JsonShape::String { optional: false }),
+            (
+                "name".to_string(),
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ),
+            (
+                "surname".to_string(),
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ),
             (
                 "middle name".to_string(),
-                JsonShape::String { optional: true },
+                JsonShape::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ),
+            (
+                "age".to_string(),
+                JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
             ),
-            ("age".to_string(), JsonShape::Number { optional: false }),
             (
                 "id".to_string(),
                 JsonShape::OneOf {
                     variants: [
                         JsonShape::Object {
                             content: [
-                                ("number".to_string(), JsonShape::Number { optional: false }),
-                                ("state".to_string(), JsonShape::String { optional: false }),
+                                (
+                                    "number".to_string(),
+                                    JsonShape::Number {
+                                        optional: false,
+                                        kind: NumberKind::Float,
+                                        range: None,
+                                        examples: None,
+                                    },
+                                ),
+                                (
+                                    "state".to_string(),
+                                    JsonShape::String {
+                                        optional: false,
+                                        format: None,
+                                        length: None,
+                                        examples: None,
+                                    },
+                                ),
                             ]
                             .into(),
                             optional: false,
+                            absent: None,
                         },
                         JsonShape::Array {
-                            r#type: Box::new(JsonShape::Number { optional: false }),
+                            r#type: Box::new(JsonShape::Number {
+                                optional: false,
+                                kind: NumberKind::Float,
+                                range: None,
+                                examples: None,
+                            }),
                             optional: false,
+                            length: None,
                         },
                     ]
                     .into(),
@@ -39,6 +91,7 @@ fn json_is_subset_of_json_shape() {
         ]
         .into(),
         optional: false,
+        absent: None,
     };
 
     let json_1 = r#"{
@@ -61,28 +114,80 @@ fn json_is_subset_of_json_shape() {
 fn json_is_subset_of_json_shape_checked() {
     let shape = JsonShape::Object {
         content: [
-            ("name".to_string(), JsonShape::String { optional: false }),
-            ("surname".to_string(), JsonShape::String { optional: false }),
+            (
+                "name".to_string(),
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ),
+            (
+                "surname".to_string(),
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ),
             (
                 "middle name".to_string(),
-                JsonShape::String { optional: true },
+                JsonShape::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ),
+            (
+                "age".to_string(),
+                JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
             ),
-            ("age".to_string(), JsonShape::Number { optional: false }),
             (
                 "id".to_string(),
                 JsonShape::OneOf {
                     variants: [
                         JsonShape::Object {
                             content: [
-                                ("number".to_string(), JsonShape::Number { optional: false }),
-                                ("state".to_string(), JsonShape::String { optional: false }),
+                                (
+                                    "number".to_string(),
+                                    JsonShape::Number {
+                                        optional: false,
+                                        kind: NumberKind::Float,
+                                        range: None,
+                                        examples: None,
+                                    },
+                                ),
+                                (
+                                    "state".to_string(),
+                                    JsonShape::String {
+                                        optional: false,
+                                        format: None,
+                                        length: None,
+                                        examples: None,
+                                    },
+                                ),
                             ]
                             .into(),
                             optional: false,
+                            absent: None,
                         },
                         JsonShape::Array {
-                            r#type: Box::new(JsonShape::Number { optional: false }),
+                            r#type: Box::new(JsonShape::Number {
+                                optional: false,
+                                kind: NumberKind::Float,
+                                range: None,
+                                examples: None,
+                            }),
                             optional: false,
+                            length: None,
                         },
                     ]
                     .into(),
@@ -92,6 +197,7 @@ fn json_is_subset_of_json_shape_checked() {
         ]
         .into(),
         optional: false,
+        absent: None,
     };
 
     let json_1 = r#"{
@@ -108,31 +214,84 @@ fn json_is_subset_of_json_shape_checked() {
 }
 
 #[test]
+#[allow(clippy::too_many_lines)]
 fn json_is_subset_of_json_shape_checked_json_error() {
     let shape = JsonShape::Object {
         content: [
-            ("name".to_string(), JsonShape::String { optional: false }),
-            ("surname".to_string(), JsonShape::String { optional: false }),
+            (
+                "name".to_string(),
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ),
+            (
+                "surname".to_string(),
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ),
             (
                 "middle name".to_string(),
-                JsonShape::String { optional: true },
+                JsonShape::String {
+                    optional: true,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
+            ),
+            (
+                "age".to_string(),
+                JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
             ),
-            ("age".to_string(), JsonShape::Number { optional: false }),
             (
                 "id".to_string(),
                 JsonShape::OneOf {
                     variants: [
                         JsonShape::Object {
                             content: [
-                                ("number".to_string(), JsonShape::Number { optional: false }),
-                                ("state".to_string(), JsonShape::String { optional: false }),
+                                (
+                                    "number".to_string(),
+                                    JsonShape::Number {
+                                        optional: false,
+                                        kind: NumberKind::Float,
+                                        range: None,
+                                        examples: None,
+                                    },
+                                ),
+                                (
+                                    "state".to_string(),
+                                    JsonShape::String {
+                                        optional: false,
+                                        format: None,
+                                        length: None,
+                                        examples: None,
+                                    },
+                                ),
                             ]
                             .into(),
                             optional: false,
+                            absent: None,
                         },
                         JsonShape::Array {
-                            r#type: Box::new(JsonShape::Number { optional: false }),
+                            r#type: Box::new(JsonShape::Number {
+                                optional: false,
+                                kind: NumberKind::Float,
+                                range: None,
+                                examples: None,
+                            }),
                             optional: false,
+                            length: None,
                         },
                     ]
                     .into(),
@@ -142,6 +301,7 @@ fn json_is_subset_of_json_shape_checked_json_error() {
         ]
         .into(),
         optional: false,
+        absent: None,
     };
 
     let json_1 = r#"{
@@ -162,3 +322,139 @@ fn json_is_subset_of_json_shape_checked_json_error() {
         }
     );
 }
+
+#[test]
+fn json_outside_learned_bounds_is_not_subset() {
+    let sources = [
+        r#"{"tags": ["a", "b"], "age": 30}"#.to_string(),
+        r#"{"tags": ["c"], "age": 45}"#.to_string(),
+    ];
+    let options = InferenceOptions::default()
+        .with_length_bounds()
+        .with_number_ranges();
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert!(
+        shape
+            .is_superset_checked(r#"{"tags": ["d", "e"], "age": 40}"#)
+            .unwrap()
+    );
+    assert!(
+        !shape
+            .is_superset_checked(r#"{"tags": ["a", "b", "c"], "age": 40}"#)
+            .unwrap()
+    );
+    assert!(
+        !shape
+            .is_superset_checked(r#"{"tags": [], "age": 40}"#)
+            .unwrap()
+    );
+    assert!(
+        !shape
+            .is_superset_checked(r#"{"tags": ["a"], "age": 99}"#)
+            .unwrap()
+    );
+    assert!(
+        JsonShape::from_sources(&sources)
+            .unwrap()
+            .is_superset_checked(r#"{"tags": ["a", "b", "c"], "age": 99}"#)
+            .unwrap()
+    );
+}
+
+#[test]
+fn deeper_json_is_subset_of_recursive_shape() {
+    let sources = [
+        r#"{"body": "a", "replies": [{"body": "b", "replies": [{"body": "c", "replies": []}]}]}"#
+            .to_string(),
+    ];
+    let options = InferenceOptions::default().with_recursion();
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert!(
+        shape
+            .is_superset_checked(
+                r#"{"body": "x", "replies": [{"body": "y", "replies": [{"body": "z", "replies": [{"body": "w", "replies": []}]}]}]}"#
+            )
+            .unwrap()
+    );
+    assert!(
+        !shape
+            .is_superset_checked(r#"{"body": "x", "replies": [{"body": 1, "replies": []}]}"#)
+            .unwrap()
+    );
+}
+
+#[test]
+fn json_with_unknown_tag_is_not_subset_of_tagged_union() {
+    let sources = [r#"[{"type": "click", "x": 1}, {"type": "close"}]"#.to_string()];
+    let options = InferenceOptions::default().with_discriminators();
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert!(
+        shape
+            .is_superset_checked(r#"[{"type": "close"}, {"type": "click", "x": 3}]"#)
+            .unwrap()
+    );
+    assert!(
+        !shape
+            .is_superset_checked(r#"[{"type": "close"}, {"type": "open", "x": 3}]"#)
+            .unwrap()
+    );
+    assert!(
+        !shape
+            .is_superset_checked(r#"[{"type": "close"}, {"type": "click", "y": 3}]"#)
+            .unwrap()
+    );
+}
+
+#[test]
+fn json_with_unknown_tag_and_known_fields_is_not_subset_of_tagged_union() {
+    let sources = [r#"[{"type": "click", "x": 1}, {"type": "close"}]"#.to_string()];
+    let options = InferenceOptions::default().with_discriminators();
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    assert!(!shape.is_superset(r#"[{"type": "open", "x": 1}]"#));
+    assert!(!shape.is_superset(r#"[{"type": "open"}]"#));
+    assert!(!shape.is_superset(r#"[{"type": "click", "x": 1}, {"type": "open", "x": 2}]"#));
+    assert!(shape.is_superset(r#"[{"type": "click", "x": 1}, {"type": "click", "x": 2}]"#));
+    assert!(shape.is_superset(r#"[{"type": "close"}]"#));
+}
+
+#[test]
+fn json_without_key_is_not_subset_of_shape_with_null_value() {
+    let nullable = JsonShape::from_sources(&[
+        r#"{"id": 1, "parent": 2}"#.to_string(),
+        r#"{"id": 2, "parent": null}"#.to_string(),
+    ])
+    .unwrap();
+    let absent = JsonShape::from_sources(&[
+        r#"{"id": 1, "parent": 2}"#.to_string(),
+        r#"{"id": 2}"#.to_string(),
+    ])
+    .unwrap();
+
+    assert!(nullable.is_superset(r#"{"id": 3, "parent": null}"#));
+    assert!(!nullable.is_superset(r#"{"id": 3}"#));
+    assert!(absent.is_superset(r#"{"id": 3}"#));
+    assert!(!absent.is_superset(r#"{"id": 3, "parent": null}"#));
+}
+
+#[test]
+fn json_without_optional_key_is_subset_of_shape_persisted_without_absent_keys() {
+    let shape: JsonShape = serde_json::from_str(
+        r#"{"Object": {"content": {
+            "id": {"Number": {"optional": false, "kind": "Unsigned"}},
+            "parent": {"Number": {"optional": true, "kind": "Unsigned"}}
+        }, "optional": false}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        shape.to_string(),
+        "Object{id: Integer<unsigned>, parent?: Option<Integer<unsigned>>}"
+    );
+    assert!(shape.is_superset(r#"{"id": 3}"#));
+    assert!(shape.is_superset(r#"{"id": 3, "parent": null}"#));
+    assert!(!shape.is_superset(r#"{"parent": 1}"#));
+}
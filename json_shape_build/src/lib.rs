@@ -9,7 +9,10 @@ use std::{
 use checksum::crc32::Crc32;
 use codegen::{Field, Scope, Variant};
 use convert_case::{Case, Casing};
-use json_shape::JsonShape;
+use json_shape::{
+    Examples, InferenceOptions, JsonShape, LiteralValue, NumberKind, NumberRange, ShapeVisitor,
+    StringFormat, walk_shape,
+};
 
 #[cfg(test)]
 mod test;
@@ -69,10 +72,37 @@ macro_rules! include_json_shape {
 ///
 /// # Errors
 /// - failed to write json shape file
-#[allow(clippy::missing_panics_doc)]
 pub fn compile_json(
     collection_name: &'static str,
     jsons: &[impl AsRef<Path>],
+) -> std::io::Result<String> {
+    compile_json_with_options(collection_name, jsons, &InferenceOptions::default())
+}
+
+/// `.json` shape compiling, using the specified [`InferenceOptions`].
+///
+/// Literal unions inferred by [`InferenceOptions::with_literal_limit`] generate Rust enums instead of `String`.
+/// Examples kept by [`InferenceOptions::with_examples`] are added to the doc comments of numbers and strings.
+///
+/// In your `build.rs`:
+/// ```rust
+/// use json_shape::InferenceOptions;
+///
+/// let dir = env!("CARGO_MANIFEST_DIR");
+/// let extension = "fixture/object.json";
+/// let path = std::path::Path::new(dir).join(extension);
+/// let options = InferenceOptions::default().with_literal_limit(8);
+/// json_shape_build::compile_json_with_options("collection_literals", &[path], &options);
+/// ```
+///
+///
+/// # Errors
+/// - failed to write json shape file
+#[allow(clippy::missing_panics_doc)]
+pub fn compile_json_with_options(
+    collection_name: &'static str,
+    jsons: &[impl AsRef<Path>],
+    options: &InferenceOptions,
 ) -> std::io::Result<String> {
     for path in jsons {
         println!("cargo:rerun-if-changed={}", path.as_ref().display());
@@ -84,7 +114,8 @@ pub fn compile_json(
         .map(std::fs::read_to_string)
         .collect::<Result<Vec<String>, std::io::Error>>()?;
 
-    let shape = json_shape::JsonShape::from_sources(&sources).map_err(std::io::Error::other)?;
+    let shape = json_shape::JsonShape::from_sources_with_options(&sources, options)
+        .map_err(std::io::Error::other)?;
     let target: PathBuf =
         std::env::var_os("OUT_DIR").map_or_else(|| std::env::current_dir().unwrap(), PathBuf::from);
     let target = target.join(collection_name).with_extension("gen.shape.rs");
@@ -100,11 +131,17 @@ pub fn compile_json(
     Ok(scope.to_string())
 }
 
+#[allow(clippy::too_many_lines)]
 pub(crate) fn first_pass(shape: &JsonShape, scope: &mut Scope) -> io::Result<()> {
     match &shape {
         json_shape::JsonShape::Null => {
             scope.new_type_alias("Void", "()").vis("pub");
         }
+        json_shape::JsonShape::Unknown => {
+            scope
+                .new_type_alias("Unknown", "serde_json::Value")
+                .vis("pub");
+        }
         json_shape::JsonShape::Bool { optional } => {
             if *optional {
                 scope
@@ -114,97 +151,156 @@ pub(crate) fn first_pass(shape: &JsonShape, scope: &mut Scope) -> io::Result<()>
                 scope.new_type_alias("Bool", "bool").vis("pub");
             }
         }
-        json_shape::JsonShape::Number { optional } => {
-            if *optional {
-                scope
-                    .new_type_alias("NullableNumber", "Option<f64>")
-                    .vis("pub");
+        json_shape::JsonShape::Number {
+            optional,
+            kind,
+            range,
+            ..
+        } => {
+            let (name, r#type) = (number_name(*kind), number_type(*kind, range.as_ref()));
+            let alias = if *optional {
+                scope.new_type_alias(format!("Nullable{name}"), format!("Option<{type}>"))
             } else {
-                scope.new_type_alias("Number", "f64").vis("pub");
+                scope.new_type_alias(name, r#type)
+            };
+            alias.vis("pub");
+            if let Some(doc) = shape_doc(shape) {
+                alias.doc(doc);
             }
         }
-        json_shape::JsonShape::String { optional } => {
-            if *optional {
-                scope
-                    .new_type_alias("NullableStr", "Option<String>")
-                    .vis("pub");
+        json_shape::JsonShape::String {
+            optional, format, ..
+        } => {
+            let name = format!("Str{}", format.map(string_format_name).unwrap_or_default());
+            let alias = if *optional {
+                scope.new_type_alias(format!("Nullable{name}"), "Option<String>")
             } else {
-                scope.new_type_alias("Str", "String").vis("pub");
+                scope.new_type_alias(name, "String")
+            };
+            alias.vis("pub");
+            if let Some(doc) = shape_doc(shape) {
+                alias.doc(doc);
             }
         }
         json_shape::JsonShape::Array {
             r#type: inner,
             optional,
+            ..
         } => {
             let name = shape_name(shape);
             create_array(scope, &name, *optional, inner);
-            create_subtype(scope, inner)?;
+            create_subtype(scope, inner);
         }
         json_shape::JsonShape::Object { content, .. } => {
             let name = shape_name(shape);
-            create_object(scope, &name, content);
+            create_object(scope, &name, content, &shape.absent_keys());
             for inner in content.values() {
-                create_subtype(scope, inner)?;
+                create_subtype(scope, inner);
             }
         }
         json_shape::JsonShape::OneOf { variants, .. } => {
             let name = shape_name(shape);
-            create_enum(scope, &name, variants);
+            create_enum(scope, &name, one_of_variants(variants), None);
             for inner in variants {
-                create_subtype(scope, inner)?;
+                create_subtype(scope, inner);
             }
         }
         json_shape::JsonShape::Tuple { elements, optional } => {
             let name = shape_name(shape);
             create_tuple(scope, &name, *optional, elements);
             for inner in elements {
-                create_subtype(scope, inner)?;
+                create_subtype(scope, inner);
+            }
+        }
+        json_shape::JsonShape::Literal { values, .. } => {
+            if is_string_literal(values) {
+                let name = shape_name(shape);
+                create_literal_enum(scope, &name, values);
+            } else {
+                first_pass(&shape.clone().widen_literal(), scope)?;
             }
         }
+        json_shape::JsonShape::Map {
+            r#type: inner,
+            optional,
+            ..
+        } => {
+            let name = shape_name(shape);
+            create_map(scope, &name, *optional, inner);
+            create_subtype(scope, inner);
+        }
+        json_shape::JsonShape::Recursive { .. } | json_shape::JsonShape::Tagged { .. } => {
+            create_subtype(scope, shape);
+        }
+        json_shape::JsonShape::Ref { .. } => {}
     }
     Ok(())
 }
 
-fn create_subtype(scope: &mut Scope, shape: &JsonShape) -> io::Result<()> {
-    match shape {
-        json_shape::JsonShape::Array {
-            r#type: inner,
-            optional: _,
-        } => {
-            create_subtype(scope, inner)?;
-        }
-        json_shape::JsonShape::Object { content, .. } => {
-            let name = shape_name(shape);
-            create_object(scope, &name, content);
-            for inner in content.values() {
-                create_subtype(scope, inner)?;
+fn create_subtype(scope: &mut Scope, shape: &JsonShape) {
+    Subtypes { scope }.visit_shape(shape, "$");
+}
+
+/// Creates the types of the shapes nested in the shape created by [`first_pass`]
+struct Subtypes<'a> {
+    scope: &'a mut Scope,
+}
+
+impl ShapeVisitor for Subtypes<'_> {
+    fn visit_shape(&mut self, shape: &JsonShape, path: &str) {
+        match shape {
+            JsonShape::Object { content, .. } => {
+                let name = shape_name(shape);
+                create_object(self.scope, &name, content, &shape.absent_keys());
             }
-        }
-        json_shape::JsonShape::OneOf { variants, .. } => {
-            let name = shape_name(shape);
-            create_enum(scope, &name, variants);
-            for inner in variants {
-                create_subtype(scope, inner)?;
+            JsonShape::OneOf { variants, .. } => {
+                let name = shape_name(shape);
+                create_enum(self.scope, &name, one_of_variants(variants), None);
             }
-        }
-        json_shape::JsonShape::Tuple {
-            elements,
-            optional: _,
-        } => {
-            for inner in elements {
-                create_subtype(scope, inner)?;
+            JsonShape::Literal { values, .. } => {
+                if is_string_literal(values) {
+                    let name = shape_name(shape);
+                    create_literal_enum(self.scope, &name, values);
+                } else {
+                    self.visit_shape(&shape.clone().widen_literal(), path);
+                }
             }
+            JsonShape::Recursive { name, shape } => {
+                if let JsonShape::Object { content, .. } = &**shape {
+                    create_object(self.scope, name, content, &shape.absent_keys());
+                    walk_shape(self, shape, path);
+                } else {
+                    self.visit_shape(shape, path);
+                }
+                return;
+            }
+            JsonShape::Tagged { tag, variants, .. } => {
+                let name = shape_name(shape);
+                create_enum(self.scope, &name, tagged_variants(variants), Some(tag));
+                for inner in variants.values() {
+                    if !matches!(inner, JsonShape::Object { content, .. } if content.is_empty()) {
+                        self.visit_shape(inner, path);
+                    }
+                }
+                return;
+            }
+            _ => {}
         }
-        _ => {}
+        walk_shape(self, shape, path);
     }
-    Ok(())
 }
 
 fn is_rust_keyword(s: &str) -> bool {
     syn::parse_str::<syn::Ident>(s).is_err()
 }
 
-fn create_object(scope: &mut Scope, name: &str, content: &BTreeMap<String, json_shape::JsonShape>) {
+/// Keys that may be absent are generated as `#[serde(default)]` fields, wrapped in an `Option` when they are never `null`.
+fn create_object(
+    scope: &mut Scope,
+    name: &str,
+    content: &BTreeMap<String, json_shape::JsonShape>,
+    absent: &BTreeSet<String>,
+) {
     let struct_data = scope
         .new_struct(name)
         .vis("pub")
@@ -213,30 +309,113 @@ fn create_object(scope: &mut Scope, name: &str, content: &BTreeMap<String, json_
         .derive("serde::Serialize")
         .derive("serde::Deserialize");
     for (name, r#type) in content {
-        let mut field = Field::new(name.to_case(Case::Snake), shape_representation(r#type));
+        let is_absent = absent.contains(name);
+        let representation = if is_absent && !r#type.is_optional() {
+            format!("Option<{}>", shape_representation(r#type))
+        } else {
+            shape_representation(r#type)
+        };
+        let mut field = Field::new(name.to_case(Case::Snake), representation);
+        if is_absent && r#type.is_optional() {
+            field.annotation("#[serde(default)]");
+        } else if is_absent {
+            field.annotation("#[serde(default, skip_serializing_if = \"Option::is_none\")]");
+        }
         assert!(
             !is_rust_keyword(&field.name),
             "rust keywords are not valid identifiers"
         );
         field.vis("pub");
+        if let Some(doc) = shape_doc(r#type) {
+            field.doc(doc);
+        }
         struct_data.push_field(field);
     }
 }
 
-fn create_enum(scope: &mut Scope, name: &str, variants: &BTreeSet<json_shape::JsonShape>) {
-    let variants = variants
+/// Enums discriminated by a `tag` key are generated as internally tagged enums, `#[serde(tag = "...")]`.
+fn create_enum(scope: &mut Scope, name: &str, variants: Vec<Variant>, tag: Option<&str>) {
+    let enum_data = scope
+        .new_enum(name)
+        .vis("pub")
+        .derive("Debug")
+        .derive("Clone")
+        .derive("serde::Serialize")
+        .derive("serde::Deserialize");
+    if let Some(tag) = tag {
+        enum_data.r#macro(format!("#[serde(tag = {tag:?})]"));
+    }
+    for var in variants {
+        enum_data.push_variant(var);
+    }
+}
+
+fn one_of_variants(variants: &BTreeSet<json_shape::JsonShape>) -> Vec<Variant> {
+    variants
         .iter()
         .map(|shape| (shape_name(shape), shape_representation(shape)))
         .map(|(name, representation)| {
             let mut var = Variant::new(name);
             var.tuple(&representation);
             var
+        })
+        .collect()
+}
+
+/// Variants without other keys than the tag are generated as unit variants.
+fn tagged_variants(variants: &BTreeMap<String, json_shape::JsonShape>) -> Vec<Variant> {
+    let mut names = BTreeSet::new();
+    variants
+        .iter()
+        .enumerate()
+        .map(|(i, (value, shape))| {
+            let mut name = value.to_case(Case::Pascal);
+            if name.is_empty() || is_rust_keyword(&name) || names.contains(&name) {
+                name = format!("Variant{i}");
+            }
+            names.insert(name.clone());
+            let mut var = Variant::new(name);
+            var.annotation(format!("#[serde(rename = {value:?})]"));
+            if !matches!(shape, JsonShape::Object { content, .. } if content.is_empty()) {
+                var.tuple(shape_representation(shape));
+            }
+            var
+        })
+        .collect()
+}
+
+/// Only literal unions made exclusively of strings are generated as fieldless enums.
+fn is_string_literal(values: &BTreeSet<LiteralValue>) -> bool {
+    values.iter().all(LiteralValue::is_string)
+}
+
+fn create_literal_enum(scope: &mut Scope, name: &str, values: &BTreeSet<LiteralValue>) {
+    let mut names = BTreeSet::new();
+    let variants = values
+        .iter()
+        .filter_map(|value| match value {
+            LiteralValue::String(value) => Some(value),
+            LiteralValue::Integer(_) => None,
+        })
+        .enumerate()
+        .map(|(i, value)| {
+            let mut name = value.to_case(Case::Pascal);
+            if name.is_empty() || is_rust_keyword(&name) || names.contains(&name) {
+                name = format!("Variant{i}");
+            }
+            names.insert(name.clone());
+            let mut var = Variant::new(name);
+            var.annotation(format!("#[serde(rename = {value:?})]"));
+            var
         });
     let enum_data = scope
         .new_enum(name)
         .vis("pub")
         .derive("Debug")
         .derive("Clone")
+        .derive("Copy")
+        .derive("PartialEq")
+        .derive("Eq")
         .derive("serde::Serialize")
         .derive("serde::Deserialize");
     for var in variants {
@@ -246,9 +425,24 @@ fn create_enum(scope: &mut Scope, name: &str, variants: &BTreeSet<json_shape::Js
 
 fn create_array(scope: &mut Scope, name: &str, optional: bool, r#type: &JsonShape) {
     let target = if optional {
-        format!("Option<Vec<{}>>", shape_representation(r#type))
+        format!("Option<Vec<{}>>", element_representation(r#type))
     } else {
-        format!("Vec<{}>", shape_representation(r#type))
+        format!("Vec<{}>", element_representation(r#type))
+    };
+    scope.new_type_alias(name, target).vis("pub");
+}
+
+fn create_map(scope: &mut Scope, name: &str, optional: bool, r#type: &JsonShape) {
+    let target = if optional {
+        format!(
+            "Option<std::collections::BTreeMap<String, {}>>",
+            element_representation(r#type)
+        )
+    } else {
+        format!(
+            "std::collections::BTreeMap<String, {}>",
+            element_representation(r#type)
+        )
     };
     scope.new_type_alias(name, target).vis("pub");
 }
@@ -268,9 +462,11 @@ fn create_tuple(scope: &mut Scope, name: &str, optional: bool, elements: &[json_
     scope.new_type_alias(name, target).vis("pub");
 }
 
+#[allow(clippy::too_many_lines)]
 fn shape_representation(shape: &JsonShape) -> String {
     match shape {
         JsonShape::Null => "()".to_string(),
+        JsonShape::Unknown => "serde_json::Value".to_string(),
         JsonShape::Bool { optional } => {
             if *optional {
                 "Option<bool>".to_string()
@@ -278,36 +474,42 @@ fn shape_representation(shape: &JsonShape) -> String {
                 "bool".to_string()
             }
         }
-        JsonShape::Number { optional } => {
+        JsonShape::Number {
+            optional,
+            kind,
+            range,
+            ..
+        } => {
+            let r#type = number_type(*kind, range.as_ref());
             if *optional {
-                "Option<f64>".to_string()
+                format!("Option<{type}>")
             } else {
-                "f64".to_string()
+                r#type.to_string()
             }
         }
-        JsonShape::String { optional } => {
+        JsonShape::String { optional, .. } => {
             if *optional {
                 "Option<String>".to_string()
             } else {
                 "String".to_string()
             }
         }
-        JsonShape::Array { r#type, optional } => {
-            let sub_shape = shape_representation(r#type);
+        JsonShape::Array {
+            r#type, optional, ..
+        } => {
+            let sub_shape = element_representation(r#type);
             if *optional {
                 format!("Option<Vec<{sub_shape}>>")
             } else {
                 format!("Vec<{sub_shape}>")
             }
         }
-        JsonShape::Object {
-            content: _,
-            optional,
-        }
+        JsonShape::Object { optional, .. }
         | JsonShape::OneOf {
             variants: _,
             optional,
-        } => {
+        }
+        | JsonShape::Tagged { optional, .. } => {
             let name = shape_name(shape);
             if *optional {
                 format!("Option<{name}>")
@@ -327,12 +529,153 @@ fn shape_representation(shape: &JsonShape) -> String {
                 format!("({sub_shapes})")
             }
         }
+        JsonShape::Literal { values, optional } => {
+            if !is_string_literal(values) {
+                return shape_representation(&shape.clone().widen_literal());
+            }
+            let name = shape_name(shape);
+            if *optional {
+                format!("Option<{name}>")
+            } else {
+                name
+            }
+        }
+        JsonShape::Map {
+            r#type, optional, ..
+        } => {
+            let sub_shape = element_representation(r#type);
+            if *optional {
+                format!("Option<std::collections::BTreeMap<String, {sub_shape}>>")
+            } else {
+                format!("std::collections::BTreeMap<String, {sub_shape}>")
+            }
+        }
+        JsonShape::Recursive { name, shape } => {
+            if !shape.is_object() {
+                return shape_representation(shape);
+            }
+            if shape.is_optional() {
+                format!("Option<{name}>")
+            } else {
+                name.clone()
+            }
+        }
+        JsonShape::Ref { name, optional } => {
+            if *optional {
+                format!("Option<Box<{name}>>")
+            } else {
+                format!("Box<{name}>")
+            }
+        }
     }
 }
 
+/// Collections already store their items on the heap, so references to a recursive struct are not boxed in them.
+fn element_representation(shape: &JsonShape) -> String {
+    match shape {
+        JsonShape::Ref {
+            name,
+            optional: false,
+        } => name.clone(),
+        JsonShape::Ref {
+            name,
+            optional: true,
+        } => format!("Option<{name}>"),
+        _ => shape_representation(shape),
+    }
+}
+
+/// Narrowest Rust integer type able to hold the inferred `range`, eg `u8` for `0..=120`.
+fn number_type(kind: NumberKind, range: Option<&NumberRange>) -> &'static str {
+    let range = range.filter(|_| kind.is_integer());
+    if let Some((min, max)) = range.and_then(|range| range.min.as_i64().zip(range.max.as_i64())) {
+        return match (min, max) {
+            (0.., ..=0xFF) => "u8",
+            (0.., ..=0xFFFF) => "u16",
+            (0.., ..=0xFFFF_FFFF) => "u32",
+            (0.., _) => "u64",
+            (-0x80.., ..=0x7F) => "i8",
+            (-0x8000.., ..=0x7FFF) => "i16",
+            (-0x8000_0000.., ..=0x7FFF_FFFF) => "i32",
+            _ => "i64",
+        };
+    }
+    match kind {
+        NumberKind::Unsigned | NumberKind::LargeUnsigned => "u64",
+        NumberKind::Signed => "i64",
+        NumberKind::Float => "f64",
+    }
+}
+
+const fn number_name(kind: NumberKind) -> &'static str {
+    match kind {
+        NumberKind::Unsigned => "UnsignedInteger",
+        NumberKind::Signed => "SignedInteger",
+        NumberKind::LargeUnsigned => "LargeUnsignedInteger",
+        NumberKind::Float => "Number",
+    }
+}
+
+const fn string_format_name(format: StringFormat) -> &'static str {
+    match format {
+        StringFormat::DateTime => "DateTime",
+        StringFormat::Date => "Date",
+        StringFormat::Uuid => "Uuid",
+        StringFormat::Email => "Email",
+        StringFormat::Uri => "Uri",
+        StringFormat::Ipv4 => "Ipv4",
+        StringFormat::Ipv6 => "Ipv6",
+    }
+}
+
+/// Format of a string field, looking through arrays and maps, eg `Vec<String>` of `date-time`.
+const fn string_format(shape: &JsonShape) -> Option<StringFormat> {
+    match shape {
+        JsonShape::String { format, .. } => *format,
+        JsonShape::Array { r#type, .. } | JsonShape::Map { r#type, .. } => string_format(r#type),
+        _ => None,
+    }
+}
+
+/// Examples of a number or string field, looking through arrays and maps like [`string_format`].
+fn shape_examples(shape: &JsonShape) -> Option<&Examples> {
+    match shape {
+        JsonShape::Number { examples, .. } | JsonShape::String { examples, .. } => {
+            examples.as_deref().filter(|examples| !examples.is_empty())
+        }
+        JsonShape::Array { r#type, .. } | JsonShape::Map { r#type, .. } => shape_examples(r#type),
+        _ => None,
+    }
+}
+
+/// Doc comment of a field or alias, with its string format and examples, eg ``Examples: `"alice"`, `"bob"` ``.
+fn shape_doc(shape: &JsonShape) -> Option<String> {
+    let format = string_format(shape).map(|format| format!("Format: `{format}`"));
+    let examples = shape_examples(shape).map(|examples| {
+        let examples = examples
+            .iter()
+            .map(|example| format!("`{example}`"))
+            .collect::<Vec<_>>();
+        format!("Examples: {}", examples.join(", "))
+    });
+    match (format, examples) {
+        (Some(format), Some(examples)) => Some(format!("{format}\n{examples}")),
+        (format, examples) => format.or(examples),
+    }
+}
+
+fn crc_name(sub_shapes: &str) -> String {
+    let mut crc = Crc32::new();
+    crc.update(sub_shapes.as_bytes());
+    crc.finalize();
+    format!("{:X}", crc.getsum())
+}
+
+#[allow(clippy::too_many_lines)]
 fn shape_name(shape: &JsonShape) -> String {
     match shape {
         JsonShape::Null => "Null".to_string(),
+        JsonShape::Unknown => "Unknown".to_string(),
         JsonShape::Bool { optional } => {
             if *optional {
                 "OptionalBool".to_string()
@@ -340,39 +683,55 @@ fn shape_name(shape: &JsonShape) -> String {
                 "Bool".to_string()
             }
         }
-        JsonShape::Number { optional } => {
+        JsonShape::Number { optional, kind, .. } => {
+            let name = number_name(*kind);
             if *optional {
-                "OptionalNumber".to_string()
+                format!("Optional{name}")
             } else {
-                "Number".to_string()
+                name.to_string()
             }
         }
-        JsonShape::String { optional } => {
+        JsonShape::String {
+            optional, format, ..
+        } => {
+            let name = format.map(string_format_name).unwrap_or_default();
             if *optional {
-                "OptionalStr".to_string()
+                format!("OptionalStr{name}")
             } else {
-                "Str".to_string()
+                format!("Str{name}")
             }
         }
-        JsonShape::Array { r#type, optional } => {
+        JsonShape::Array {
+            r#type, optional, ..
+        }
+        | JsonShape::Map {
+            r#type, optional, ..
+        } => {
+            let collection = if shape.is_map() { "MapOf" } else { "ArrayOf" };
             let sub_shape = shape_name(r#type);
             if *optional {
-                format!("OptionalArrayOf{sub_shape}")
+                format!("Optional{collection}{sub_shape}")
             } else {
-                format!("ArrayOf{sub_shape}")
+                format!("{collection}{sub_shape}")
             }
         }
-        JsonShape::Object { content, optional } => {
+        JsonShape::Object {
+            content, optional, ..
+        } => {
+            let absent = shape.absent_keys();
             let len = content.len();
             let sub_shapes = content
-                .values()
-                .map(shape_name)
+                .iter()
+                .map(|(key, value)| {
+                    if absent.contains(key) {
+                        format!("Absent{}", shape_name(value))
+                    } else {
+                        shape_name(value)
+                    }
+                })
                 .collect::<String>()
                 .to_case(convert_case::Case::Pascal);
-            let mut crc = Crc32::new();
-            crc.update(sub_shapes.as_bytes());
-            crc.finalize();
-            let name = format!("{:X}", crc.getsum());
+            let name = crc_name(&sub_shapes);
 
             if *optional {
                 format!("OptionalStruct{len}Crc{name}")
@@ -387,10 +746,7 @@ fn shape_name(shape: &JsonShape) -> String {
                 .map(shape_name)
                 .collect::<String>()
                 .to_case(convert_case::Case::Pascal);
-            let mut crc = Crc32::new();
-            crc.update(sub_shapes.as_bytes());
-            crc.finalize();
-            let name = format!("{:X}", crc.getsum());
+            let name = crc_name(&sub_shapes);
             if *optional {
                 format!("OptionalEnum{len}Crc{name}")
             } else {
@@ -404,10 +760,7 @@ fn shape_name(shape: &JsonShape) -> String {
                 .map(shape_representation)
                 .collect::<String>()
                 .to_case(convert_case::Case::Pascal);
-            let mut crc = Crc32::new();
-            crc.update(sub_shapes.as_bytes());
-            crc.finalize();
-            let name = format!("{:X}", crc.getsum());
+            let name = crc_name(&sub_shapes);
 
             if *optional {
                 format!("OptionalTuple{len}Crc{name}")
@@ -415,5 +768,54 @@ fn shape_name(shape: &JsonShape) -> String {
                 format!("Tuple{len}Crc{name}")
             }
         }
+        JsonShape::Literal { values, optional } => {
+            if !is_string_literal(values) {
+                return shape_name(&shape.clone().widen_literal());
+            }
+            let len = values.len();
+            let sub_shapes = values.iter().map(ToString::to_string).collect::<String>();
+            let name = crc_name(&sub_shapes);
+
+            if *optional {
+                format!("OptionalLiteral{len}Crc{name}")
+            } else {
+                format!("Literal{len}Crc{name}")
+            }
+        }
+        JsonShape::Recursive { name, shape } => {
+            if !shape.is_object() {
+                return shape_name(shape);
+            }
+            if shape.is_optional() {
+                format!("Optional{name}")
+            } else {
+                name.clone()
+            }
+        }
+        JsonShape::Ref { name, optional } => {
+            if *optional {
+                format!("Optional{name}")
+            } else {
+                name.clone()
+            }
+        }
+        JsonShape::Tagged {
+            tag,
+            variants,
+            optional,
+        } => {
+            let len = variants.len();
+            let sub_shapes = variants
+                .iter()
+                .flat_map(|(value, variant)| [value.clone(), shape_name(variant)])
+                .collect::<String>();
+            let name = crc_name(&format!("{tag}{sub_shapes}"));
+
+            if *optional {
+                format!("OptionalTagged{len}Crc{name}")
+            } else {
+                format!("Tagged{len}Crc{name}")
+            }
+        }
     }
 }
@@ -1,5 +1,9 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use codegen::Scope;
-use json_shape::JsonShape;
+use json_shape::{
+    InferenceOptions, JsonShape, KeyPattern, LiteralValue, NumberKind, NumberRange, StringFormat,
+};
 
 use crate::first_pass;
 
@@ -17,7 +21,12 @@ fn from_null() {
 
 #[test]
 fn from_number() {
-    let shape = JsonShape::Number { optional: false };
+    let shape = JsonShape::Number {
+        optional: false,
+        kind: NumberKind::Float,
+        range: None,
+        examples: None,
+    };
 
     let mut scope = Scope::new();
 
@@ -29,7 +38,12 @@ fn from_number() {
 
 #[test]
 fn from_opt_number() {
-    let shape = JsonShape::Number { optional: true };
+    let shape = JsonShape::Number {
+        optional: true,
+        kind: NumberKind::Float,
+        range: None,
+        examples: None,
+    };
 
     let mut scope = Scope::new();
 
@@ -65,7 +79,12 @@ fn from_opt_bool() {
 
 #[test]
 fn from_str() {
-    let shape = JsonShape::String { optional: false };
+    let shape = JsonShape::String {
+        optional: false,
+        format: None,
+        length: None,
+        examples: None,
+    };
 
     let mut scope = Scope::new();
 
@@ -77,7 +96,12 @@ fn from_str() {
 
 #[test]
 fn from_opt_str() {
-    let shape = JsonShape::String { optional: true };
+    let shape = JsonShape::String {
+        optional: true,
+        format: None,
+        length: None,
+        examples: None,
+    };
 
     let mut scope = Scope::new();
 
@@ -90,8 +114,14 @@ fn from_opt_str() {
 #[test]
 fn from_array() {
     let shape = JsonShape::Array {
-        r#type: Box::new(JsonShape::Number { optional: false }),
+        r#type: Box::new(JsonShape::Number {
+            optional: false,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        }),
         optional: false,
+        length: None,
     };
 
     let mut scope = Scope::new();
@@ -105,8 +135,14 @@ fn from_array() {
 #[test]
 fn from_opt_array() {
     let shape = JsonShape::Array {
-        r#type: Box::new(JsonShape::Number { optional: true }),
+        r#type: Box::new(JsonShape::Number {
+            optional: true,
+            kind: NumberKind::Float,
+            range: None,
+            examples: None,
+        }),
         optional: true,
+        length: None,
     };
 
     let mut scope = Scope::new();
@@ -120,18 +156,45 @@ fn from_opt_array() {
     );
 }
 
+#[test]
+fn from_array_of_unknown() {
+    let shape = JsonShape::Array {
+        r#type: Box::new(JsonShape::Unknown),
+        optional: false,
+        length: None,
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert_eq!(file, "pub type ArrayOfUnknown = Vec<serde_json::Value>;");
+}
+
 #[test]
 fn from_array_of_enum() {
     let shape = JsonShape::Array {
         r#type: Box::new(JsonShape::OneOf {
             variants: [
-                JsonShape::Number { optional: false },
-                JsonShape::String { optional: false },
+                JsonShape::Number {
+                    optional: false,
+                    kind: NumberKind::Float,
+                    range: None,
+                    examples: None,
+                },
+                JsonShape::String {
+                    optional: false,
+                    format: None,
+                    length: None,
+                    examples: None,
+                },
             ]
             .into(),
             optional: false,
         }),
         optional: false,
+        length: None,
     };
 
     let mut scope = Scope::new();
@@ -155,15 +218,39 @@ pub enum Enum2CrcB0F27C9A {
 fn from_tuple() {
     let shape = JsonShape::Tuple {
         elements: [
-            JsonShape::Number { optional: false },
-            JsonShape::String { optional: false },
+            JsonShape::Number {
+                optional: false,
+                kind: NumberKind::Float,
+                range: None,
+                examples: None,
+            },
+            JsonShape::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None,
+            },
             JsonShape::OneOf {
                 variants: [
-                    JsonShape::Number { optional: false },
+                    JsonShape::Number {
+                        optional: false,
+                        kind: NumberKind::Float,
+                        range: None,
+                        examples: None,
+                    },
                     JsonShape::Object {
-                        content: [("key".to_string(), JsonShape::Number { optional: false })]
-                            .into(),
+                        content: [(
+                            "key".to_string(),
+                            JsonShape::Number {
+                                optional: false,
+                                kind: NumberKind::Float,
+                                range: None,
+                                examples: None,
+                            },
+                        )]
+                        .into(),
                         optional: false,
+                        absent: Some(BTreeSet::new()),
                     },
                 ]
                 .into(),
@@ -200,10 +287,350 @@ pub struct Struct1Crc913C1A62 {
 #[should_panic(expected = "rust keywords are not valid identifiers")]
 fn fail_for_object_keyword_field() {
     let shape = JsonShape::Object {
-        content: [("type".to_string(), JsonShape::String { optional: false })].into(),
+        content: [(
+            "type".to_string(),
+            JsonShape::String {
+                optional: false,
+                format: None,
+                length: None,
+                examples: None,
+            },
+        )]
+        .into(),
+        optional: false,
+        absent: Some(BTreeSet::new()),
+    };
+
+    let mut scope = Scope::new();
+    first_pass(&shape, &mut scope).unwrap();
+}
+
+#[test]
+fn from_integers() {
+    let shape = JsonShape::Tuple {
+        elements: [
+            JsonShape::Number {
+                optional: false,
+                kind: NumberKind::Unsigned,
+                range: None,
+                examples: None,
+            },
+            JsonShape::Number {
+                optional: true,
+                kind: NumberKind::Signed,
+                range: None,
+                examples: None,
+            },
+            JsonShape::Number {
+                optional: false,
+                kind: NumberKind::LargeUnsigned,
+                range: None,
+                examples: None,
+            },
+        ]
+        .into(),
+        optional: false,
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert_eq!(
+        file,
+        "pub type Tuple3Crc26EDD468 = (u64, Option<i64>, u64);"
+    );
+}
+
+#[test]
+fn from_formatted_string() {
+    let shape = JsonShape::String {
+        optional: true,
+        format: Some(StringFormat::DateTime),
+        length: None,
+        examples: None,
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert_eq!(
+        file,
+        "/// Format: `date-time`\npub type NullableStrDateTime = Option<String>;"
+    );
+}
+
+#[test]
+fn from_object_with_formatted_string() {
+    let shape = JsonShape::Object {
+        content: [(
+            "created_at".to_string(),
+            JsonShape::String {
+                optional: false,
+                format: Some(StringFormat::DateTime),
+                length: None,
+                examples: None,
+            },
+        )]
+        .into(),
+        optional: false,
+        absent: Some(BTreeSet::new()),
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert!(file.contains("    /// Format: `date-time`\n    pub created_at: String,"));
+}
+
+#[test]
+fn from_object_with_absent_keys() {
+    let shape = JsonShape::Object {
+        content: [
+            ("absent".to_string(), JsonShape::Bool { optional: false }),
+            ("nullable".to_string(), JsonShape::Bool { optional: true }),
+            ("both".to_string(), JsonShape::Bool { optional: true }),
+        ]
+        .into(),
+        optional: false,
+        absent: Some(["absent".to_string(), "both".to_string()].into()),
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert!(file.contains(
+        "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub absent: Option<bool>,"
+    ));
+    assert!(file.contains("    #[serde(default)]\n    pub both: Option<bool>,"));
+    assert!(file.contains("\n    pub nullable: Option<bool>,"));
+    assert!(!file.contains("#[serde(default)]\n    pub nullable"));
+}
+
+#[test]
+fn from_object_with_examples() {
+    let options = InferenceOptions::default().with_examples(2);
+    let sources = [
+        r#"{"id": "67e55044-10b1-426f-9247-bb680e5fe0c8", "tags": ["a"], "age": 30}"#.to_string(),
+        r#"{"id": "2c5ea4c0-4067-11e9-8bad-9b1deb4d3b7d", "tags": ["b", "c"], "age": 30}"#
+            .to_string(),
+    ];
+    let shape = JsonShape::from_sources_with_options(&sources, &options).unwrap();
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert!(file.contains("    /// Examples: `30`\n    pub age: u64,"));
+    assert!(file.contains(
+        "    /// Format: `uuid`\n    /// Examples: `\"67e55044-10b1-426f-9247-bb680e5fe0c8\"`, `\"2c5ea4c0-4067-11e9-8bad-9b1deb4d3b7d\"`\n    pub id: String,"
+    ));
+    assert!(file.contains("    /// Examples: `\"a\"`, `\"b\"`\n    pub tags: Vec<String>,"));
+}
+
+#[test]
+fn from_string_literal() {
+    let shape = JsonShape::Literal {
+        values: [
+            LiteralValue::String("active".to_string()),
+            LiteralValue::String("on hold".to_string()),
+            LiteralValue::String("self".to_string()),
+        ]
+        .into(),
+        optional: false,
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert_eq!(
+        file,
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Literal3Crc1D85D7A {
+    #[serde(rename = \"active\")]
+    Active,
+    #[serde(rename = \"on hold\")]
+    OnHold,
+    #[serde(rename = \"self\")]
+    Variant2,
+}"
+    );
+}
+
+#[test]
+fn from_integer_literal() {
+    let shape = JsonShape::Literal {
+        values: [LiteralValue::Integer(1), LiteralValue::Integer(2)].into(),
+        optional: true,
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert_eq!(file, "pub type NullableUnsignedInteger = Option<u64>;");
+}
+
+#[test]
+fn from_ranged_integers_narrows_type() {
+    let ranged = |min: i64, max: i64| JsonShape::Number {
+        optional: false,
+        kind: NumberKind::Signed,
+        range: Some(NumberRange::new(&min.into()).union(&NumberRange::new(&max.into()))),
+        examples: None,
+    };
+    let shape = JsonShape::Object {
+        content: [
+            ("age".to_string(), ranged(0, 120)),
+            ("delta".to_string(), ranged(-300, 300)),
+            ("id".to_string(), ranged(0, 70_000)),
+        ]
+        .into(),
+        optional: false,
+        absent: Some(BTreeSet::new()),
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert!(file.contains("pub age: u8,"));
+    assert!(file.contains("pub delta: i16,"));
+    assert!(file.contains("pub id: u32,"));
+}
+
+#[test]
+fn from_map() {
+    let shape = JsonShape::Object {
+        content: [(
+            "scores".to_string(),
+            JsonShape::Map {
+                r#type: Box::new(JsonShape::Bool { optional: false }),
+                key: Some(KeyPattern::Integer),
+                optional: true,
+            },
+        )]
+        .into(),
+        optional: false,
+        absent: Some(BTreeSet::new()),
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert!(file.contains("pub scores: Option<std::collections::BTreeMap<String, bool>>,"));
+}
+
+#[test]
+fn from_recursive() {
+    let shape = JsonShape::Recursive {
+        name: "Comment".to_string(),
+        shape: Box::new(JsonShape::Object {
+            content: [
+                (
+                    "body".to_string(),
+                    JsonShape::String {
+                        optional: false,
+                        format: None,
+                        length: None,
+                        examples: None,
+                    },
+                ),
+                (
+                    "parent".to_string(),
+                    JsonShape::Ref {
+                        name: "Comment".to_string(),
+                        optional: true,
+                    },
+                ),
+                (
+                    "replies".to_string(),
+                    JsonShape::Array {
+                        r#type: Box::new(JsonShape::Ref {
+                            name: "Comment".to_string(),
+                            optional: false,
+                        }),
+                        optional: false,
+                        length: None,
+                    },
+                ),
+            ]
+            .into(),
+            optional: false,
+            absent: Some(BTreeSet::new()),
+        }),
+    };
+
+    let mut scope = Scope::new();
+
+    first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert!(file.contains("pub struct Comment {"));
+    assert!(file.contains("pub parent: Option<Box<Comment>>,"));
+    assert!(file.contains("pub replies: Vec<Comment>,"));
+}
+
+#[test]
+fn from_tagged() {
+    let shape = JsonShape::Array {
+        r#type: Box::new(JsonShape::Tagged {
+            tag: "type".to_string(),
+            variants: [
+                (
+                    "click".to_string(),
+                    JsonShape::Object {
+                        content: [(
+                            "x".to_string(),
+                            JsonShape::Number {
+                                optional: false,
+                                kind: NumberKind::Unsigned,
+                                range: None,
+                                examples: None,
+                            },
+                        )]
+                        .into(),
+                        optional: false,
+                        absent: Some(BTreeSet::new()),
+                    },
+                ),
+                (
+                    "close".to_string(),
+                    JsonShape::Object {
+                        content: BTreeMap::default(),
+                        optional: false,
+                        absent: Some(BTreeSet::new()),
+                    },
+                ),
+            ]
+            .into(),
+            optional: false,
+        }),
         optional: false,
+        length: None,
     };
 
     let mut scope = Scope::new();
+
     first_pass(&shape, &mut scope).unwrap();
+
+    let file = scope.to_string();
+    assert!(file.contains("#[serde(tag = \"type\")]\npub enum Tagged2Crc"));
+    assert!(file.contains("#[serde(rename = \"click\")]\n    Click(Struct1Crc"));
+    assert!(file.contains("#[serde(rename = \"close\")]\n    Close,"));
+    assert!(file.contains("pub x: u64,"));
 }
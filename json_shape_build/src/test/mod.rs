@@ -1,28 +1,30 @@
 mod from_shape;
 static LARGE_OBJECT: &str = "#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct Struct9Crc477AE068 {
-    pub array: Vec<f64>,
-    pub array_of_maps: Vec<Struct3CrcFDD0C6E5>,
+pub struct Struct9Crc1FCA3959 {
+    pub array: Vec<u64>,
+    pub array_of_maps: Vec<Struct3CrcA7E7C39E>,
     pub bool_false: bool,
     pub bool_true: bool,
-    pub map: Struct2CrcDEC58CB4,
+    pub map: Struct2CrcE21E1769,
     pub nil: (),
     pub number: f64,
     pub str: String,
-    pub tuple: (f64, String, bool),
+    pub tuple: (u64, String, bool),
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct Struct3CrcFDD0C6E5 {
+pub struct Struct3CrcA7E7C39E {
     pub a: String,
+    #[serde(default, skip_serializing_if = \"Option::is_none\")]
     pub b: Option<bool>,
-    pub c: Option<f64>,
+    #[serde(default, skip_serializing_if = \"Option::is_none\")]
+    pub c: Option<u64>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct Struct2CrcDEC58CB4 {
+pub struct Struct2CrcE21E1769 {
     pub a: String,
-    pub c: f64,
+    pub c: u64,
 }";
 
 #[test]
@@ -53,14 +55,14 @@ fn codegen_oneof() {
     assert_eq!(
         file,
         "#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct Struct1CrcB3DA869A {
-    pub a: Enum3CrcEFC15C8A,
+pub struct Struct1Crc1C5714B {
+    pub a: Enum3CrcA67EF18A,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub enum Enum3CrcEFC15C8A {
+pub enum Enum3CrcA67EF18A {
     Bool(bool),
-    Number(f64),
+    UnsignedInteger(u64),
     Struct1CrcF5B399AC(Struct1CrcF5B399AC),
 }
 